@@ -0,0 +1,715 @@
+//! Arithmetic over big numbers, which are the integers from `0` to `p^8 - 1` represented by
+//! digests. Addition, subtraction and multiplication wrap around modulo `p^8`.
+//!
+//! The operands are converted into 32-byte words, so that the arithmetic can be carried out by
+//! the `unsigned` gadgets, and the results are converted back into digests.
+
+use crate::gadgets::big_num::uint::{U256, U512};
+use crate::gadgets::big_num::word::{big_num_modulus, BigNumToWord, WordToBigNum};
+use crate::gadgets::big_num::{Word256, Word512, WORD256_SIZE, WORD512_SIZE};
+use crate::gadgets::bytes::{ByteAirRecord, ByteRecord};
+use crate::gadgets::unsigned::add::{AddWitness, Diff, Sum};
+use crate::gadgets::unsigned::less_than::LessThanWitness;
+use crate::gadgets::unsigned::mul::Product;
+use crate::gadgets::unsigned::UncheckedWord;
+use crate::lurk::zstore::DIGEST_SIZE;
+use num_traits::{ToBytes, Zero};
+use p3_air::AirBuilder;
+use p3_field::{AbstractField, PrimeField, PrimeField32};
+use sphinx_derive::AlignedBorrow;
+
+/// Returns the word `p^8 * flag`, where `flag` is boolean
+fn scaled_modulus<F: PrimeField, E: AbstractField>(flag: E) -> Word256<E> {
+    let modulus = big_num_modulus::<F>().to_le_bytes();
+    Word256::from_fn(|i| flag.clone() * E::from_canonical_u8(modulus[i]))
+}
+
+fn zero_extend<E: AbstractField>(word: Word256<E>) -> Word512<E> {
+    Word512::from_fn(|i| {
+        if i < WORD256_SIZE {
+            word[i].clone()
+        } else {
+            E::zero()
+        }
+    })
+}
+
+/// Witness for the addition of two big numbers modulo `p^8`
+#[derive(Clone, Debug, Default, AlignedBorrow)]
+#[repr(C)]
+pub struct BigNumAddWitness<T> {
+    lhs: BigNumToWord<T>,
+    rhs: BigNumToWord<T>,
+    sum: Sum<T, WORD256_SIZE>,
+    /// Whether the sum must be reduced by `p^8`
+    is_overflow: T,
+    result_word: Diff<T, WORD256_SIZE>,
+    result: WordToBigNum<T>,
+}
+
+impl<F: PrimeField32> BigNumAddWitness<F> {
+    pub fn populate(
+        &mut self,
+        lhs: &[F; DIGEST_SIZE],
+        rhs: &[F; DIGEST_SIZE],
+        byte_record: &mut impl ByteRecord,
+    ) -> [F; DIGEST_SIZE] {
+        let lhs = self.lhs.populate(lhs, byte_record);
+        let rhs = self.rhs.populate(rhs, byte_record);
+        let sum = self.sum.populate(&lhs, &rhs, byte_record);
+        let modulus = big_num_modulus::<F>();
+        let is_overflow = sum >= modulus;
+        self.is_overflow = F::from_bool(is_overflow);
+        let reduction = if is_overflow { modulus } else { U256::zero() };
+        let out = self.result_word.populate(&sum, &reduction, byte_record);
+        self.result.populate(&out, byte_record)
+    }
+}
+
+impl<Var> BigNumAddWitness<Var> {
+    pub fn eval<AB: AirBuilder<Var = Var>>(
+        &self,
+        builder: &mut AB,
+        lhs: &[AB::Expr; DIGEST_SIZE],
+        rhs: &[AB::Expr; DIGEST_SIZE],
+        record: &mut impl ByteAirRecord<AB::Expr>,
+        is_real: impl Into<AB::Expr>,
+    ) -> [AB::Var; DIGEST_SIZE]
+    where
+        Var: Copy + Into<AB::Expr>,
+        AB::F: PrimeField,
+    {
+        let is_real = is_real.into();
+        let lhs = self.lhs.eval(builder, lhs, record, is_real.clone());
+        let rhs = self.rhs.eval(builder, rhs, record, is_real.clone());
+        // Both operands are less than p^8 < 2^248, so the sum doesn't wrap around
+        let sum = self
+            .sum
+            .eval(builder, lhs.into(), rhs.into(), record, is_real.clone());
+
+        builder.when(is_real.clone()).assert_bool(self.is_overflow);
+        let reduction = scaled_modulus::<AB::F, AB::Expr>(self.is_overflow.into());
+        let out = self
+            .result_word
+            .eval(builder, sum.into(), reduction, record, is_real.clone());
+
+        // The conversion ensures the result is less than p^8, which can only happen when
+        // `is_overflow` is set correctly
+        self.result.eval(builder, &out.into(), record, is_real)
+    }
+}
+
+impl<T> BigNumAddWitness<T> {
+    pub const fn num_requires() -> usize {
+        BigNumToWord::<T>::num_requires() * 2
+            + Sum::<T, WORD256_SIZE>::num_requires()
+            + Diff::<T, WORD256_SIZE>::num_requires()
+            + WordToBigNum::<T>::num_requires()
+    }
+
+    pub const fn witness_size() -> usize {
+        size_of::<BigNumAddWitness<u8>>()
+    }
+}
+
+/// Witness for the subtraction of two big numbers modulo `p^8`
+#[derive(Clone, Debug, Default, AlignedBorrow)]
+#[repr(C)]
+pub struct BigNumSubWitness<T> {
+    lhs: BigNumToWord<T>,
+    rhs: BigNumToWord<T>,
+    diff: Diff<T, WORD256_SIZE>,
+    /// Whether the difference must be increased by `p^8`
+    is_underflow: T,
+    result_word: Sum<T, WORD256_SIZE>,
+    result: WordToBigNum<T>,
+}
+
+impl<F: PrimeField32> BigNumSubWitness<F> {
+    pub fn populate(
+        &mut self,
+        lhs: &[F; DIGEST_SIZE],
+        rhs: &[F; DIGEST_SIZE],
+        byte_record: &mut impl ByteRecord,
+    ) -> [F; DIGEST_SIZE] {
+        let lhs = self.lhs.populate(lhs, byte_record);
+        let rhs = self.rhs.populate(rhs, byte_record);
+        let diff = self.diff.populate(&lhs, &rhs, byte_record);
+        let is_underflow = lhs < rhs;
+        self.is_underflow = F::from_bool(is_underflow);
+        let correction = if is_underflow {
+            big_num_modulus::<F>()
+        } else {
+            U256::zero()
+        };
+        let out = self.result_word.populate(&diff, &correction, byte_record);
+        self.result.populate(&out, byte_record)
+    }
+}
+
+impl<Var> BigNumSubWitness<Var> {
+    pub fn eval<AB: AirBuilder<Var = Var>>(
+        &self,
+        builder: &mut AB,
+        lhs: &[AB::Expr; DIGEST_SIZE],
+        rhs: &[AB::Expr; DIGEST_SIZE],
+        record: &mut impl ByteAirRecord<AB::Expr>,
+        is_real: impl Into<AB::Expr>,
+    ) -> [AB::Var; DIGEST_SIZE]
+    where
+        Var: Copy + Into<AB::Expr>,
+        AB::F: PrimeField,
+    {
+        let is_real = is_real.into();
+        let lhs = self.lhs.eval(builder, lhs, record, is_real.clone());
+        let rhs = self.rhs.eval(builder, rhs, record, is_real.clone());
+        // On underflow, the difference wraps around to lhs - rhs + 2^256, so adding p^8 yields
+        // lhs - rhs + p^8 modulo 2^256
+        let diff = self
+            .diff
+            .eval(builder, lhs.into(), rhs.into(), record, is_real.clone());
+
+        builder.when(is_real.clone()).assert_bool(self.is_underflow);
+        let correction = scaled_modulus::<AB::F, AB::Expr>(self.is_underflow.into());
+        let out = self
+            .result_word
+            .eval(builder, diff.into(), correction, record, is_real.clone());
+
+        // The conversion ensures the result is less than p^8, which can only happen when
+        // `is_underflow` is set correctly
+        self.result.eval(builder, &out.into(), record, is_real)
+    }
+}
+
+impl<T> BigNumSubWitness<T> {
+    pub const fn num_requires() -> usize {
+        BigNumToWord::<T>::num_requires() * 2
+            + Diff::<T, WORD256_SIZE>::num_requires()
+            + Sum::<T, WORD256_SIZE>::num_requires()
+            + WordToBigNum::<T>::num_requires()
+    }
+
+    pub const fn witness_size() -> usize {
+        size_of::<BigNumSubWitness<u8>>()
+    }
+}
+
+/// Witness for the division of a 64-byte dividend by a non-zero 32-byte divisor, whose quotient
+/// is expected to fit in 32 bytes.
+#[derive(Clone, Debug, Default, AlignedBorrow)]
+#[repr(C)]
+struct WideDivRem<T> {
+    quotient: UncheckedWord<T, WORD256_SIZE>,
+    /// quotient * divisor
+    product: Product<T, WORD512_SIZE>,
+    remainder: UncheckedWord<T, WORD256_SIZE>,
+    /// remainder < divisor
+    r_lt_d: LessThanWitness<T, WORD256_SIZE>,
+}
+
+impl<F: PrimeField> WideDivRem<F> {
+    fn populate(
+        &mut self,
+        dividend: &U512,
+        divisor: &U256,
+        byte_record: &mut impl ByteRecord,
+    ) -> (U256, U256) {
+        let divisor_wide: U512 = divisor.resize();
+        let quotient_wide = *dividend / divisor_wide;
+        let quotient: U256 = quotient_wide.resize();
+        assert_eq!(
+            quotient.resize::<WORD512_SIZE>(),
+            quotient_wide,
+            "Quotient too large"
+        );
+        let remainder: U256 = (*dividend % divisor_wide).resize();
+
+        self.quotient
+            .assign_bytes(&quotient.to_le_bytes(), byte_record);
+        self.product
+            .populate(&quotient_wide, &divisor_wide, byte_record);
+        self.remainder
+            .assign_bytes(&remainder.to_le_bytes(), byte_record);
+        self.r_lt_d.populate(&remainder, divisor, byte_record);
+        (quotient, remainder)
+    }
+}
+
+impl<Var> WideDivRem<Var> {
+    fn eval<AB: AirBuilder<Var = Var>>(
+        &self,
+        builder: &mut AB,
+        dividend: &Word512<AB::Expr>,
+        divisor: &Word256<AB::Expr>,
+        record: &mut impl ByteAirRecord<AB::Expr>,
+        is_real: AB::Expr,
+    ) -> (Word256<AB::Var>, Word256<AB::Var>)
+    where
+        Var: Copy + Into<AB::Expr>,
+    {
+        let quotient = self.quotient.into_checked(record, is_real.clone());
+        // Both factors have 32 bytes, so the product doesn't wrap around
+        let product = self.product.eval(
+            builder,
+            &zero_extend(quotient.into()),
+            &zero_extend(divisor.clone()),
+            record,
+            is_real.clone(),
+        );
+        let remainder = self.remainder.into_checked(record, is_real.clone());
+
+        // quotient * divisor + remainder = dividend, without a final carry
+        let carry = AddWitness::<Var, WORD512_SIZE>::assert_add(
+            builder,
+            product.into(),
+            zero_extend(remainder.into()),
+            dividend.clone(),
+            is_real.clone(),
+        );
+        builder.when(is_real.clone()).assert_zero(carry);
+
+        self.r_lt_d
+            .assert_less_than(builder, &remainder.into(), divisor, record, is_real.clone());
+        (quotient, remainder)
+    }
+}
+
+impl<T> WideDivRem<T> {
+    const fn num_requires() -> usize {
+        WORD256_SIZE / 2
+            + Product::<T, WORD512_SIZE>::num_requires()
+            + WORD256_SIZE / 2
+            + LessThanWitness::<T, WORD256_SIZE>::num_requires()
+    }
+}
+
+/// Witness for the product of two words modulo a third one, where the operands are expected to
+/// be reduced so that the quotient fits in 32 bytes. The result is returned as a digest.
+#[derive(Clone, Debug, Default, AlignedBorrow)]
+#[repr(C)]
+struct ReducedProduct<T> {
+    product: Product<T, WORD512_SIZE>,
+    div_rem: WideDivRem<T>,
+    result: WordToBigNum<T>,
+}
+
+impl<F: PrimeField32> ReducedProduct<F> {
+    fn populate(
+        &mut self,
+        lhs: &U256,
+        rhs: &U256,
+        modulus: &U256,
+        byte_record: &mut impl ByteRecord,
+    ) -> [F; DIGEST_SIZE] {
+        let product =
+            self.product
+                .populate(&lhs.resize::<WORD512_SIZE>(), &rhs.resize(), byte_record);
+        let (_, remainder) = self.div_rem.populate(&product, modulus, byte_record);
+        self.result.populate(&remainder, byte_record)
+    }
+}
+
+impl<Var> ReducedProduct<Var> {
+    fn eval<AB: AirBuilder<Var = Var>>(
+        &self,
+        builder: &mut AB,
+        lhs: Word256<AB::Expr>,
+        rhs: Word256<AB::Expr>,
+        modulus: &Word256<AB::Expr>,
+        record: &mut impl ByteAirRecord<AB::Expr>,
+        is_real: AB::Expr,
+    ) -> [AB::Var; DIGEST_SIZE]
+    where
+        Var: Copy + Into<AB::Expr>,
+        AB::F: PrimeField,
+    {
+        // Both factors have 32 bytes, so the product doesn't wrap around
+        let product = self.product.eval(
+            builder,
+            &zero_extend(lhs),
+            &zero_extend(rhs),
+            record,
+            is_real.clone(),
+        );
+        let (_, remainder) =
+            self.div_rem
+                .eval(builder, &product.into(), modulus, record, is_real.clone());
+        self.result
+            .eval(builder, &remainder.into(), record, is_real)
+    }
+}
+
+impl<T> ReducedProduct<T> {
+    const fn num_requires() -> usize {
+        Product::<T, WORD512_SIZE>::num_requires()
+            + WideDivRem::<T>::num_requires()
+            + WordToBigNum::<T>::num_requires()
+    }
+}
+
+/// Witness for the multiplication of two big numbers modulo `p^8`
+#[derive(Clone, Debug, Default, AlignedBorrow)]
+#[repr(C)]
+pub struct BigNumMulWitness<T> {
+    lhs: BigNumToWord<T>,
+    rhs: BigNumToWord<T>,
+    product: ReducedProduct<T>,
+}
+
+impl<F: PrimeField32> BigNumMulWitness<F> {
+    pub fn populate(
+        &mut self,
+        lhs: &[F; DIGEST_SIZE],
+        rhs: &[F; DIGEST_SIZE],
+        byte_record: &mut impl ByteRecord,
+    ) -> [F; DIGEST_SIZE] {
+        let lhs = self.lhs.populate(lhs, byte_record);
+        let rhs = self.rhs.populate(rhs, byte_record);
+        self.product
+            .populate(&lhs, &rhs, &big_num_modulus::<F>(), byte_record)
+    }
+}
+
+impl<Var> BigNumMulWitness<Var> {
+    pub fn eval<AB: AirBuilder<Var = Var>>(
+        &self,
+        builder: &mut AB,
+        lhs: &[AB::Expr; DIGEST_SIZE],
+        rhs: &[AB::Expr; DIGEST_SIZE],
+        record: &mut impl ByteAirRecord<AB::Expr>,
+        is_real: impl Into<AB::Expr>,
+    ) -> [AB::Var; DIGEST_SIZE]
+    where
+        Var: Copy + Into<AB::Expr>,
+        AB::F: PrimeField,
+    {
+        let is_real = is_real.into();
+        let lhs = self.lhs.eval(builder, lhs, record, is_real.clone());
+        let rhs = self.rhs.eval(builder, rhs, record, is_real.clone());
+        let modulus = scaled_modulus::<AB::F, AB::Expr>(AB::Expr::one());
+        self.product
+            .eval(builder, lhs.into(), rhs.into(), &modulus, record, is_real)
+    }
+}
+
+impl<T> BigNumMulWitness<T> {
+    pub const fn num_requires() -> usize {
+        BigNumToWord::<T>::num_requires() * 2 + ReducedProduct::<T>::num_requires()
+    }
+
+    pub const fn witness_size() -> usize {
+        size_of::<BigNumMulWitness<u8>>()
+    }
+}
+
+/// Witness for the modular multiplication of two big numbers, which must be smaller than the
+/// non-zero modulus
+#[derive(Clone, Debug, Default, AlignedBorrow)]
+#[repr(C)]
+pub struct BigNumMulModWitness<T> {
+    lhs: BigNumToWord<T>,
+    rhs: BigNumToWord<T>,
+    modulus: BigNumToWord<T>,
+    product: ReducedProduct<T>,
+}
+
+impl<F: PrimeField32> BigNumMulModWitness<F> {
+    pub fn populate(
+        &mut self,
+        lhs: &[F; DIGEST_SIZE],
+        rhs: &[F; DIGEST_SIZE],
+        modulus: &[F; DIGEST_SIZE],
+        byte_record: &mut impl ByteRecord,
+    ) -> [F; DIGEST_SIZE] {
+        let lhs = self.lhs.populate(lhs, byte_record);
+        let rhs = self.rhs.populate(rhs, byte_record);
+        let modulus = self.modulus.populate(modulus, byte_record);
+        self.product.populate(&lhs, &rhs, &modulus, byte_record)
+    }
+}
+
+impl<Var> BigNumMulModWitness<Var> {
+    pub fn eval<AB: AirBuilder<Var = Var>>(
+        &self,
+        builder: &mut AB,
+        lhs: &[AB::Expr; DIGEST_SIZE],
+        rhs: &[AB::Expr; DIGEST_SIZE],
+        modulus: &[AB::Expr; DIGEST_SIZE],
+        record: &mut impl ByteAirRecord<AB::Expr>,
+        is_real: impl Into<AB::Expr>,
+    ) -> [AB::Var; DIGEST_SIZE]
+    where
+        Var: Copy + Into<AB::Expr>,
+        AB::F: PrimeField,
+    {
+        let is_real = is_real.into();
+        let lhs = self.lhs.eval(builder, lhs, record, is_real.clone());
+        let rhs = self.rhs.eval(builder, rhs, record, is_real.clone());
+        let modulus = self.modulus.eval(builder, modulus, record, is_real.clone());
+        self.product.eval(
+            builder,
+            lhs.into(),
+            rhs.into(),
+            &modulus.into(),
+            record,
+            is_real,
+        )
+    }
+}
+
+impl<T> BigNumMulModWitness<T> {
+    pub const fn num_requires() -> usize {
+        BigNumToWord::<T>::num_requires() * 3 + ReducedProduct::<T>::num_requires()
+    }
+
+    pub const fn witness_size() -> usize {
+        size_of::<BigNumMulModWitness<u8>>()
+    }
+}
+
+/// Witness for the euclidean division of two big numbers, where the divisor is non-zero
+#[derive(Clone, Debug, Default, AlignedBorrow)]
+#[repr(C)]
+pub struct BigNumDivRemWitness<T> {
+    lhs: BigNumToWord<T>,
+    rhs: BigNumToWord<T>,
+    div_rem: WideDivRem<T>,
+    quotient: WordToBigNum<T>,
+    remainder: WordToBigNum<T>,
+}
+
+impl<F: PrimeField32> BigNumDivRemWitness<F> {
+    pub fn populate(
+        &mut self,
+        lhs: &[F; DIGEST_SIZE],
+        rhs: &[F; DIGEST_SIZE],
+        byte_record: &mut impl ByteRecord,
+    ) -> ([F; DIGEST_SIZE], [F; DIGEST_SIZE]) {
+        let lhs = self.lhs.populate(lhs, byte_record);
+        let rhs = self.rhs.populate(rhs, byte_record);
+        let (quotient, remainder) = self.div_rem.populate(&lhs.resize(), &rhs, byte_record);
+        let quotient = self.quotient.populate(&quotient, byte_record);
+        let remainder = self.remainder.populate(&remainder, byte_record);
+        (quotient, remainder)
+    }
+}
+
+impl<Var> BigNumDivRemWitness<Var> {
+    pub fn eval<AB: AirBuilder<Var = Var>>(
+        &self,
+        builder: &mut AB,
+        lhs: &[AB::Expr; DIGEST_SIZE],
+        rhs: &[AB::Expr; DIGEST_SIZE],
+        record: &mut impl ByteAirRecord<AB::Expr>,
+        is_real: impl Into<AB::Expr>,
+    ) -> ([AB::Var; DIGEST_SIZE], [AB::Var; DIGEST_SIZE])
+    where
+        Var: Copy + Into<AB::Expr>,
+        AB::F: PrimeField,
+    {
+        let is_real = is_real.into();
+        let lhs = self.lhs.eval(builder, lhs, record, is_real.clone());
+        let rhs = self.rhs.eval(builder, rhs, record, is_real.clone());
+        let (quotient, remainder) = self.div_rem.eval(
+            builder,
+            &zero_extend(lhs.into()),
+            &rhs.into(),
+            record,
+            is_real.clone(),
+        );
+        let quotient = self
+            .quotient
+            .eval(builder, &quotient.into(), record, is_real.clone());
+        let remainder = self
+            .remainder
+            .eval(builder, &remainder.into(), record, is_real);
+        (quotient, remainder)
+    }
+}
+
+impl<T> BigNumDivRemWitness<T> {
+    pub const fn num_requires() -> usize {
+        BigNumToWord::<T>::num_requires() * 2
+            + WideDivRem::<T>::num_requires()
+            + WordToBigNum::<T>::num_requires() * 2
+    }
+
+    pub const fn witness_size() -> usize {
+        size_of::<BigNumDivRemWitness<u8>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gadgets::debug::{ByteRecordTester, GadgetTester};
+    use crate::lurk::big_num::field_elts_to_biguint;
+    use expect_test::expect;
+    use num_bigint::BigUint;
+    use p3_baby_bear::BabyBear;
+    use proptest::prelude::*;
+
+    type F = BabyBear;
+
+    const BABYBEAR_MOD: u32 = 0x78000001;
+
+    #[test]
+    fn test_witness_size() {
+        expect!["350"].assert_eq(&BigNumAddWitness::<u8>::witness_size().to_string());
+        expect!["350"].assert_eq(&BigNumSubWitness::<u8>::witness_size().to_string());
+        expect!["639"].assert_eq(&BigNumMulWitness::<u8>::witness_size().to_string());
+        expect!["742"].assert_eq(&BigNumMulModWitness::<u8>::witness_size().to_string());
+        expect!["590"].assert_eq(&BigNumDivRemWitness::<u8>::witness_size().to_string());
+    }
+
+    #[test]
+    fn test_num_requires() {
+        expect!["229"].assert_eq(&BigNumAddWitness::<u8>::num_requires().to_string());
+        expect!["229"].assert_eq(&BigNumSubWitness::<u8>::num_requires().to_string());
+        expect!["422"].assert_eq(&BigNumMulWitness::<u8>::num_requires().to_string());
+        expect!["493"].assert_eq(&BigNumMulModWitness::<u8>::num_requires().to_string());
+        expect!["381"].assert_eq(&BigNumDivRemWitness::<u8>::num_requires().to_string());
+    }
+
+    fn to_biguint(digest: &[F; DIGEST_SIZE]) -> BigUint {
+        field_elts_to_biguint(digest)
+    }
+
+    fn modulus() -> BigUint {
+        big_num_modulus::<F>().to_biguint()
+    }
+
+    fn test_add_sub_inner(lhs: &[F; DIGEST_SIZE], rhs: &[F; DIGEST_SIZE]) {
+        let (a, b) = (to_biguint(lhs), to_biguint(rhs));
+
+        let record = &mut ByteRecordTester::default();
+        let mut witness = BigNumAddWitness::<F>::default();
+        let sum = witness.populate(lhs, rhs, record);
+        assert_eq!(to_biguint(&sum), (&a + &b) % modulus());
+        let sum_f = witness.eval(
+            &mut GadgetTester::passing(),
+            lhs,
+            rhs,
+            &mut record.passing(BigNumAddWitness::<F>::num_requires()),
+            F::one(),
+        );
+        assert_eq!(sum_f, sum);
+
+        let record = &mut ByteRecordTester::default();
+        let mut witness = BigNumSubWitness::<F>::default();
+        let diff = witness.populate(lhs, rhs, record);
+        assert_eq!(to_biguint(&diff), (&a + modulus() - &b) % modulus());
+        let diff_f = witness.eval(
+            &mut GadgetTester::passing(),
+            lhs,
+            rhs,
+            &mut record.passing(BigNumSubWitness::<F>::num_requires()),
+            F::one(),
+        );
+        assert_eq!(diff_f, diff);
+    }
+
+    fn test_mul_inner(lhs: &[F; DIGEST_SIZE], rhs: &[F; DIGEST_SIZE]) {
+        let (a, b) = (to_biguint(lhs), to_biguint(rhs));
+
+        let record = &mut ByteRecordTester::default();
+        let mut witness = BigNumMulWitness::<F>::default();
+        let prod = witness.populate(lhs, rhs, record);
+        assert_eq!(to_biguint(&prod), (&a * &b) % modulus());
+        let prod_f = witness.eval(
+            &mut GadgetTester::passing(),
+            lhs,
+            rhs,
+            &mut record.passing(BigNumMulWitness::<F>::num_requires()),
+            F::one(),
+        );
+        assert_eq!(prod_f, prod);
+    }
+
+    fn test_div_rem_inner(lhs: &[F; DIGEST_SIZE], rhs: &[F; DIGEST_SIZE]) {
+        let (a, b) = (to_biguint(lhs), to_biguint(rhs));
+
+        let record = &mut ByteRecordTester::default();
+        let mut witness = BigNumDivRemWitness::<F>::default();
+        let (quot, rem) = witness.populate(lhs, rhs, record);
+        assert_eq!(to_biguint(&quot), &a / &b);
+        assert_eq!(to_biguint(&rem), &a % &b);
+        let (quot_f, rem_f) = witness.eval(
+            &mut GadgetTester::passing(),
+            lhs,
+            rhs,
+            &mut record.passing(BigNumDivRemWitness::<F>::num_requires()),
+            F::one(),
+        );
+        assert_eq!(quot_f, quot);
+        assert_eq!(rem_f, rem);
+    }
+
+    fn test_mul_mod_inner(
+        lhs: &[F; DIGEST_SIZE],
+        rhs: &[F; DIGEST_SIZE],
+        modulus: &[F; DIGEST_SIZE],
+    ) {
+        let (a, b, m) = (to_biguint(lhs), to_biguint(rhs), to_biguint(modulus));
+
+        let record = &mut ByteRecordTester::default();
+        let mut witness = BigNumMulModWitness::<F>::default();
+        let prod = witness.populate(lhs, rhs, modulus, record);
+        assert_eq!(to_biguint(&prod), (&a * &b) % &m);
+        let prod_f = witness.eval(
+            &mut GadgetTester::passing(),
+            lhs,
+            rhs,
+            modulus,
+            &mut record.passing(BigNumMulModWitness::<F>::num_requires()),
+            F::one(),
+        );
+        assert_eq!(prod_f, prod);
+    }
+
+    #[test]
+    fn test_arith_special() {
+        let zero = [F::zero(); DIGEST_SIZE];
+        let mut one = zero;
+        one[0] = F::one();
+        let max = [F::from_canonical_u32(BABYBEAR_MOD - 1); DIGEST_SIZE];
+
+        test_add_sub_inner(&zero, &zero);
+        test_add_sub_inner(&max, &one);
+        test_add_sub_inner(&max, &max);
+        test_add_sub_inner(&zero, &max);
+        test_mul_inner(&max, &max);
+        test_div_rem_inner(&max, &one);
+        test_div_rem_inner(&one, &max);
+        test_div_rem_inner(&max, &max);
+        test_mul_mod_inner(&zero, &zero, &one);
+        test_mul_mod_inner(&one, &one, &max);
+    }
+
+    proptest! {
+
+    #[test]
+    fn test_arith(lhs: [u32; DIGEST_SIZE], rhs: [u32; DIGEST_SIZE]) {
+        let lhs = lhs.map(|x| x % BABYBEAR_MOD).map(F::from_canonical_u32);
+        let mut rhs = rhs.map(|x| x % BABYBEAR_MOD).map(F::from_canonical_u32);
+        test_add_sub_inner(&lhs, &rhs);
+        test_mul_inner(&lhs, &rhs);
+        if rhs == [F::zero(); DIGEST_SIZE] {
+            rhs[0] = F::one();
+        }
+        test_div_rem_inner(&lhs, &rhs);
+    }
+
+    #[test]
+    fn test_mul_mod(lhs: [u32; DIGEST_SIZE], rhs: [u32; DIGEST_SIZE]) {
+        let max = [F::from_canonical_u32(BABYBEAR_MOD - 1); DIGEST_SIZE];
+        let lhs = lhs.map(|x| x % BABYBEAR_MOD).map(F::from_canonical_u32);
+        let rhs = rhs.map(|x| x % BABYBEAR_MOD).map(F::from_canonical_u32);
+        // Both operands must be smaller than the modulus
+        if lhs != max && rhs != max {
+            test_mul_mod_inner(&lhs, &rhs, &max);
+        }
+    }
+
+    }
+}
@@ -1 +1,12 @@
+use crate::gadgets::unsigned::Word;
+
+pub mod arith;
 pub mod cmp;
+pub mod uint;
+pub mod word;
+
+pub(crate) const WORD256_SIZE: usize = 32;
+pub(crate) const WORD512_SIZE: usize = 64;
+
+pub type Word256<T> = Word<T, WORD256_SIZE>;
+pub type Word512<T> = Word<T, WORD512_SIZE>;
@@ -0,0 +1,164 @@
+use num_bigint::{BigUint, ParseBigIntError};
+use num_traits::ops::overflowing::{OverflowingAdd, OverflowingSub};
+use num_traits::{FromBytes, Num, One, ToBytes, Unsigned, Zero};
+use std::array;
+use std::cmp::Ordering;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+use super::{WORD256_SIZE, WORD512_SIZE};
+
+/// Unsigned integer stored as `W` little-endian bytes, whose arithmetic wraps around modulo
+/// `2^(8 * W)` like the native unsigned types.
+///
+/// It's only meant for populating the `unsigned` gadgets with words that are wider than the
+/// native types, so the operations are implemented by going through `BigUint`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Uint<const W: usize>([u8; W]);
+
+pub type U256 = Uint<WORD256_SIZE>;
+pub type U512 = Uint<WORD512_SIZE>;
+
+impl<const W: usize> Uint<W> {
+    /// Truncates a `BigUint` to its `W` least significant bytes
+    pub fn from_biguint(n: &BigUint) -> Self {
+        let mut bytes = [0; W];
+        for (byte, n_byte) in bytes.iter_mut().zip(n.to_bytes_le()) {
+            *byte = n_byte;
+        }
+        Self(bytes)
+    }
+
+    pub fn to_biguint(&self) -> BigUint {
+        BigUint::from_bytes_le(&self.0)
+    }
+
+    /// Zero-extends or truncates the integer to `W2` bytes
+    pub fn resize<const W2: usize>(&self) -> Uint<W2> {
+        Uint(array::from_fn(|i| if i < W { self.0[i] } else { 0 }))
+    }
+
+    fn modulus() -> BigUint {
+        BigUint::one() << (8 * W)
+    }
+}
+
+impl<const W: usize> Ord for Uint<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare starting from the most significant byte
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const W: usize> PartialOrd for Uint<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const W: usize> Add for Uint<W> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::from_biguint(&(self.to_biguint() + rhs.to_biguint()))
+    }
+}
+
+impl<const W: usize> Sub for Uint<W> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_biguint(&(self.to_biguint() + Self::modulus() - rhs.to_biguint()))
+    }
+}
+
+impl<const W: usize> Mul for Uint<W> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::from_biguint(&(self.to_biguint() * rhs.to_biguint()))
+    }
+}
+
+impl<const W: usize> Div for Uint<W> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self::from_biguint(&(self.to_biguint() / rhs.to_biguint()))
+    }
+}
+
+impl<const W: usize> Rem for Uint<W> {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        Self::from_biguint(&(self.to_biguint() % rhs.to_biguint()))
+    }
+}
+
+impl<const W: usize> Zero for Uint<W> {
+    fn zero() -> Self {
+        Self([0; W])
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(Zero::is_zero)
+    }
+}
+
+impl<const W: usize> One for Uint<W> {
+    fn one() -> Self {
+        Self(array::from_fn(|i| u8::from(i == 0)))
+    }
+}
+
+impl<const W: usize> Num for Uint<W> {
+    type FromStrRadixErr = ParseBigIntError;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        BigUint::from_str_radix(str, radix).map(|n| Self::from_biguint(&n))
+    }
+}
+
+impl<const W: usize> Unsigned for Uint<W> {}
+
+impl<const W: usize> OverflowingAdd for Uint<W> {
+    fn overflowing_add(&self, v: &Self) -> (Self, bool) {
+        let sum = self.to_biguint() + v.to_biguint();
+        let overflow = sum >= Self::modulus();
+        (Self::from_biguint(&sum), overflow)
+    }
+}
+
+impl<const W: usize> OverflowingSub for Uint<W> {
+    fn overflowing_sub(&self, v: &Self) -> (Self, bool) {
+        (*self - *v, self < v)
+    }
+}
+
+impl<const W: usize> ToBytes for Uint<W> {
+    type Bytes = [u8; W];
+
+    fn to_be_bytes(&self) -> Self::Bytes {
+        let mut bytes = self.0;
+        bytes.reverse();
+        bytes
+    }
+
+    fn to_le_bytes(&self) -> Self::Bytes {
+        self.0
+    }
+}
+
+impl<const W: usize> FromBytes for Uint<W> {
+    type Bytes = [u8; W];
+
+    fn from_be_bytes(bytes: &Self::Bytes) -> Self {
+        let mut bytes = *bytes;
+        bytes.reverse();
+        Self(bytes)
+    }
+
+    fn from_le_bytes(bytes: &Self::Bytes) -> Self {
+        Self(*bytes)
+    }
+}
@@ -0,0 +1,300 @@
+use crate::gadgets::big_num::uint::U256;
+use crate::gadgets::big_num::{Word256, WORD256_SIZE};
+use crate::gadgets::bytes::{ByteAirRecord, ByteRecord};
+use crate::gadgets::unsigned::field::FieldToWord32;
+use crate::gadgets::unsigned::UncheckedWord;
+use crate::lurk::zstore::DIGEST_SIZE;
+use itertools::{enumerate, izip};
+use num_traits::{FromBytes, ToBytes, ToPrimitive};
+use p3_air::AirBuilder;
+use p3_field::{AbstractField, PrimeField, PrimeField32};
+use sphinx_derive::AlignedBorrow;
+use std::array;
+
+/// Returns the little-endian bytes of `p^i` for every limb `i` of a digest, where `p` is the
+/// order of the field. These are the weights of the limbs in the integer represented by a digest.
+fn limb_weights<F: PrimeField>() -> [[u8; WORD256_SIZE]; DIGEST_SIZE] {
+    let order = F::order();
+    array::from_fn(|i| U256::from_biguint(&order.pow(i as u32)).to_le_bytes())
+}
+
+/// Returns `p^8`, the number of integers representable by a digest
+pub fn big_num_modulus<F: PrimeField>() -> U256 {
+    U256::from_biguint(&F::order().pow(DIGEST_SIZE as u32))
+}
+
+/// Witness for the equality between the integer represented by a digest,
+///   digest[0] + digest[1] * p + ... + digest[7] * p^7,
+/// and a 32-byte little-endian word. Since every limb is checked to be canonical, this also
+/// proves that the word is less than `p^8`.
+#[derive(Clone, Debug, Default, AlignedBorrow)]
+#[repr(C)]
+struct DigestWordWitness<T> {
+    limbs: [FieldToWord32<T>; DIGEST_SIZE],
+    /// Since `p^8 < 2^248`, there's no carry out of the most significant byte
+    carry: [T; WORD256_SIZE - 1],
+}
+
+impl<F: PrimeField32> DigestWordWitness<F> {
+    fn populate(&mut self, digest: &[F; DIGEST_SIZE], byte_record: &mut impl ByteRecord) -> U256 {
+        // Compute columns[k] = ∑_{i + j = k} limb_bytes[i] * weight[j] for every limb
+        let mut columns = [0u32; WORD256_SIZE];
+        for (limb_witness, limb, weight) in izip!(&mut self.limbs, digest, limb_weights::<F>()) {
+            let limb = limb.as_canonical_u32();
+            limb_witness.populate(&limb, byte_record);
+            for (i, limb_byte) in enumerate(limb.to_le_bytes()) {
+                for (j, &weight_byte) in enumerate(&weight) {
+                    if i + j < WORD256_SIZE {
+                        columns[i + j] += u32::from(limb_byte) * u32::from(weight_byte);
+                    }
+                }
+            }
+        }
+
+        let mut carry = 0u32;
+        let mut result = [0u8; WORD256_SIZE];
+        for (k, column) in enumerate(columns) {
+            let [limb, carry_lo, carry_hi, null] = (column + carry).to_le_bytes();
+            debug_assert_eq!(null, 0);
+            result[k] = limb;
+            carry = u32::from(u16::from_le_bytes([carry_lo, carry_hi]));
+            if k < WORD256_SIZE - 1 {
+                byte_record.range_check_u16(carry as u16);
+                self.carry[k] = F::from_canonical_u32(carry);
+            }
+        }
+        debug_assert_eq!(carry, 0);
+        U256::from_le_bytes(&result)
+    }
+}
+
+impl<Var> DigestWordWitness<Var> {
+    /// Constraints for checking that the digest and the word represent the same integer, where
+    /// the word is assumed to be range checked.
+    fn assert_eq<AB: AirBuilder<Var = Var>>(
+        &self,
+        builder: &mut AB,
+        digest: &[AB::Expr; DIGEST_SIZE],
+        word: &Word256<AB::Expr>,
+        record: &mut impl ByteAirRecord<AB::Expr>,
+        is_real: AB::Expr,
+    ) where
+        Var: Copy + Into<AB::Expr>,
+        AB::F: PrimeField,
+    {
+        // Each column is the sum of at most 32 products of two bytes, so it's smaller than 2^21.
+        // Together with the range checks over the carries, this ensures that the equations below
+        // hold over the integers, as both sides are smaller than the field order.
+        let mut columns: [AB::Expr; WORD256_SIZE] = array::from_fn(|_| AB::Expr::zero());
+        for (limb_witness, limb, weight) in izip!(&self.limbs, digest, limb_weights::<AB::F>()) {
+            let limb_bytes = limb_witness.eval(builder, limb, record, is_real.clone());
+            for (i, &limb_byte) in enumerate(&limb_bytes) {
+                for (j, &weight_byte) in enumerate(&weight) {
+                    if i + j < WORD256_SIZE && weight_byte != 0 {
+                        columns[i + j] += limb_byte.into() * AB::F::from_canonical_u8(weight_byte);
+                    }
+                }
+            }
+        }
+
+        let base = AB::F::from_canonical_u16(256);
+        let mut carry_prev = AB::Expr::zero();
+        for (k, column) in enumerate(columns) {
+            let carry = if k < WORD256_SIZE - 1 {
+                record.range_check_u16(self.carry[k], is_real.clone());
+                self.carry[k].into()
+            } else {
+                AB::Expr::zero()
+            };
+            builder
+                .when(is_real.clone())
+                .assert_eq(column + carry_prev, word[k].clone() + carry.clone() * base);
+            carry_prev = carry;
+        }
+    }
+}
+
+impl<T> DigestWordWitness<T> {
+    const fn num_requires() -> usize {
+        FieldToWord32::<T>::num_requires() * DIGEST_SIZE + (WORD256_SIZE - 1)
+    }
+}
+
+/// Converts a digest into the little-endian bytes of the integer it represents
+#[derive(Clone, Debug, Default, AlignedBorrow)]
+#[repr(C)]
+pub struct BigNumToWord<T> {
+    witness: DigestWordWitness<T>,
+    result: UncheckedWord<T, WORD256_SIZE>,
+}
+
+impl<F: PrimeField32> BigNumToWord<F> {
+    pub fn populate(
+        &mut self,
+        digest: &[F; DIGEST_SIZE],
+        byte_record: &mut impl ByteRecord,
+    ) -> U256 {
+        let out = self.witness.populate(digest, byte_record);
+        self.result.assign_bytes(&out.to_le_bytes(), byte_record);
+        out
+    }
+}
+
+impl<Var> BigNumToWord<Var> {
+    pub fn eval<AB: AirBuilder<Var = Var>>(
+        &self,
+        builder: &mut AB,
+        digest: &[AB::Expr; DIGEST_SIZE],
+        record: &mut impl ByteAirRecord<AB::Expr>,
+        is_real: impl Into<AB::Expr>,
+    ) -> Word256<AB::Var>
+    where
+        Var: Copy + Into<AB::Expr>,
+        AB::F: PrimeField,
+    {
+        let is_real = is_real.into();
+        self.witness.assert_eq(
+            builder,
+            digest,
+            &self.result.into_unchecked().into(),
+            record,
+            is_real.clone(),
+        );
+        self.result.into_checked(record, is_real)
+    }
+}
+
+impl<T> BigNumToWord<T> {
+    pub const fn num_requires() -> usize {
+        DigestWordWitness::<T>::num_requires() + WORD256_SIZE / 2
+    }
+
+    pub const fn witness_size() -> usize {
+        size_of::<BigNumToWord<u8>>()
+    }
+}
+
+/// Converts a little-endian word into the digest representing the same integer, which also
+/// proves that the word is less than `p^8`
+#[derive(Clone, Debug, Default, AlignedBorrow)]
+#[repr(C)]
+pub struct WordToBigNum<T> {
+    result: [T; DIGEST_SIZE],
+    witness: DigestWordWitness<T>,
+}
+
+impl<F: PrimeField32> WordToBigNum<F> {
+    pub fn populate(&mut self, word: &U256, byte_record: &mut impl ByteRecord) -> [F; DIGEST_SIZE] {
+        let order = F::order();
+        let mut n = word.to_biguint();
+        assert!(n < order.pow(DIGEST_SIZE as u32), "Word too large");
+        let digest = array::from_fn(|_| {
+            let limb = (&n % &order).to_u32().unwrap();
+            n /= &order;
+            F::from_canonical_u32(limb)
+        });
+        self.result = digest;
+        let out = self.witness.populate(&digest, byte_record);
+        debug_assert_eq!(&out, word);
+        digest
+    }
+}
+
+impl<Var> WordToBigNum<Var> {
+    pub fn eval<AB: AirBuilder<Var = Var>>(
+        &self,
+        builder: &mut AB,
+        word: &Word256<AB::Expr>,
+        record: &mut impl ByteAirRecord<AB::Expr>,
+        is_real: impl Into<AB::Expr>,
+    ) -> [AB::Var; DIGEST_SIZE]
+    where
+        Var: Copy + Into<AB::Expr>,
+        AB::F: PrimeField,
+    {
+        let digest = self.result.map(Into::into);
+        self.witness
+            .assert_eq(builder, &digest, word, record, is_real.into());
+        self.result
+    }
+}
+
+impl<T> WordToBigNum<T> {
+    pub const fn num_requires() -> usize {
+        DigestWordWitness::<T>::num_requires()
+    }
+
+    pub const fn witness_size() -> usize {
+        size_of::<WordToBigNum<u8>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gadgets::debug::{ByteRecordTester, GadgetTester};
+    use crate::lurk::big_num::field_elts_to_biguint;
+    use expect_test::expect;
+    use p3_baby_bear::BabyBear;
+    use proptest::prelude::*;
+
+    type F = BabyBear;
+
+    const BABYBEAR_MOD: u32 = 0x78000001;
+
+    #[test]
+    fn test_witness_size() {
+        expect!["103"].assert_eq(&BigNumToWord::<u8>::witness_size().to_string());
+        expect!["79"].assert_eq(&WordToBigNum::<u8>::witness_size().to_string());
+    }
+
+    #[test]
+    fn test_num_requires() {
+        expect!["71"].assert_eq(&BigNumToWord::<u8>::num_requires().to_string());
+        expect!["55"].assert_eq(&WordToBigNum::<u8>::num_requires().to_string());
+    }
+
+    fn test_conversion_inner(digest: &[F; DIGEST_SIZE]) {
+        let expected = U256::from_biguint(&field_elts_to_biguint(digest));
+
+        let record = &mut ByteRecordTester::default();
+        let mut to_word = BigNumToWord::<F>::default();
+        let word = to_word.populate(digest, record);
+        assert_eq!(word, expected);
+        let word_f = to_word.eval(
+            &mut GadgetTester::passing(),
+            digest,
+            &mut record.passing(BigNumToWord::<F>::num_requires()),
+            F::one(),
+        );
+        assert_eq!(word_f, Word256::from_unsigned(&expected));
+
+        let record = &mut ByteRecordTester::default();
+        let mut to_big_num = WordToBigNum::<F>::default();
+        let digest_out = to_big_num.populate(&word, record);
+        assert_eq!(&digest_out, digest);
+        let digest_f = to_big_num.eval(
+            &mut GadgetTester::passing(),
+            &word_f,
+            &mut record.passing(WordToBigNum::<F>::num_requires()),
+            F::one(),
+        );
+        assert_eq!(&digest_f, digest);
+    }
+
+    #[test]
+    fn test_conversion_special() {
+        test_conversion_inner(&[F::zero(); DIGEST_SIZE]);
+        test_conversion_inner(&[F::from_canonical_u32(BABYBEAR_MOD - 1); DIGEST_SIZE]);
+    }
+
+    proptest! {
+
+    #[test]
+    fn test_conversion(digest: [u32; DIGEST_SIZE]) {
+        let digest = digest.map(|x| x % BABYBEAR_MOD).map(F::from_canonical_u32);
+        test_conversion_inner(&digest);
+    }
+
+    }
+}
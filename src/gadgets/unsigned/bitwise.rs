@@ -0,0 +1,170 @@
+use crate::gadgets::bytes::{ByteAirRecord, ByteRecord};
+use crate::gadgets::unsigned::Word;
+use num_traits::{FromBytes, ToBytes, Unsigned};
+use p3_field::AbstractField;
+use sphinx_derive::AlignedBorrow;
+use std::array;
+
+/// The bitwise operations supported by `Bitwise`, each one backed by the byte
+/// relation of the same name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitwiseOp {
+    And,
+    Or,
+    Xor,
+}
+
+impl BitwiseOp {
+    fn record(&self, byte_record: &mut impl ByteRecord, i1: u8, i2: u8) -> u8 {
+        match self {
+            BitwiseOp::And => byte_record.and(i1, i2),
+            BitwiseOp::Or => byte_record.or(i1, i2),
+            BitwiseOp::Xor => byte_record.xor(i1, i2),
+        }
+    }
+
+    fn air_record<E: AbstractField>(
+        &self,
+        record: &mut impl ByteAirRecord<E>,
+        i1: E,
+        i2: E,
+        r: E,
+        is_real: E,
+    ) {
+        match self {
+            BitwiseOp::And => record.and(i1, i2, r, is_real),
+            BitwiseOp::Or => record.or(i1, i2, r, is_real),
+            BitwiseOp::Xor => record.xor(i1, i2, r, is_real),
+        }
+    }
+}
+
+/// Wrapper type for bitwise operations, which contains the output of the computation.
+/// Each output limb is the result of a byte lookup on the corresponding input limbs, so the
+/// output is range checked for free.
+#[derive(Clone, Debug, AlignedBorrow)]
+#[repr(C)]
+pub struct Bitwise<T, const W: usize> {
+    result: [T; W],
+}
+
+impl<F: AbstractField, const W: usize> Bitwise<F, W> {
+    pub fn populate<U>(
+        &mut self,
+        op: BitwiseOp,
+        lhs: &U,
+        rhs: &U,
+        byte_record: &mut impl ByteRecord,
+    ) -> U
+    where
+        U: ToBytes<Bytes = [u8; W]> + FromBytes<Bytes = [u8; W]> + Unsigned,
+    {
+        let lhs = lhs.to_le_bytes();
+        let rhs = rhs.to_le_bytes();
+        let out: [u8; W] = array::from_fn(|i| op.record(byte_record, lhs[i], rhs[i]));
+        self.result = out.map(F::from_canonical_u8);
+        U::from_le_bytes(&out)
+    }
+}
+
+impl<Var, const W: usize> Bitwise<Var, W> {
+    /// Constraints for checking that out = lhs `op` rhs, where the operands are assumed to be
+    /// range checked little-endian unsigned integers.
+    pub fn eval<E: AbstractField>(
+        &self,
+        op: BitwiseOp,
+        lhs: &Word<E, W>,
+        rhs: &Word<E, W>,
+        record: &mut impl ByteAirRecord<E>,
+        is_real: impl Into<E>,
+    ) -> Word<Var, W>
+    where
+        Var: Copy + Into<E>,
+    {
+        let is_real = is_real.into();
+        for i in 0..W {
+            op.air_record(
+                record,
+                lhs[i].clone(),
+                rhs[i].clone(),
+                self.result[i].into(),
+                is_real.clone(),
+            );
+        }
+        Word(self.result)
+    }
+}
+
+impl<T, const W: usize> Bitwise<T, W> {
+    pub const fn num_requires() -> usize {
+        W
+    }
+
+    pub const fn witness_size() -> usize {
+        size_of::<Bitwise<u8, W>>()
+    }
+
+    pub fn iter_result(&self) -> impl IntoIterator<Item = T>
+    where
+        T: Clone,
+    {
+        self.result.clone()
+    }
+}
+
+impl<T: Default, const W: usize> Default for Bitwise<T, W> {
+    fn default() -> Self {
+        Self {
+            result: array::from_fn(|_| T::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gadgets::debug::ByteRecordTester;
+    use p3_baby_bear::BabyBear;
+    use proptest::prelude::*;
+    use std::fmt::Debug;
+
+    type F = BabyBear;
+
+    fn test_bitwise<const W: usize, U>(op: BitwiseOp, lhs: &U, rhs: &U, expected: &U)
+    where
+        U: ToBytes<Bytes = [u8; W]> + FromBytes<Bytes = [u8; W]> + Unsigned + Debug,
+    {
+        let record = &mut ByteRecordTester::default();
+
+        let mut witness = Bitwise::<F, W>::default();
+        let out = witness.populate(op, lhs, rhs, record);
+        assert_eq!(&out, expected);
+
+        let out_f = witness.eval(
+            op,
+            &Word::<F, W>::from_unsigned(lhs),
+            &Word::<F, W>::from_unsigned(rhs),
+            &mut record.passing(Bitwise::<F, W>::num_requires()),
+            F::one(),
+        );
+        assert_eq!(out_f, Word::<F, W>::from_unsigned(expected));
+    }
+
+    proptest! {
+
+    #[test]
+    fn test_bitwise_32(a: u32, b: u32) {
+        test_bitwise::<4, _>(BitwiseOp::And, &a, &b, &(a & b));
+        test_bitwise::<4, _>(BitwiseOp::Or, &a, &b, &(a | b));
+        test_bitwise::<4, _>(BitwiseOp::Xor, &a, &b, &(a ^ b));
+    }
+
+    #[test]
+    fn test_bitwise_64(a: u64, b: u64) {
+        test_bitwise::<8, _>(BitwiseOp::And, &a, &b, &(a & b));
+        test_bitwise::<8, _>(BitwiseOp::Or, &a, &b, &(a | b));
+        test_bitwise::<8, _>(BitwiseOp::Xor, &a, &b, &(a ^ b));
+    }
+
+    }
+}
@@ -9,12 +9,14 @@ use std::iter::zip;
 use std::ops::{Index, IndexMut};
 
 pub mod add;
+pub mod bitwise;
 pub mod cmp;
 pub mod div_rem;
 pub mod field;
 pub mod is_zero;
 pub mod less_than;
 pub mod mul;
+pub mod shift;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, AlignedBorrow)]
 #[repr(C)]
@@ -0,0 +1,267 @@
+use crate::gadgets::bytes::{ByteAirRecord, ByteRecord};
+use crate::gadgets::unsigned::Word;
+use itertools::chain;
+use num_traits::{FromBytes, ToBytes, Unsigned};
+use p3_air::AirBuilder;
+use p3_field::AbstractField;
+use sphinx_derive::AlignedBorrow;
+use std::array;
+
+/// The direction of a logical shift
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShiftDirection {
+    Left,
+    Right,
+}
+
+/// Witness for logical shifts of `W`-byte words. As with `wrapping_shl` and `wrapping_shr`, the
+/// shift amount is taken modulo the bit width `8 * W`.
+///
+/// Writing the shift amount as `8 * q + r`, with `r < 8`, the input is first shifted by `q`
+/// limbs, and then each limb is multiplied by `2^r` (or `2^(8-r)` for right shifts) and split
+/// into a low and a high byte. The output limbs are obtained by combining the low byte of a limb
+/// with the high byte of its neighbour, which never overlap.
+#[derive(Clone, Debug, AlignedBorrow)]
+#[repr(C)]
+pub struct ShiftWitness<T, const W: usize> {
+    /// One-hot encoding of the number of bits `r` to shift by
+    bit_shift: [T; 8],
+    /// One-hot encoding of the number of limbs `q` to shift by
+    limb_shift: [T; W],
+    /// The bits of the shift amount's first limb that exceed the bit width
+    shift_rest: T,
+    /// The input shifted by `q` limbs
+    shifted: [T; W],
+    lo: [T; W],
+    hi: [T; W],
+}
+
+impl<F: AbstractField, const W: usize> ShiftWitness<F, W> {
+    pub fn populate<U>(
+        &mut self,
+        direction: ShiftDirection,
+        input: &U,
+        shift: &U,
+        byte_record: &mut impl ByteRecord,
+    ) -> U
+    where
+        U: ToBytes<Bytes = [u8; W]> + FromBytes<Bytes = [u8; W]> + Unsigned,
+    {
+        let shift_limb = shift.to_le_bytes()[0] as usize;
+        let (r, q, rest) = (shift_limb % 8, (shift_limb / 8) % W, shift_limb / (8 * W));
+        self.bit_shift = array::from_fn(|i| F::from_bool(i == r));
+        self.limb_shift = array::from_fn(|i| F::from_bool(i == q));
+        self.shift_rest = F::from_canonical_usize(rest);
+
+        let input = input.to_le_bytes();
+        let (shifted, factor): ([u8; W], u16) = match direction {
+            ShiftDirection::Left => (
+                array::from_fn(|i| if i >= q { input[i - q] } else { 0 }),
+                1 << r,
+            ),
+            ShiftDirection::Right => (
+                array::from_fn(|i| if i + q < W { input[i + q] } else { 0 }),
+                1 << (8 - r),
+            ),
+        };
+        let products = shifted.map(|limb| u16::from(limb) * factor);
+        let lo = products.map(|p| p.to_le_bytes()[0]);
+        let hi = products.map(|p| p.to_le_bytes()[1]);
+        self.shifted = shifted.map(F::from_canonical_u8);
+        self.lo = lo.map(F::from_canonical_u8);
+        self.hi = hi.map(F::from_canonical_u8);
+        byte_record.range_check_u8_iter(chain!(lo, hi, [rest as u8]));
+
+        // The bytes of a limb and its neighbour never overlap, so their sum can't overflow
+        let out = array::from_fn(|i| match direction {
+            ShiftDirection::Left => lo[i] + if i > 0 { hi[i - 1] } else { 0 },
+            ShiftDirection::Right => hi[i] + if i + 1 < W { lo[i + 1] } else { 0 },
+        });
+        U::from_le_bytes(&out)
+    }
+}
+
+impl<Var, const W: usize> ShiftWitness<Var, W> {
+    /// Constraints for checking that out = input << shift (or input >> shift), where the operands
+    /// are assumed to be range checked little-endian unsigned integers. Only the first limb of
+    /// `shift` is used, since the shift amount is taken modulo the bit width.
+    pub fn eval<AB: AirBuilder<Var = Var>>(
+        &self,
+        builder: &mut AB,
+        direction: ShiftDirection,
+        input: &Word<AB::Expr, W>,
+        shift: &Word<AB::Expr, W>,
+        record: &mut impl ByteAirRecord<AB::Expr>,
+        is_real: impl Into<AB::Expr>,
+    ) -> Word<AB::Expr, W>
+    where
+        Var: Copy + Into<AB::Expr>,
+    {
+        let is_real = is_real.into();
+        let builder = &mut builder.when(is_real.clone());
+
+        let mut r = AB::Expr::zero();
+        let mut factor = AB::Expr::zero();
+        let mut bit_shift_sum = AB::Expr::zero();
+        for (i, &flag) in self.bit_shift.iter().enumerate() {
+            builder.assert_bool(flag);
+            bit_shift_sum += flag.into();
+            r += flag.into() * AB::F::from_canonical_usize(i);
+            let factor_i = match direction {
+                ShiftDirection::Left => 1 << i,
+                ShiftDirection::Right => 1 << (8 - i),
+            };
+            factor += flag.into() * AB::F::from_canonical_u16(factor_i);
+        }
+        builder.assert_one(bit_shift_sum);
+
+        let mut q = AB::Expr::zero();
+        let mut limb_shift_sum = AB::Expr::zero();
+        for (i, &flag) in self.limb_shift.iter().enumerate() {
+            builder.assert_bool(flag);
+            limb_shift_sum += flag.into();
+            q += flag.into() * AB::F::from_canonical_usize(i);
+        }
+        builder.assert_one(limb_shift_sum);
+
+        // Since `shift_rest` is range checked, the decomposition
+        //   shift[0] = r + 8 * q + 8 * W * shift_rest
+        // can't overflow, so `r` and `q` are the bit and limb offsets of the shift amount
+        // modulo the bit width.
+        let width = AB::F::from_canonical_usize(8 * W);
+        builder.assert_eq(
+            shift[0].clone(),
+            r + q * AB::F::from_canonical_u8(8) + self.shift_rest.into() * width,
+        );
+
+        let base = AB::F::from_canonical_u16(256);
+        for i in 0..W {
+            // shifted[i] = input[i - q] (or input[i + q]), selected by the one-hot `limb_shift`
+            let selected = self
+                .limb_shift
+                .iter()
+                .enumerate()
+                .filter_map(|(j, &flag)| {
+                    let source = match direction {
+                        ShiftDirection::Left => i.checked_sub(j),
+                        ShiftDirection::Right => Some(i + j).filter(|&k| k < W),
+                    }?;
+                    Some(input[source].clone() * flag.into())
+                })
+                .sum::<AB::Expr>();
+            builder.assert_eq(self.shifted[i], selected);
+
+            // With `lo` and `hi` range checked, this decomposition is unique since
+            //   shifted[i] * factor < 2^16
+            builder.assert_eq(
+                self.shifted[i].into() * factor.clone(),
+                self.lo[i].into() + self.hi[i].into() * base,
+            );
+        }
+
+        record.range_check_u8_iter(chain!(self.lo, self.hi, [self.shift_rest]), is_real.clone());
+
+        // For left shifts, the high byte of each limb carries into the next one, whereas for
+        // right shifts, the low byte of each limb carries into the previous one.
+        Word::from_fn(|i| match direction {
+            ShiftDirection::Left => {
+                let carry = if i > 0 {
+                    self.hi[i - 1].into()
+                } else {
+                    AB::Expr::zero()
+                };
+                self.lo[i].into() + carry
+            }
+            ShiftDirection::Right => {
+                let carry = if i + 1 < W {
+                    self.lo[i + 1].into()
+                } else {
+                    AB::Expr::zero()
+                };
+                self.hi[i].into() + carry
+            }
+        })
+    }
+}
+
+impl<T, const W: usize> ShiftWitness<T, W> {
+    pub const fn num_requires() -> usize {
+        // `lo`, `hi` and `shift_rest` are range checked in pairs
+        W + 1
+    }
+
+    pub const fn witness_size() -> usize {
+        size_of::<ShiftWitness<u8, W>>()
+    }
+}
+
+impl<T: Default, const W: usize> Default for ShiftWitness<T, W> {
+    fn default() -> Self {
+        Self {
+            bit_shift: array::from_fn(|_| T::default()),
+            limb_shift: array::from_fn(|_| T::default()),
+            shift_rest: T::default(),
+            shifted: array::from_fn(|_| T::default()),
+            lo: array::from_fn(|_| T::default()),
+            hi: array::from_fn(|_| T::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gadgets::debug::{ByteRecordTester, GadgetTester};
+    use p3_baby_bear::BabyBear;
+    use proptest::prelude::*;
+    use std::fmt::Debug;
+
+    type F = BabyBear;
+
+    fn test_shift<const W: usize, U>(direction: ShiftDirection, input: &U, shift: &U, expected: &U)
+    where
+        U: ToBytes<Bytes = [u8; W]> + FromBytes<Bytes = [u8; W]> + Unsigned + Debug,
+    {
+        let record = &mut ByteRecordTester::default();
+
+        let mut witness = ShiftWitness::<F, W>::default();
+        let out = witness.populate(direction, input, shift, record);
+        assert_eq!(&out, expected);
+
+        let out_f = witness.eval(
+            &mut GadgetTester::passing(),
+            direction,
+            &Word::<F, W>::from_unsigned(input),
+            &Word::<F, W>::from_unsigned(shift),
+            &mut record.passing(ShiftWitness::<F, W>::num_requires()),
+            F::one(),
+        );
+        assert_eq!(out_f, Word::<F, W>::from_unsigned(expected));
+    }
+
+    #[test]
+    fn test_shift_edge_cases() {
+        test_shift::<8, _>(ShiftDirection::Left, &1u64, &0, &1);
+        test_shift::<8, _>(ShiftDirection::Left, &1u64, &63, &(1 << 63));
+        test_shift::<8, _>(ShiftDirection::Left, &1u64, &64, &1);
+        test_shift::<8, _>(ShiftDirection::Right, &u64::MAX, &63, &1);
+        test_shift::<8, _>(ShiftDirection::Right, &u64::MAX, &255, &1);
+    }
+
+    proptest! {
+
+    #[test]
+    fn test_shift_32(a: u32, b: u32) {
+        test_shift::<4, _>(ShiftDirection::Left, &a, &b, &a.wrapping_shl(b & 0xff));
+        test_shift::<4, _>(ShiftDirection::Right, &a, &b, &a.wrapping_shr(b & 0xff));
+    }
+
+    #[test]
+    fn test_shift_64(a: u64, b: u64) {
+        let shift = (b & 0xff) as u32;
+        test_shift::<8, _>(ShiftDirection::Left, &a, &b, &a.wrapping_shl(shift));
+        test_shift::<8, _>(ShiftDirection::Right, &a, &b, &a.wrapping_shr(shift));
+    }
+
+    }
+}
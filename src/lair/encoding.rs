@@ -0,0 +1,485 @@
+//! A canonical byte encoding of Lair programs, meant to be hashed into
+//! identifiers that must be stable across builds and platforms.
+//!
+//! Unlike `Debug`, it doesn't depend on the formatting of the types involved and
+//! it leaves out the error formatting callbacks of `AssertEq` and `PreImg`,
+//! whose addresses change from one build to the other. Those callbacks only
+//! affect error messages, not the constraints.
+
+use p3_field::PrimeField32;
+
+use super::{
+    bytecode::{Block, Cases, Ctrl, Func, Op},
+    expr::{BlockE, CaseType, CasesE, CtrlE, FuncE, Ident, OpE, Var, VarList},
+    List, Name,
+};
+
+/// Types with a canonical byte encoding
+pub trait Encode {
+    /// Appends the encoding of `self` to `bytes`
+    fn encode(&self, bytes: &mut Vec<u8>);
+
+    /// The encoding of `self`
+    fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.encode(&mut bytes);
+        bytes
+    }
+}
+
+#[inline]
+fn encode_tag(tag: u8, bytes: &mut Vec<u8>) {
+    bytes.push(tag);
+}
+
+#[inline]
+fn encode_usize(n: usize, bytes: &mut Vec<u8>) {
+    bytes.extend((n as u64).to_le_bytes());
+}
+
+#[inline]
+fn encode_str(s: &str, bytes: &mut Vec<u8>) {
+    encode_usize(s.len(), bytes);
+    bytes.extend(s.as_bytes());
+}
+
+#[inline]
+fn encode_f<F: PrimeField32>(f: &F, bytes: &mut Vec<u8>) {
+    bytes.extend(f.as_canonical_u32().to_le_bytes());
+}
+
+fn encode_fs<F: PrimeField32>(fs: &[F], bytes: &mut Vec<u8>) {
+    encode_usize(fs.len(), bytes);
+    fs.iter().for_each(|f| encode_f(f, bytes));
+}
+
+fn encode_usizes(ns: &[usize], bytes: &mut Vec<u8>) {
+    encode_usize(ns.len(), bytes);
+    ns.iter().for_each(|n| encode_usize(*n, bytes));
+}
+
+fn encode_slice<T: Encode>(ts: &[T], bytes: &mut Vec<u8>) {
+    encode_usize(ts.len(), bytes);
+    ts.iter().for_each(|t| t.encode(bytes));
+}
+
+impl Encode for bool {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        encode_tag(*self as u8, bytes);
+    }
+}
+
+impl Encode for Name {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        encode_str(self.0, bytes);
+    }
+}
+
+impl Encode for Var {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self.name {
+            Ident::User(name) => {
+                encode_tag(0, bytes);
+                encode_str(name, bytes);
+            }
+            Ident::Internal(n) => {
+                encode_tag(1, bytes);
+                encode_usize(n, bytes);
+            }
+        }
+        encode_usize(self.size, bytes);
+    }
+}
+
+impl Encode for VarList {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        encode_slice(self.as_slice(), bytes);
+    }
+}
+
+impl Encode for CaseType {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            CaseType::Constrained => encode_tag(0, bytes),
+            CaseType::Unconstrained => encode_tag(1, bytes),
+        }
+    }
+}
+
+impl<F: PrimeField32> Encode for OpE<F> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            OpE::AssertEq(x, y, _) => {
+                encode_tag(0, bytes);
+                x.encode(bytes);
+                y.encode(bytes);
+            }
+            OpE::AssertNe(x, y) => {
+                encode_tag(1, bytes);
+                x.encode(bytes);
+                y.encode(bytes);
+            }
+            OpE::Contains(x, y) => {
+                encode_tag(2, bytes);
+                x.encode(bytes);
+                y.encode(bytes);
+            }
+            OpE::Const(x, f) => {
+                encode_tag(3, bytes);
+                x.encode(bytes);
+                encode_f(f, bytes);
+            }
+            OpE::Array(x, fs) => {
+                encode_tag(4, bytes);
+                x.encode(bytes);
+                encode_fs(fs, bytes);
+            }
+            OpE::Add(x, y, z) => {
+                encode_tag(5, bytes);
+                [x, y, z].iter().for_each(|v| v.encode(bytes));
+            }
+            OpE::Sub(x, y, z) => {
+                encode_tag(6, bytes);
+                [x, y, z].iter().for_each(|v| v.encode(bytes));
+            }
+            OpE::Mul(x, y, z) => {
+                encode_tag(7, bytes);
+                [x, y, z].iter().for_each(|v| v.encode(bytes));
+            }
+            OpE::Div(x, y, z) => {
+                encode_tag(8, bytes);
+                [x, y, z].iter().for_each(|v| v.encode(bytes));
+            }
+            OpE::Inv(x, y) => {
+                encode_tag(9, bytes);
+                x.encode(bytes);
+                y.encode(bytes);
+            }
+            OpE::Not(x, y) => {
+                encode_tag(10, bytes);
+                x.encode(bytes);
+                y.encode(bytes);
+            }
+            OpE::Eq(x, y, z) => {
+                encode_tag(11, bytes);
+                [x, y, z].iter().for_each(|v| v.encode(bytes));
+            }
+            OpE::Call(out, name, inp) => {
+                encode_tag(12, bytes);
+                out.encode(bytes);
+                name.encode(bytes);
+                inp.encode(bytes);
+            }
+            OpE::PreImg(out, name, inp, _) => {
+                encode_tag(13, bytes);
+                out.encode(bytes);
+                name.encode(bytes);
+                inp.encode(bytes);
+            }
+            OpE::Store(x, ys) => {
+                encode_tag(14, bytes);
+                x.encode(bytes);
+                ys.encode(bytes);
+            }
+            OpE::Load(xs, y) => {
+                encode_tag(15, bytes);
+                xs.encode(bytes);
+                y.encode(bytes);
+            }
+            OpE::Slice(xs, ys) => {
+                encode_tag(16, bytes);
+                xs.encode(bytes);
+                ys.encode(bytes);
+            }
+            OpE::ExternCall(out, name, inp) => {
+                encode_tag(17, bytes);
+                out.encode(bytes);
+                name.encode(bytes);
+                inp.encode(bytes);
+            }
+            OpE::Emit(xs) => {
+                encode_tag(18, bytes);
+                xs.encode(bytes);
+            }
+            OpE::RangeU8(xs) => {
+                encode_tag(19, bytes);
+                xs.encode(bytes);
+            }
+            OpE::Breakpoint => encode_tag(20, bytes),
+            OpE::Debug(s) => {
+                encode_tag(21, bytes);
+                encode_str(s, bytes);
+            }
+        }
+    }
+}
+
+impl<F: PrimeField32> Encode for (BlockE<F>, CaseType) {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.0.encode(bytes);
+        self.1.encode(bytes);
+    }
+}
+
+impl<F: PrimeField32, B: Encode> Encode for CasesE<List<F>, B> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        encode_usize(self.branches.len(), bytes);
+        for (fs, block) in &self.branches {
+            encode_fs(fs, bytes);
+            block.encode(bytes);
+        }
+        match &self.default {
+            None => encode_tag(0, bytes),
+            Some(block) => {
+                encode_tag(1, bytes);
+                block.encode(bytes);
+            }
+        }
+    }
+}
+
+impl<F: PrimeField32> Encode for CtrlE<F> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            CtrlE::Match(x, cases) => {
+                encode_tag(0, bytes);
+                x.encode(bytes);
+                cases.encode(bytes);
+            }
+            CtrlE::MatchMany(x, cases) => {
+                encode_tag(1, bytes);
+                x.encode(bytes);
+                cases.encode(bytes);
+            }
+            CtrlE::Choose(x, cases) => {
+                encode_tag(2, bytes);
+                x.encode(bytes);
+                cases.encode(bytes);
+            }
+            CtrlE::ChooseMany(x, cases) => {
+                encode_tag(3, bytes);
+                x.encode(bytes);
+                cases.encode(bytes);
+            }
+            CtrlE::If(x, t, f) => {
+                encode_tag(4, bytes);
+                x.encode(bytes);
+                t.encode(bytes);
+                f.encode(bytes);
+            }
+            CtrlE::Return(xs) => {
+                encode_tag(5, bytes);
+                xs.encode(bytes);
+            }
+        }
+    }
+}
+
+impl<F: PrimeField32> Encode for BlockE<F> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        encode_slice(&self.ops, bytes);
+        self.ctrl.encode(bytes);
+    }
+}
+
+impl<F: PrimeField32> Encode for FuncE<F> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.name.encode(bytes);
+        self.invertible.encode(bytes);
+        self.partial.encode(bytes);
+        self.input_params.encode(bytes);
+        encode_usize(self.output_size, bytes);
+        self.body.encode(bytes);
+    }
+}
+
+impl<F: PrimeField32> Encode for Op<F> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Op::AssertEq(xs, ys, _) => {
+                encode_tag(0, bytes);
+                encode_usizes(xs, bytes);
+                encode_usizes(ys, bytes);
+            }
+            Op::AssertNe(xs, ys) => {
+                encode_tag(1, bytes);
+                encode_usizes(xs, bytes);
+                encode_usizes(ys, bytes);
+            }
+            Op::Contains(xs, y) => {
+                encode_tag(2, bytes);
+                encode_usizes(xs, bytes);
+                encode_usize(*y, bytes);
+            }
+            Op::Const(f) => {
+                encode_tag(3, bytes);
+                encode_f(f, bytes);
+            }
+            Op::Add(i, j) => {
+                encode_tag(4, bytes);
+                encode_usizes(&[*i, *j], bytes);
+            }
+            Op::Sub(i, j) => {
+                encode_tag(5, bytes);
+                encode_usizes(&[*i, *j], bytes);
+            }
+            Op::Mul(i, j) => {
+                encode_tag(6, bytes);
+                encode_usizes(&[*i, *j], bytes);
+            }
+            Op::Inv(i) => {
+                encode_tag(7, bytes);
+                encode_usize(*i, bytes);
+            }
+            Op::Not(i) => {
+                encode_tag(8, bytes);
+                encode_usize(*i, bytes);
+            }
+            Op::Call(i, inp) => {
+                encode_tag(9, bytes);
+                encode_usize(*i, bytes);
+                encode_usizes(inp, bytes);
+            }
+            Op::PreImg(i, inp, _) => {
+                encode_tag(10, bytes);
+                encode_usize(*i, bytes);
+                encode_usizes(inp, bytes);
+            }
+            Op::Store(ys) => {
+                encode_tag(11, bytes);
+                encode_usizes(ys, bytes);
+            }
+            Op::Load(len, y) => {
+                encode_tag(12, bytes);
+                encode_usizes(&[*len, *y], bytes);
+            }
+            Op::ExternCall(i, inp) => {
+                encode_tag(13, bytes);
+                encode_usize(*i, bytes);
+                encode_usizes(inp, bytes);
+            }
+            Op::Emit(xs) => {
+                encode_tag(14, bytes);
+                encode_usizes(xs, bytes);
+            }
+            Op::RangeU8(xs) => {
+                encode_tag(15, bytes);
+                encode_usizes(xs, bytes);
+            }
+            Op::Breakpoint => encode_tag(16, bytes),
+            Op::Debug(s) => {
+                encode_tag(17, bytes);
+                encode_str(s, bytes);
+            }
+        }
+    }
+}
+
+impl<F: PrimeField32> Encode for Cases<F, F> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        encode_usize(self.branches.size(), bytes);
+        for (f, block) in self.branches.iter() {
+            encode_f(f, bytes);
+            block.encode(bytes);
+        }
+        encode_default(&self.default, bytes);
+    }
+}
+
+impl<F: PrimeField32> Encode for Cases<List<F>, F> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        encode_usize(self.branches.size(), bytes);
+        for (fs, block) in self.branches.iter() {
+            encode_fs(fs, bytes);
+            block.encode(bytes);
+        }
+        encode_default(&self.default, bytes);
+    }
+}
+
+fn encode_default<F: PrimeField32>(default: &Option<Box<Block<F>>>, bytes: &mut Vec<u8>) {
+    match default {
+        None => encode_tag(0, bytes),
+        Some(block) => {
+            encode_tag(1, bytes);
+            block.encode(bytes);
+        }
+    }
+}
+
+impl<F: PrimeField32> Encode for Ctrl<F> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        match self {
+            // the unique branches are already encoded in the cases
+            Ctrl::Choose(x, cases, _) => {
+                encode_tag(0, bytes);
+                encode_usize(*x, bytes);
+                cases.encode(bytes);
+            }
+            Ctrl::ChooseMany(xs, cases) => {
+                encode_tag(1, bytes);
+                encode_usizes(xs, bytes);
+                cases.encode(bytes);
+            }
+            Ctrl::Return(sel, xs) => {
+                encode_tag(2, bytes);
+                encode_usize(*sel, bytes);
+                encode_usizes(xs, bytes);
+            }
+        }
+    }
+}
+
+impl<F: PrimeField32> Encode for Block<F> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        encode_slice(&self.ops, bytes);
+        self.ctrl.encode(bytes);
+        encode_usizes(&self.return_idents, bytes);
+    }
+}
+
+impl<F: PrimeField32> Encode for Func<F> {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        self.name.encode(bytes);
+        self.invertible.encode(bytes);
+        self.partial.encode(bytes);
+        encode_usizes(&[self.index, self.input_size, self.output_size], bytes);
+        self.body.encode(bytes);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use p3_baby_bear::BabyBear as F;
+
+    use crate::func;
+
+    use super::Encode;
+
+    #[test]
+    fn test_encoding_ignores_fmt() {
+        fn fmt(_: &[F], _: &[F]) -> String {
+            "error".into()
+        }
+        let plain = func!(
+            fn f(a, b): [0] {
+                assert_eq!(a, b);
+                return ()
+            }
+        );
+        let with_fmt = func!(
+            fn f(a, b): [0] {
+                assert_eq!(a, b, fmt);
+                return ()
+            }
+        );
+        let other = func!(
+            fn f(a, b): [0] {
+                assert_ne!(a, b);
+                return ()
+            }
+        );
+        assert_eq!(plain.to_canonical_bytes(), with_fmt.to_canonical_bytes());
+        assert_ne!(plain.to_canonical_bytes(), other.to_canonical_bytes());
+    }
+}
@@ -9,6 +9,7 @@ use self::{chipset::NoChip, toplevel::Toplevel};
 pub mod air;
 pub mod bytecode;
 pub mod chipset;
+pub mod encoding;
 pub mod execute;
 pub mod expr;
 pub mod func_chip;
@@ -526,8 +526,8 @@ impl Store {
                 let (body, ..) = self.fetch_tuple3(ptr);
                 format!("<Thunk {}>", self.fmt(zstore, body))
             }
-            Tag::Err => format!("<Err {:?}>", EvalErr::from_field(&ptr.addr())),
-            Tag::U64 | Tag::Char | Tag::Comm | Tag::Str | Tag::Env => unimplemented!(),
+            Tag::Err => format!("<Err {:?}>", EvalErr::from_code(&ptr.addr())),
+            Tag::U64 | Tag::I64 | Tag::Char | Tag::Comm | Tag::Str | Tag::Env => unimplemented!(),
         }
     }
 }
@@ -4,40 +4,67 @@ use num_bigint::BigUint;
 use p3_air::AirBuilder;
 use p3_field::{PrimeField, PrimeField32};
 
-use crate::gadgets::big_num::cmp::BigNumCompareWitness;
+use crate::gadgets::big_num::{
+    arith::{
+        BigNumAddWitness, BigNumDivRemWitness, BigNumMulModWitness, BigNumMulWitness,
+        BigNumSubWitness,
+    },
+    cmp::BigNumCompareWitness,
+};
 use crate::{
     air::builder::{LookupBuilder, Record, RequireRecord},
     gadgets::bytes::{builder::BytesAirRecordWithContext, record::DummyBytesRecord},
     lair::{chipset::Chipset, execute::QueryRecord},
 };
 
+/// Chips for the arithmetic over `BigNum`s. Addition, subtraction and multiplication wrap
+/// around modulo `p^8`, where `p` is the field order.
 #[derive(Clone)]
 pub enum BigNum {
     LessThan,
+    Add,
+    Sub,
+    Mul,
+    DivRem,
+    /// Multiplication modulo the third argument, where the operands must be smaller than it
+    MulMod,
 }
 
 impl<F: PrimeField32> Chipset<F> for BigNum {
     fn input_size(&self) -> usize {
         match self {
-            BigNum::LessThan => 16,
+            BigNum::LessThan | BigNum::Add | BigNum::Sub | BigNum::Mul | BigNum::DivRem => 16,
+            BigNum::MulMod => 24,
         }
     }
 
     fn output_size(&self) -> usize {
         match self {
             BigNum::LessThan => 1,
+            BigNum::Add | BigNum::Sub | BigNum::Mul | BigNum::MulMod => 8,
+            BigNum::DivRem => 16,
         }
     }
 
     fn witness_size(&self) -> usize {
         match self {
             BigNum::LessThan => BigNumCompareWitness::<F>::witness_size(),
+            BigNum::Add => BigNumAddWitness::<F>::witness_size(),
+            BigNum::Sub => BigNumSubWitness::<F>::witness_size(),
+            BigNum::Mul => BigNumMulWitness::<F>::witness_size(),
+            BigNum::DivRem => BigNumDivRemWitness::<F>::witness_size(),
+            BigNum::MulMod => BigNumMulModWitness::<F>::witness_size(),
         }
     }
 
     fn require_size(&self) -> usize {
         match self {
             BigNum::LessThan => BigNumCompareWitness::<F>::num_requires(),
+            BigNum::Add => BigNumAddWitness::<F>::num_requires(),
+            BigNum::Sub => BigNumSubWitness::<F>::num_requires(),
+            BigNum::Mul => BigNumMulWitness::<F>::num_requires(),
+            BigNum::DivRem => BigNumDivRemWitness::<F>::num_requires(),
+            BigNum::MulMod => BigNumMulModWitness::<F>::num_requires(),
         }
     }
 
@@ -57,6 +84,28 @@ impl<F: PrimeField32> Chipset<F> for BigNum {
                 witness.populate(&in1, &in2, bytes);
                 witness.iter_result().into_iter().collect()
             }
+            BigNum::Add => {
+                let mut witness = BigNumAddWitness::<F>::default();
+                witness.populate(&in1, &in2, bytes).to_vec()
+            }
+            BigNum::Sub => {
+                let mut witness = BigNumSubWitness::<F>::default();
+                witness.populate(&in1, &in2, bytes).to_vec()
+            }
+            BigNum::Mul => {
+                let mut witness = BigNumMulWitness::<F>::default();
+                witness.populate(&in1, &in2, bytes).to_vec()
+            }
+            BigNum::DivRem => {
+                let mut witness = BigNumDivRemWitness::<F>::default();
+                let (quot, rem) = witness.populate(&in1, &in2, bytes);
+                [quot, rem].concat()
+            }
+            BigNum::MulMod => {
+                let in3: [F; 8] = input[16..24].try_into().unwrap();
+                let mut witness = BigNumMulModWitness::<F>::default();
+                witness.populate(&in1, &in2, &in3, bytes).to_vec()
+            }
         }
     }
 
@@ -70,6 +119,28 @@ impl<F: PrimeField32> Chipset<F> for BigNum {
                 witness.populate(&in1, &in2, bytes);
                 witness.iter_result().into_iter().collect()
             }
+            BigNum::Add => {
+                let witness: &mut BigNumAddWitness<F> = witness.borrow_mut();
+                witness.populate(&in1, &in2, bytes).to_vec()
+            }
+            BigNum::Sub => {
+                let witness: &mut BigNumSubWitness<F> = witness.borrow_mut();
+                witness.populate(&in1, &in2, bytes).to_vec()
+            }
+            BigNum::Mul => {
+                let witness: &mut BigNumMulWitness<F> = witness.borrow_mut();
+                witness.populate(&in1, &in2, bytes).to_vec()
+            }
+            BigNum::DivRem => {
+                let witness: &mut BigNumDivRemWitness<F> = witness.borrow_mut();
+                let (quot, rem) = witness.populate(&in1, &in2, bytes);
+                [quot, rem].concat()
+            }
+            BigNum::MulMod => {
+                let in3: [F; 8] = input[16..24].try_into().unwrap();
+                let witness: &mut BigNumMulModWitness<F> = witness.borrow_mut();
+                witness.populate(&in1, &in2, &in3, bytes).to_vec()
+            }
         }
     }
 
@@ -91,6 +162,33 @@ impl<F: PrimeField32> Chipset<F> for BigNum {
                 let cmp = witness.eval(builder, &in1, &in2, &mut air_record, is_real.clone());
                 vec![cmp.is_less_than()]
             }
+            BigNum::Add => {
+                let witness: &BigNumAddWitness<AB::Var> = witness.borrow();
+                let out = witness.eval(builder, &in1, &in2, &mut air_record, is_real.clone());
+                out.into_iter().map(Into::into).collect()
+            }
+            BigNum::Sub => {
+                let witness: &BigNumSubWitness<AB::Var> = witness.borrow();
+                let out = witness.eval(builder, &in1, &in2, &mut air_record, is_real.clone());
+                out.into_iter().map(Into::into).collect()
+            }
+            BigNum::Mul => {
+                let witness: &BigNumMulWitness<AB::Var> = witness.borrow();
+                let out = witness.eval(builder, &in1, &in2, &mut air_record, is_real.clone());
+                out.into_iter().map(Into::into).collect()
+            }
+            BigNum::DivRem => {
+                let witness: &BigNumDivRemWitness<AB::Var> = witness.borrow();
+                let (quot, rem) =
+                    witness.eval(builder, &in1, &in2, &mut air_record, is_real.clone());
+                quot.into_iter().chain(rem).map(Into::into).collect()
+            }
+            BigNum::MulMod => {
+                let in3: [AB::Expr; 8] = ins[16..24].to_vec().try_into().unwrap();
+                let witness: &BigNumMulModWitness<AB::Var> = witness.borrow();
+                let out = witness.eval(builder, &in1, &in2, &in3, &mut air_record, is_real.clone());
+                out.into_iter().map(Into::into).collect()
+            }
         };
         air_record.require_all(builder, nonce, requires.iter().cloned());
         out
@@ -107,10 +205,23 @@ pub fn field_elts_to_biguint<F: PrimeField>(elts: &[F]) -> BigUint {
     num
 }
 
+/// Returns the `N` field elements, in little-endian order, that represent a
+/// `BigUint`, or `None` if it doesn't fit.
+pub fn biguint_to_field_elts<F: PrimeField32, const N: usize>(num: &BigUint) -> Option<[F; N]> {
+    let mut num = num.clone();
+    let mut elts = [F::zero(); N];
+    for elt in &mut elts {
+        let rem = &num % F::order();
+        *elt = F::from_canonical_u32(rem.try_into().unwrap());
+        num /= F::order();
+    }
+    (num == BigUint::ZERO).then_some(elts)
+}
+
 #[cfg(test)]
 mod test {
     use p3_baby_bear::BabyBear as F;
-    use p3_field::AbstractField;
+    use p3_field::{AbstractField, PrimeField};
     use sphinx_core::{stark::StarkMachine, utils::BabyBearPoseidon2};
 
     use crate::{
@@ -122,9 +233,14 @@ mod test {
             lair_chip::{build_chip_vector, build_lair_chip_vector, LairMachineProgram},
             toplevel::Toplevel,
         },
-        lurk::chipset::lurk_chip_map_native,
+        lurk::{
+            chipset::lurk_chip_map_native,
+            misc::{big_num_modexp, big_num_modexp_reduced},
+        },
     };
 
+    use super::{biguint_to_field_elts, field_elts_to_biguint};
+
     #[test]
     fn big_num_lessthan_test() {
         sphinx_core::utils::setup_logger();
@@ -179,4 +295,91 @@ mod test {
         let shard = Shard::new(&queries);
         machine.debug_constraints(&pk, shard.clone());
     }
+
+    #[test]
+    fn big_num_arith_test() {
+        let arith_func = func!(
+        fn arith(a: [8], b: [8]): [40] {
+            let sum: [8] = extern_call(big_num_add, a, b);
+            let diff: [8] = extern_call(big_num_sub, a, b);
+            let prod: [8] = extern_call(big_num_mul, a, b);
+            let (quot: [8], rem: [8]) = extern_call(big_num_divrem, a, b);
+            return (sum, diff, prod, quot, rem)
+        });
+        let lurk_chip_map = lurk_chip_map_native();
+        let toplevel = Toplevel::new(&[arith_func], lurk_chip_map);
+
+        let arith_chip = FuncChip::from_name("arith", &toplevel);
+        let mut queries = QueryRecord::new(&toplevel);
+        let f = F::from_canonical_u32;
+        let a = [1, 2, 3, 4, 5, 6, 7, 8].map(|x| f(x * 123456789));
+        let b = [0x77777777, 5, 0, 0, 0, 0, 0, 9].map(f);
+        let args = &[a, b].concat();
+        let out = toplevel
+            .execute_by_name("arith", args, &mut queries, None)
+            .unwrap();
+
+        let modulus = F::order().pow(8);
+        let (a, b) = (field_elts_to_biguint(&a), field_elts_to_biguint(&b));
+        let expected = [
+            (&a + &b) % &modulus,
+            (&a + &modulus - &b) % &modulus,
+            (&a * &b) % &modulus,
+            &a / &b,
+            &a % &b,
+        ];
+        for (digest, expected) in out.chunks(8).zip(expected) {
+            assert_eq!(field_elts_to_biguint(digest), expected);
+        }
+
+        let lair_chips = build_lair_chip_vector(&arith_chip);
+        debug_chip_constraints_and_queries_with_sharding(&queries, &lair_chips, None);
+    }
+
+    #[test]
+    fn big_num_modexp_test() {
+        let modexp_func = func!(
+        fn modexp(base: [8], exp: [8], modulus: [8]): [8] {
+            let base = store(base);
+            let exp = store(exp);
+            let modulus = store(modulus);
+            let res = call(big_num_modexp, base, exp, modulus);
+            let res: [8] = load(res);
+            return res
+        });
+        let lurk_chip_map = lurk_chip_map_native();
+        let toplevel = Toplevel::new(
+            &[modexp_func, big_num_modexp(), big_num_modexp_reduced()],
+            lurk_chip_map,
+        );
+
+        let modexp_chip = FuncChip::from_name("modexp", &toplevel);
+        let mut queries = QueryRecord::new(&toplevel);
+        let f = F::from_canonical_u32;
+        let base = [7, 0, 0, 0, 0, 0, 0, 0x12345678].map(f);
+        let exp = [65537, 3, 0, 0, 0, 0, 0, 0].map(f);
+        let modulus = [0x2d, 0, 0, 0, 1, 0, 0, 0x6fffffff].map(f);
+        let args = &[base, exp, modulus].concat();
+        let out = toplevel
+            .execute_by_name("modexp", args, &mut queries, None)
+            .unwrap();
+
+        let expected = field_elts_to_biguint(&base).modpow(
+            &field_elts_to_biguint(&exp),
+            &field_elts_to_biguint(&modulus),
+        );
+        assert_eq!(field_elts_to_biguint(&out), expected);
+
+        let lair_chips = build_lair_chip_vector(&modexp_chip);
+        debug_chip_constraints_and_queries_with_sharding(&queries, &lair_chips, None);
+    }
+
+    #[test]
+    fn biguint_to_field_elts_test() {
+        let num = F::order() * 3u32 + 7u32;
+        let elts: [F; 2] = biguint_to_field_elts(&num).unwrap();
+        assert_eq!(elts, [F::from_canonical_u32(7), F::from_canonical_u32(3)]);
+        assert_eq!(field_elts_to_biguint(&elts), num);
+        assert!(biguint_to_field_elts::<F, 1>(&num).is_none());
+    }
 }
@@ -37,7 +37,18 @@ pub fn lurk_chip_map<C2: Chipset<BabyBear>>(
     let u64_divrem = LurkChip::U64(U64::DivRem);
     let u64_lessthan = LurkChip::U64(U64::LessThan);
     let u64_iszero = LurkChip::U64(U64::IsZero);
+    let u64_and = LurkChip::U64(U64::And);
+    let u64_or = LurkChip::U64(U64::Or);
+    let u64_xor = LurkChip::U64(U64::Xor);
+    let u64_shl = LurkChip::U64(U64::Shl);
+    let u64_shr = LurkChip::U64(U64::Shr);
+    let u64_from_num = LurkChip::U64(U64::FromNum);
     let big_num_lessthan = LurkChip::BigNum(BigNum::LessThan);
+    let big_num_add = LurkChip::BigNum(BigNum::Add);
+    let big_num_sub = LurkChip::BigNum(BigNum::Sub);
+    let big_num_mul = LurkChip::BigNum(BigNum::Mul);
+    let big_num_divrem = LurkChip::BigNum(BigNum::DivRem);
+    let big_num_mulmod = LurkChip::BigNum(BigNum::MulMod);
     let mut chips: FxIndexMap<_, _> = [
         (Name("hasher3"), Either::Left(hasher3)),
         (Name("hasher4"), Either::Left(hasher4)),
@@ -48,7 +59,18 @@ pub fn lurk_chip_map<C2: Chipset<BabyBear>>(
         (Name("u64_divrem"), Either::Left(u64_divrem)),
         (Name("u64_lessthan"), Either::Left(u64_lessthan)),
         (Name("u64_iszero"), Either::Left(u64_iszero)),
+        (Name("u64_and"), Either::Left(u64_and)),
+        (Name("u64_or"), Either::Left(u64_or)),
+        (Name("u64_xor"), Either::Left(u64_xor)),
+        (Name("u64_shl"), Either::Left(u64_shl)),
+        (Name("u64_shr"), Either::Left(u64_shr)),
+        (Name("u64_from_num"), Either::Left(u64_from_num)),
         (Name("big_num_lessthan"), Either::Left(big_num_lessthan)),
+        (Name("big_num_add"), Either::Left(big_num_add)),
+        (Name("big_num_sub"), Either::Left(big_num_sub)),
+        (Name("big_num_mul"), Either::Left(big_num_mul)),
+        (Name("big_num_divrem"), Either::Left(big_num_divrem)),
+        (Name("big_num_mulmod"), Either::Left(big_num_mulmod)),
     ]
     .into_iter()
     .collect();
@@ -6,12 +6,11 @@ use crate::{
     lair::chipset::Chipset,
     lurk::{
         tag::Tag,
+        zdag::ZDag,
         zstore::{ZPtr, ZStore, DIGEST_SIZE, HASH3_SIZE},
     },
 };
 
-use super::zdag::ZDag;
-
 #[derive(Serialize, Deserialize, Clone)]
 pub(crate) struct CommData<F: Hash + Eq> {
     pub(crate) secret: [F; DIGEST_SIZE],
@@ -0,0 +1,375 @@
+//! JSON encoding of Lurk data, meant for tooling that consumes REPL results
+//! and proof claims.
+//!
+//! A Lurk DAG is encoded as an object with two fields:
+//! * `"root"`: the encoding of the root value
+//! * `"nodes"`: an object mapping the key of every compound value reachable
+//!   from the root, in the form `"<tag>:<digest>"`, to its encoding
+//!
+//! Compound values (`Cons`, `Fun`, `Fix`, `Env` and `Err`s with a payload) are
+//! encoded once in `"nodes"` and referenced elsewhere by an object with only
+//! their `"tag"` and `"digest"`, so shared subterms aren't repeated.
+//!
+//! Every Lurk value is encoded as an object with the following fields:
+//! * `"tag"`: the name of its tag, such as `"Num"` or `"Cons"`
+//! * `"digest"`: its digest as a hexadecimal string
+//!
+//! And, depending on the tag:
+//! * `Num`: `"value"` with the field element as a number
+//! * `U64` and `I64`: `"value"` with the integer as a number
+//! * `Char` and `Str`: `"value"` with the text as a string
+//! * `BigNum` and `Comm`: `"value"` with the hexadecimal representation
+//! * `Sym`, `Key`, `Builtin` and `Coroutine`: `"value"` with the formatted symbol
+//! * `Cons`: `"elements"` with the list elements and, for improper lists,
+//!   `"tail"` with the last `cdr`
+//! * `Fun`: `"args"`, `"body"` and `"env"`
+//! * `Fix`: `"body"`, `"mutual_env"` and `"env"`
+//! * `Env`: `"bindings"` with a list of objects with `"var"` and `"val"` and,
+//!   if the rest of the environment isn't available, `"hidden"` with it
+//! * `Err`: `"value"` with the name of the error and, if there's one, `"payload"`
+//!
+//! Values whose data isn't available are encoded with `"opaque": true` and no
+//! other fields than `"tag"` and `"digest"`. They never appear in `"nodes"`.
+//!
+//! Arbitrary JSON data can also be imported as Lurk data with `from_json`.
+
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use num_bigint::BigUint;
+use p3_field::PrimeField32;
+use serde_json::{json, value::RawValue, Map, Value};
+use std::collections::BTreeMap;
+
+use crate::{
+    lair::chipset::Chipset,
+    lurk::{
+        big_num::{biguint_to_field_elts, field_elts_to_biguint},
+        state::StateRcCell,
+        symbol::Symbol,
+        tag::Tag,
+        zstore::{get_char, ZPtr, ZPtrType, ZStore},
+    },
+};
+
+/// How the results of Lurk reductions are printed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// Lurk syntax
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Encodes a `ZPtr` DAG from a `ZStore` as JSON, with shared nodes encoded once
+pub(crate) fn to_json<F: PrimeField32, C: Chipset<F>>(
+    zstore: &ZStore<F, C>,
+    state: &StateRcCell,
+    zptr: &ZPtr<F>,
+) -> Value {
+    let mut nodes = Map::new();
+    let root = encode(zstore, state, zptr, &mut nodes);
+    json!({ "root": root, "nodes": nodes })
+}
+
+/// The key of a compound value in the `"nodes"` object
+pub(crate) fn node_key<F: PrimeField32>(zptr: &ZPtr<F>) -> String {
+    format!("{:?}:{:#x}", zptr.tag, field_elts_to_biguint(&zptr.digest))
+}
+
+fn header<F: PrimeField32>(zptr: &ZPtr<F>) -> Map<String, Value> {
+    let mut obj = Map::new();
+    obj.insert("tag".into(), json!(format!("{:?}", zptr.tag)));
+    obj.insert(
+        "digest".into(),
+        json!(format!("{:#x}", field_elts_to_biguint(&zptr.digest))),
+    );
+    obj
+}
+
+/// Encodes a value, adding the compound values it reaches to `nodes` and
+/// returning a reference in place of compound values
+fn encode<F: PrimeField32, C: Chipset<F>>(
+    zstore: &ZStore<F, C>,
+    state: &StateRcCell,
+    zptr: &ZPtr<F>,
+    nodes: &mut Map<String, Value>,
+) -> Value {
+    let mut obj = header(zptr);
+    // errors are atoms unless they carry a payload
+    let is_atom = match zptr.tag {
+        Tag::Num | Tag::U64 | Tag::I64 | Tag::Char | Tag::BigNum | Tag::Comm => true,
+        Tag::Err => zptr.digest[1..].iter().all(F::is_zero),
+        _ => false,
+    };
+    if !is_atom && !zstore.dag.contains_key(zptr) {
+        obj.insert("opaque".into(), json!(true));
+        return Value::Object(obj);
+    }
+    match zptr.tag {
+        Tag::Num => {
+            obj.insert("value".into(), json!(zptr.digest[0].as_canonical_u32()));
+        }
+        Tag::U64 => {
+            let bytes = zptr
+                .digest
+                .map(|f| u8::try_from(f.as_canonical_u32()).expect("invalid u64 limbs"));
+            obj.insert("value".into(), json!(u64::from_le_bytes(bytes)));
+        }
+        Tag::I64 => {
+            let bytes = zptr
+                .digest
+                .map(|f| u8::try_from(f.as_canonical_u32()).expect("invalid i64 limbs"));
+            obj.insert("value".into(), json!(i64::from_le_bytes(bytes)));
+        }
+        Tag::Char => {
+            obj.insert("value".into(), json!(get_char(&zptr.digest).to_string()));
+        }
+        Tag::Str => {
+            obj.insert("value".into(), json!(zstore.fetch_string(zptr)));
+        }
+        Tag::BigNum | Tag::Comm => {
+            let value = format!("{:#x}", field_elts_to_biguint(&zptr.digest));
+            obj.insert("value".into(), json!(value));
+        }
+        Tag::Sym | Tag::Key | Tag::Builtin | Tag::Coroutine => {
+            let value = state.borrow().fmt_to_string(&zstore.fetch_symbol(zptr));
+            obj.insert("value".into(), json!(value));
+        }
+        Tag::Err if is_atom => {
+            let (err, _) = zstore.fetch_error(zptr);
+            obj.insert("value".into(), json!(format!("{err:?}")));
+        }
+        Tag::Cons | Tag::Fun | Tag::Fix | Tag::Env | Tag::Err => {
+            let key = node_key(zptr);
+            if !nodes.contains_key(&key) {
+                let node = encode_node(zstore, state, zptr, obj.clone(), nodes);
+                nodes.insert(key, node);
+            }
+        }
+    }
+    Value::Object(obj)
+}
+
+/// Encodes the fields of a compound value whose data is in the `ZStore`
+fn encode_node<F: PrimeField32, C: Chipset<F>>(
+    zstore: &ZStore<F, C>,
+    state: &StateRcCell,
+    zptr: &ZPtr<F>,
+    mut obj: Map<String, Value>,
+    nodes: &mut Map<String, Value>,
+) -> Value {
+    let mut encode = |zptr: &ZPtr<F>| encode(zstore, state, zptr, nodes);
+    match zptr.tag {
+        Tag::Cons => {
+            let mut elts = vec![];
+            let mut tail = zptr;
+            while tail.tag == Tag::Cons {
+                let Some(ZPtrType::Tuple11(car, cdr)) = zstore.dag.get(tail) else {
+                    break;
+                };
+                elts.push(encode(car));
+                tail = cdr;
+            }
+            obj.insert("elements".into(), Value::Array(elts));
+            if tail != zstore.nil() {
+                obj.insert("tail".into(), encode(tail));
+            }
+        }
+        Tag::Fun => {
+            let (args, body, env) = zstore.fetch_tuple110(zptr);
+            obj.insert("args".into(), encode(args));
+            obj.insert("body".into(), encode(body));
+            obj.insert("env".into(), encode(env));
+        }
+        Tag::Fix => {
+            let (body, mutual_env, env) = zstore.fetch_tuple110(zptr);
+            obj.insert("body".into(), encode(body));
+            obj.insert("mutual_env".into(), encode(mutual_env));
+            obj.insert("env".into(), encode(env));
+        }
+        Tag::Env => {
+            let mut bindings = vec![];
+            let mut tail = zptr;
+            while tail.digest.iter().any(|f| !f.is_zero()) {
+                let Some(ZPtrType::Tuple110(var, val, next)) = zstore.dag.get(tail) else {
+                    obj.insert("hidden".into(), encode(tail));
+                    break;
+                };
+                bindings.push(json!({ "var": encode(var), "val": encode(val) }));
+                tail = next;
+            }
+            obj.insert("bindings".into(), Value::Array(bindings));
+        }
+        Tag::Err => {
+            let (err, payload) = zstore.fetch_error(zptr);
+            obj.insert("value".into(), json!(format!("{err:?}")));
+            if let Some(payload) = payload {
+                obj.insert("payload".into(), encode(payload));
+            }
+        }
+        _ => unreachable!("not a compound value"),
+    }
+    Value::Object(obj)
+}
+
+/// Interns JSON data as Lurk data:
+/// * `null` and `false` become `nil` and `true` becomes `t`
+/// * Non-negative integers up to 2^64-1 become `U64`s, other integers from
+///   -2^63 become `I64`s and bigger non-negative integers become `BigNum`s
+/// * Strings become `Str`s
+/// * Arrays become lists
+/// * Objects become property lists, with their keys as keywords, sorted
+///
+/// Since `false` is the falsy `nil` in Lurk, `null`, `false` and `[]` all
+/// become `nil` and can't be told apart after the import.
+///
+/// Numbers are read from the JSON text itself, so big integers keep their
+/// precision. Errors on numbers that aren't representable as `U64`s, `I64`s or
+/// `BigNum`s.
+pub(crate) fn from_json<F: PrimeField32, C: Chipset<F>>(
+    zstore: &mut ZStore<F, C>,
+    json: &RawValue,
+) -> Result<ZPtr<F>> {
+    let text = json.get().trim();
+    let zptr = match text.as_bytes().first() {
+        Some(b'-' | b'0'..=b'9') => intern_number(zstore, text)?,
+        Some(b'[') => {
+            let xs: Vec<&RawValue> = serde_json::from_str(text)?;
+            let zptrs = xs
+                .into_iter()
+                .map(|x| from_json(zstore, x))
+                .collect::<Result<Vec<_>>>()?;
+            zstore.intern_list(zptrs)
+        }
+        Some(b'{') => {
+            let map: BTreeMap<String, &RawValue> = serde_json::from_str(text)?;
+            let mut zptrs = Vec::with_capacity(2 * map.len());
+            for (key, val) in map {
+                zptrs.push(zstore.intern_symbol_no_lang(&Symbol::key(&[key])));
+                zptrs.push(from_json(zstore, val)?);
+            }
+            zstore.intern_list(zptrs)
+        }
+        _ => match serde_json::from_str(text)? {
+            Value::Null | Value::Bool(false) => *zstore.nil(),
+            Value::Bool(true) => *zstore.t(),
+            Value::String(s) => zstore.intern_string(&s),
+            _ => unreachable!("numbers, arrays and objects are handled above"),
+        },
+    };
+    Ok(zptr)
+}
+
+/// Interns a JSON number from its text, as described in `from_json`
+fn intern_number<F: PrimeField32, C: Chipset<F>>(
+    zstore: &mut ZStore<F, C>,
+    text: &str,
+) -> Result<ZPtr<F>> {
+    if let Ok(u) = text.parse::<u64>() {
+        return Ok(zstore.intern_u64(u));
+    }
+    if let Ok(i) = text.parse::<i64>() {
+        return Ok(zstore.intern_i64(i));
+    }
+    let digest = text
+        .parse::<BigUint>()
+        .ok()
+        .and_then(|num| biguint_to_field_elts(&num));
+    let Some(digest) = digest else {
+        bail!("Unsupported number {text}: only u64, i64 and big num integers are supported");
+    };
+    Ok(zstore.intern_big_num(digest))
+}
+
+#[cfg(test)]
+mod test {
+    use anyhow::Result;
+    use p3_field::PrimeField32;
+    use serde_json::json;
+
+    use crate::{
+        lair::chipset::Chipset,
+        lurk::{
+            state::State,
+            zstore::{lurk_zstore, ZPtr, ZStore},
+        },
+    };
+
+    use super::{from_json, node_key, to_json};
+
+    #[test]
+    fn test_to_json() {
+        let mut zstore = lurk_zstore();
+        let state = State::init_lurk_state().rccell();
+        let expr = zstore.read("(1 \"a\" . 2u64)", &Default::default());
+        let json = to_json(&zstore, &state, &expr);
+        assert_eq!(json["root"]["tag"], json!("Cons"));
+        let node = &json["nodes"][node_key(&expr)];
+        let elements = node["elements"].as_array().unwrap();
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[0]["tag"], json!("Num"));
+        assert_eq!(elements[0]["value"], json!(1));
+        assert_eq!(elements[1]["value"], json!("a"));
+        assert_eq!(node["tail"]["tag"], json!("U64"));
+        assert_eq!(node["tail"]["value"], json!(2));
+    }
+
+    #[test]
+    fn test_to_json_shares_nodes() {
+        let mut zstore = lurk_zstore();
+        let state = State::init_lurk_state().rccell();
+        let expr = zstore.read("((1 2) (1 2))", &Default::default());
+        let json = to_json(&zstore, &state, &expr);
+        let nodes = json["nodes"].as_object().unwrap();
+        assert_eq!(nodes.len(), 2);
+        let elements = nodes[&node_key(&expr)]["elements"].as_array().unwrap();
+        assert_eq!(elements[0], elements[1]);
+        assert!(elements[0].get("elements").is_none());
+        let inner = zstore.read("(1 2)", &Default::default());
+        assert_eq!(
+            nodes[&node_key(&inner)]["elements"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    fn import<F: PrimeField32, C: Chipset<F>>(
+        zstore: &mut ZStore<F, C>,
+        json: &str,
+    ) -> Result<ZPtr<F>> {
+        from_json(zstore, serde_json::from_str(json)?)
+    }
+
+    #[test]
+    fn test_from_json() {
+        let mut zstore = lurk_zstore();
+        let value = r#"{ "tags": [1, true, null], "name": "lurk" }"#;
+        let zptr = import(&mut zstore, value).unwrap();
+        let expected = zstore.read("(:name \"lurk\" :tags (1u64 t nil))", &Default::default());
+        assert_eq!(zptr, expected);
+        let name = zstore.intern_string("lurk");
+        let map = zstore.property_map(&zptr).unwrap();
+        assert_eq!(map["name"], &name);
+
+        assert!(import(&mut zstore, "1.5").is_err());
+    }
+
+    #[test]
+    fn test_from_json_numbers() {
+        let mut zstore = lurk_zstore();
+        let mut import_number = |json: &str| import(&mut zstore, json);
+        let minus_one = import_number("-1").unwrap();
+        let big = import_number("18446744073709551616").unwrap();
+        assert!(import_number("-9223372036854775809").is_err());
+        assert!(import_number("1e3").is_err());
+        assert!(import_number(&"9".repeat(80)).is_err());
+        assert_eq!(minus_one, zstore.intern_i64(-1));
+        assert_eq!(
+            big,
+            zstore.read("#0x10000000000000000", &Default::default())
+        );
+    }
+}
@@ -3,11 +3,12 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     lair::chipset::Chipset,
-    lurk::zstore::{ZPtr, ZStore},
+    lurk::{
+        zdag::ZDag,
+        zstore::{ZPtr, ZStore},
+    },
 };
 
-use super::zdag::ZDag;
-
 #[derive(Serialize, Deserialize, Clone)]
 pub(crate) struct LurkData<F: std::hash::Hash + Eq> {
     pub(crate) zptr: ZPtr<F>,
@@ -1,21 +1,22 @@
 use anyhow::{bail, Result};
 use camino::Utf8Path;
 use itertools::Itertools;
+use k256::ecdsa::SigningKey;
 use p3_baby_bear::BabyBear;
 use p3_field::PrimeField32;
 use rustc_hash::FxHashMap;
-use sphinx_core::stark::StarkGenericConfig;
 use std::net::TcpStream;
 
 use crate::{
-    lair::{chipset::Chipset, lair_chip::LairMachineProgram},
+    lair::chipset::Chipset,
     lurk::{
         big_num::field_elts_to_biguint,
+        lang::is_lang_id,
         package::{Package, SymbolRef},
-        stark_machine::new_machine,
         state::{builtin_sym, meta_sym, META_SYMBOLS},
         symbol::Symbol,
         tag::Tag,
+        verifier::{CachedProof, Verifier},
         zstore::{ZPtr, DIGEST_SIZE},
     },
     ocaml::compile::compile_and_transform_single_file,
@@ -24,12 +25,18 @@ use crate::{
 use super::{
     comm_data::CommData,
     debug::debug_mode,
+    json::{from_json, OutputFormat},
     lurk_data::LurkData,
-    microchain::{read_data, write_data, CallableData, ChainState, Request, Response},
+    microchain::{
+        connect, generate_key, public_key_bytes, read_data, read_key, request_export,
+        request_import, sign_transition, write_data, write_key, CallableData, ChainArchive,
+        ChainState, Checkpoint, Request, Response, Retention, TransitionEvent, MAX_RANGE_SIZE,
+    },
     paths::{commits_dir, proofs_dir},
-    proofs::{get_verifier_version, CachedProof, ChainProof, OpaqueChainProof, ProtocolProof},
+    proof_store::{fmt_proofs, list_proofs, ProofTags},
+    proofs::{ChainProof, OpaqueChainProof, ProtocolProof},
     rdg::rand_digest,
-    repl::Repl,
+    repl::{text_print, text_println, Repl},
 };
 
 #[allow(clippy::type_complexity)]
@@ -569,18 +576,25 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         },
     };
 
-    fn transition_call(
+    fn reduce_current_state(
         repl: &mut Repl<F, C1, C2>,
         current_state_expr: &ZPtr<F>,
-        call_args: ZPtr<F>,
-        env: Option<ZPtr<F>>,
     ) -> Result<ZPtr<F>> {
         let (current_state, _) = repl.reduce_aux(current_state_expr)?;
         if current_state.tag != Tag::Cons {
             bail!("Current state must reduce to a pair");
         }
         repl.memoize_dag(current_state.tag, &current_state.digest);
-        let (_, &callable) = repl.zstore.fetch_tuple11(&current_state);
+        Ok(current_state)
+    }
+
+    fn transition_call(
+        repl: &mut Repl<F, C1, C2>,
+        current_state: &ZPtr<F>,
+        call_args: ZPtr<F>,
+        env: Option<ZPtr<F>>,
+    ) -> Result<ZPtr<F>> {
+        let (_, &callable) = repl.zstore.fetch_tuple11(current_state);
         let call_expr = repl.zstore.intern_cons(callable, call_args);
         Self::call(repl, &call_expr, env)
     }
@@ -594,7 +608,8 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         returns: "The chained result",
         run: |repl, args, _dir| {
             let (&current_state_expr, &call_args) = repl.car_cdr(args);
-            let cons = Self::transition_call(repl, &current_state_expr, call_args, None)?;
+            let current_state = Self::reduce_current_state(repl, &current_state_expr)?;
+            let cons = Self::transition_call(repl, &current_state, call_args, None)?;
             Self::persist_chain_comm(repl, &cons)?;
             Ok(cons)
         },
@@ -708,7 +723,7 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
             let lurk_data = LurkData::new(result, &repl.zstore);
             let lurk_data_bytes = bincode::serialize(&lurk_data)?;
             std::fs::write(&path_str, lurk_data_bytes)?;
-            println!("Data persisted on file `{path_str}`");
+            text_println!(repl, "Data persisted on file `{path_str}`");
             Ok(result)
         },
     };
@@ -734,6 +749,43 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         },
     };
 
+    const TO_JSON: Self = Self {
+        name: "to-json",
+        summary: "Evaluates an expression and encodes the result as JSON",
+        info: &["The JSON schema is documented in the `json` module of the CLI."],
+        format: "!(to-json <expr>)",
+        example: &["!(to-json '(1 \"a\" . 2u64))"],
+        returns: "The JSON encoding as a string",
+        run: |repl, args, _dir| {
+            let [&expr] = repl.take(args)?;
+            let (result, _) = repl.reduce_aux(&expr)?;
+            repl.memoize_dag(result.tag, &result.digest);
+            let json = repl.to_json(&result).to_string();
+            Ok(repl.zstore.intern_string(&json))
+        },
+    };
+
+    const LOAD_JSON: Self = Self {
+        name: "load-json",
+        summary: "Loads JSON data from the file system as Lurk data",
+        info: &[
+            "null, false and [] become nil and true becomes t. Integers become",
+            "u64s if they fit, then i64s, then big nums. Strings become strings,",
+            "arrays become lists and objects become property lists with keywords",
+            "as keys.",
+        ],
+        format: "!(load-json <string>)",
+        example: &["!(def data !(load-json \"data.json\"))"],
+        returns: "The loaded data",
+        run: |repl, args, dir| {
+            let [&path] = repl.take(args)?;
+            Self::validate_path_type(&path)?;
+            let path_str = repl.zstore.fetch_string(&path);
+            let json_bytes = std::fs::read(dir.join(path_str))?;
+            from_json(&mut repl.zstore, serde_json::from_slice(&json_bytes)?)
+        },
+    };
+
     const DEFPROTOCOL: Self = Self {
         name: "defprotocol",
         summary: "Defines a protocol",
@@ -747,7 +799,9 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
             "  proof verification to further constrain the proof, if needed.",
             "  If this is not necessary, this component can simply be nil.\n",
             "defprotocol accepts the following options:",
-            "  :lang specifies the Lang (ignored, WIP)",
+            "  :lang is the ID of the Lang the protocol is bound to, defaulting to",
+            "    the Lang of the REPL. Proofs can only be created and verified with",
+            "    that Lang. If nil, the protocol isn't bound to any Lang",
             "  :description is a description of the protocol, defaulting to \"\"",
         ],
         format: "!(defprotocol <symbol> <vars> <body> options...)",
@@ -784,12 +838,13 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
                 }
             };
 
-            // TODO: handle lang properly
-            let lang = get_prop(
-                "lang",
-                |_| true, // accept anything for now
-                *repl.zstore.nil(),
-            )?;
+            let repl_lang = repl.zstore.intern_string(&repl.lang_id);
+            let lang = get_prop("lang", |_| true, repl_lang)?;
+            if lang != *repl.zstore.nil() {
+                if lang.tag != Tag::Str || !is_lang_id(&repl.zstore.fetch_string(&lang)) {
+                    bail!("Invalid value for property lang: expected a Lang ID or nil");
+                }
+            }
 
             let description = get_prop("description", |val| val.tag == Tag::Str, empty_str)?;
 
@@ -822,25 +877,25 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
                 let Some(meta_cmd) = repl.meta_cmds.get(&meta_sym(name)) else {
                     bail!("Unknown meta command");
                 };
-                println!("{} - {}", meta_cmd.name, meta_cmd.summary);
+                text_println!(repl, "{} - {}", meta_cmd.name, meta_cmd.summary);
                 if !meta_cmd.info.is_empty() {
-                    println!("  Info:");
+                    text_println!(repl, "  Info:");
                 }
                 for e in meta_cmd.info {
-                    println!("    {e}");
+                    text_println!(repl, "    {e}");
                 }
-                println!("  Format: {}", meta_cmd.format);
+                text_println!(repl, "  Format: {}", meta_cmd.format);
                 if !meta_cmd.example.is_empty() {
-                    println!("  Example:");
+                    text_println!(repl, "  Example:");
                 }
                 for e in meta_cmd.example {
-                    println!("    {e}");
+                    text_println!(repl, "    {e}");
                 }
-                println!("  Returns: {}", meta_cmd.returns);
+                text_println!(repl, "  Returns: {}", meta_cmd.returns);
             } else {
-                println!("Available commands:");
+                text_println!(repl, "Available commands:");
                 for (_, i) in repl.meta_cmds.iter().sorted_by_key(|x| x.0) {
-                    println!("  {} - {}", i.name, i.summary);
+                    text_println!(repl, "  {} - {}", i.name, i.summary);
                 }
             }
             Ok(*repl.zstore.t())
@@ -868,6 +923,69 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         },
     };
 
+    const PROVE_DISCLOSING: Self = Self {
+        name: "prove-disclosing",
+        summary: "Proves a Lurk reduction disclosing only some environment bindings",
+        info: &[
+            "Works like `prove`, but the persisted proof only carries the bindings of",
+            "the provided variables. The expression is reduced in a rearrangement",
+            "of the environment with those bindings on top of the others, which the",
+            "proof only commits to through the digest of a randomly salted",
+            "environment.",
+        ],
+        format: "!(prove-disclosing <vars> <expr>)",
+        example: &[
+            "!(def secret 7)",
+            "!(def public 3)",
+            "!(prove-disclosing (public) (+ public 1))",
+        ],
+        returns: "The proof key as a string",
+        run: |repl, args, _dir| {
+            let (&vars, &rest) = repl.car_cdr(args);
+            if vars.tag != Tag::Cons && &vars != repl.zstore.nil() {
+                bail!("Variables must be a list");
+            }
+            let disclosed = repl
+                .zstore
+                .fetch_list(&vars)
+                .0
+                .into_iter()
+                .copied()
+                .collect::<Vec<_>>();
+            let [&expr] = repl.take(&rest)?;
+            let proof_key = repl.prove_disclosing(&expr, &disclosed)?;
+            Ok(repl.zstore.intern_string(&proof_key))
+        },
+    };
+
+    const PROVE_BATCH: Self = Self {
+        name: "prove-batch",
+        summary: "Proves many Lurk reductions, persists the proofs and prints their keys",
+        info: &[
+            "The STARK machine is set up once and reused for all proofs. Each",
+            "expression is proved as soon as it's reduced.",
+            "Prints an index with the proof key for each expression.",
+            "The latest reduction before the batch remains the one that `prove`",
+            "proves without arguments.",
+        ],
+        format: "!(prove-batch <expr1> <expr2> ...)",
+        example: &["!(prove-batch (+ 1 2) '(1 2 3))"],
+        returns: "The list of proof keys as strings",
+        run: |repl, args, _dir| {
+            let (exprs, _) = repl.zstore.fetch_list(args);
+            let exprs = exprs.into_iter().copied().collect::<Vec<_>>();
+            if exprs.is_empty() {
+                bail!("Missing expressions to prove");
+            }
+            let proof_keys = repl.prove_batch(&exprs)?;
+            let proof_keys = proof_keys
+                .iter()
+                .map(|proof_key| repl.zstore.intern_string(proof_key))
+                .collect::<Vec<_>>();
+            Ok(repl.zstore.intern_list(proof_keys))
+        },
+    };
+
     fn load_cached_proof(proof_key: &str) -> Result<CachedProof> {
         let proof_dir = proofs_dir()?.join(proof_key);
         if !proof_dir.exists() {
@@ -887,11 +1005,18 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         if proof_key_zptr.tag != Tag::Str {
             bail!("Proof key must be a string");
         }
-        let proof_key = repl.zstore.fetch_string(&proof_key_zptr);
+        let proof_key = ProofTags::resolve(&repl.zstore.fetch_string(&proof_key_zptr))?;
         let cached_proof = Self::load_cached_proof(&proof_key)?;
         Ok((proof_key, cached_proof))
     }
 
+    fn get_verifier(repl: &Repl<F, C1, C2>) -> Result<&'static Verifier> {
+        let Some(verifier) = Verifier::get_by_id(&repl.lang_id) else {
+            bail!("No verifier available for Lang {}", repl.lang_id);
+        };
+        Ok(verifier)
+    }
+
     const VERIFY: Self = Self {
         name: "verify",
         summary: "Verifies Lurk reduction proof",
@@ -904,13 +1029,13 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         returns: "t",
         run: |repl, args, _dir| {
             let (proof_key, cached_proof) = Self::load_cached_proof_with_repl(repl, args)?;
-            let has_same_verifier_version = cached_proof.crypto_proof.has_same_verifier_version();
-            let machine = new_machine(&repl.toplevel);
+            let has_same_verifier_version = cached_proof
+                .crypto_proof
+                .has_same_verifier_version(&repl.verifier_version);
+            let verifier = Self::get_verifier(repl)?;
             let machine_proof = cached_proof.into_machine_proof();
-            let (_, vk) = machine.setup(&LairMachineProgram);
-            let challenger = &mut machine.config().challenger();
-            if machine.verify(&vk, &machine_proof, challenger).is_ok() {
-                println!("✓ Proof \"{proof_key}\" verified");
+            if verifier.verify(&machine_proof) {
+                text_println!(repl, "✓ Proof \"{proof_key}\" verified");
                 Ok(*repl.zstore.t())
             } else {
                 let mut msg = format!("✗ Proof \"{proof_key}\" failed on verification");
@@ -925,7 +1050,10 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
     const INSPECT: Self = Self {
         name: "inspect",
         summary: "Prints a proof claim",
-        info: &[],
+        info: &[
+            "The part of the environment left undisclosed by the prover is printed as",
+            "<hidden>, after the disclosed bindings.",
+        ],
         format: "!(inspect <string>)",
         example: &["!(inspect \"2ae20412c6f4740f409196522c15b0e42aae2338c2b5b9c524f675cba0a93e\")"],
         returns: "The proof claim",
@@ -937,18 +1065,82 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
                 zdag,
                 ..
             } = Self::load_cached_proof_with_repl(repl, args)?.1;
+            let (bindings, hidden_env) = zdag.env_bindings(&env);
             zdag.populate_zstore(&mut repl.zstore);
-            println!(
-                "Expr: {}\nEnv: {}\nResult: {}",
-                repl.fmt(&expr),
-                repl.fmt(&env),
-                repl.fmt(&result)
-            );
+            let mut bindings_strs = bindings
+                .iter()
+                .map(|(var, val)| format!("({} . {})", repl.fmt(var), repl.fmt(val)))
+                .collect::<Vec<_>>();
+            if hidden_env.is_some() {
+                bindings_strs.push("<hidden>".to_string());
+            }
+            let bindings_str = bindings_strs.join(" ");
+            // in JSON mode, hidden bindings are marked as opaque in the claim
+            if repl.output_format == OutputFormat::Text {
+                println!(
+                    "Expr: {}\nEnv: <Env ({bindings_str})>\nResult: {}",
+                    repl.fmt(&expr),
+                    repl.fmt(&result)
+                );
+                if let Some(hidden_env) = hidden_env {
+                    let digest = field_elts_to_biguint(&hidden_env.digest);
+                    println!("Hidden environment digest: {digest:#x}");
+                }
+            }
             let expr_env = repl.zstore.intern_cons(expr, env);
             Ok(repl.zstore.intern_cons(expr_env, result))
         },
     };
 
+    const PROOFS: Self = Self {
+        name: "proofs",
+        summary: "Lists the persisted proofs",
+        info: &[
+            "Prints the key of each proof along with its tags, claim, verifier",
+            "version, size and creation time, from the oldest to the newest.",
+        ],
+        format: "!(proofs)",
+        example: &["!(proofs)"],
+        returns: "The list of proof keys",
+        run: |repl, args, _dir| {
+            if args != repl.zstore.nil() {
+                bail!("No arguments are accepted");
+            }
+            let entries = list_proofs()?;
+            text_print!(repl, "{}", fmt_proofs(&entries));
+            let keys = entries
+                .iter()
+                .map(|entry| repl.zstore.intern_string(&entry.key))
+                .collect::<Vec<_>>();
+            Ok(repl.zstore.intern_list(keys))
+        },
+    };
+
+    const TAG_PROOF: Self = Self {
+        name: "tag-proof",
+        summary: "Assigns a tag to a proof key",
+        info: &[
+            "The tag can be used in place of the key in `verify` and `inspect`.",
+            "Tagging another proof with the same tag moves the tag.",
+        ],
+        format: "!(tag-proof <string> <string>)",
+        example: &[
+            "!(tag-proof \"sum\" \"2ae20412c6f4740f409196522c15b0e42aae2338c2b5b9c524f675cba0a93e\")",
+            "!(verify \"sum\")",
+        ],
+        returns: "t",
+        run: |repl, args, _dir| {
+            let [tag, key] = repl.take(args)?;
+            if tag.tag != Tag::Str || key.tag != Tag::Str {
+                bail!("The tag and the proof key must be strings");
+            }
+            let tag = repl.zstore.fetch_string(tag);
+            let key = repl.zstore.fetch_string(key);
+            ProofTags::tag(tag, key)?;
+            Ok(*repl.zstore.t())
+        },
+    };
+
     fn get_vars_vec_and_body<'a>(
         repl: &'a mut Repl<F, C1, C2>,
         protocol: &'a ZPtr<F>,
@@ -965,6 +1157,29 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         Ok((vars_vec, body))
     }
 
+    /// Checks that the REPL runs the `Lang` a protocol is bound to
+    fn check_protocol_lang(repl: &Repl<F, C1, C2>, protocol: &ZPtr<F>) -> Result<()> {
+        let (protocol_elts, _) = repl.zstore.fetch_list(protocol);
+        let Some(lang) = protocol_elts.get(2) else {
+            bail!("Malformed protocol: missing Lang");
+        };
+        if lang == repl.zstore.nil() {
+            // protocols defined with `:lang nil` aren't bound to any Lang
+            return Ok(());
+        }
+        if lang.tag != Tag::Str {
+            bail!("Malformed protocol: Lang must be a string");
+        }
+        let lang_id = repl.zstore.fetch_string(lang);
+        if lang_id != repl.lang_id {
+            bail!(
+                "Protocol is bound to Lang {lang_id} but the REPL runs Lang {}",
+                repl.lang_id
+            );
+        }
+        Ok(())
+    }
+
     fn get_claim_and_post_verify_predicade<'a>(
         repl: &'a mut Repl<F, C1, C2>,
         vars_vec: Vec<ZPtr<F>>,
@@ -1036,6 +1251,7 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
             }
             let (vars_vec, &body) = Self::get_vars_vec_and_body(repl, &protocol)?;
             let vars_vec = copy_inner(vars_vec);
+            Self::check_protocol_lang(repl, &protocol)?;
 
             let (args_vec, None) = repl.zstore.fetch_list(&args) else {
                 bail!("Arguments must be a list");
@@ -1081,9 +1297,14 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
             let crypto_proof = cached_proof.crypto_proof;
             let args_reduced = repl.zstore.intern_list(args_vec_reduced);
             repl.memoize_dag(args_reduced.tag, &args_reduced.digest);
-            let protocol_proof = ProtocolProof::new(crypto_proof, args_reduced, &repl.zstore);
+            let protocol_proof = ProtocolProof::new(
+                crypto_proof,
+                args_reduced,
+                repl.lang_id.clone(),
+                &repl.zstore,
+            );
             std::fs::write(&path_str, bincode::serialize(&protocol_proof)?)?;
-            println!("Protocol proof saved on file `{path_str}`");
+            text_println!(repl, "Protocol proof saved on file `{path_str}`");
             Ok(repl.zstore.intern_string(&proof_key))
         },
     };
@@ -1116,9 +1337,20 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
             }
             let (vars_vec, &body) = Self::get_vars_vec_and_body(repl, &protocol)?;
             let vars_vec = copy_inner(vars_vec);
+            Self::check_protocol_lang(repl, &protocol)?;
 
             let protocol_proof_bytes = std::fs::read(path_str)?;
-            let ProtocolProof { crypto_proof, args } = bincode::deserialize(&protocol_proof_bytes)?;
+            let ProtocolProof {
+                crypto_proof,
+                args,
+                lang_id,
+            } = bincode::deserialize(&protocol_proof_bytes)?;
+            if lang_id != repl.lang_id {
+                bail!(
+                    "Proof was created with Lang {lang_id} but the REPL runs Lang {}",
+                    repl.lang_id
+                );
+            }
             if args.has_opaque_data() {
                 bail!("Arguments can't have opaque data");
             }
@@ -1143,12 +1375,11 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
                 bail!("Malformed protocol claim");
             }
             let (expr, env) = repl.zstore.fetch_tuple11(expr_env);
-            let has_same_verifier_version = crypto_proof.has_same_verifier_version();
+            let has_same_verifier_version =
+                crypto_proof.has_same_verifier_version(&repl.verifier_version);
             let machine_proof = crypto_proof.into_machine_proof(expr, env, result);
-            let machine = new_machine(&repl.toplevel);
-            let (_, vk) = machine.setup(&LairMachineProgram);
-            let challenger = &mut machine.config().challenger();
-            if machine.verify(&vk, &machine_proof, challenger).is_err() {
+            let verifier = Self::get_verifier(repl)?;
+            if !verifier.verify(&machine_proof) {
                 let mut msg = "Proof verification failed".to_string();
                 if !has_same_verifier_version {
                     msg.push_str("\nWarning: proof was created for a different verifier version");
@@ -1158,7 +1389,7 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
 
             Self::post_verify_check(repl, post_verify_predicate)?;
 
-            println!("Proof accepted by the protocol");
+            text_println!(repl, "Proof accepted by the protocol");
             Ok(*repl.zstore.t())
         },
     };
@@ -1175,6 +1406,17 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         CommData::new(secret, payload, &repl.zstore)
     }
 
+    fn reduce_usize(repl: &mut Repl<F, C1, C2>, expr: &ZPtr<F>) -> Result<usize> {
+        let (zptr, _) = repl.reduce_aux(expr)?;
+        if zptr.tag != Tag::U64 {
+            bail!("Expected a u64");
+        }
+        let bytes = zptr
+            .digest
+            .map(|f| u8::try_from(f.as_canonical_u32()).expect("invalid u64 limbs"));
+        Ok(usize::try_from(u64::from_le_bytes(bytes))?)
+    }
+
     const MICROCHAIN_START: Self = Self {
         name: "microchain-start",
         summary: "Starts a new microchain and binds the resulting ID to a symbol",
@@ -1183,15 +1425,32 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
             "a timestamp-based secret generated in the server.",
             "Upon success, it becomes possible to open the ID and retrieve genesis",
             "state associated with the microchain.",
+            "If a checkpoint interval is provided, the server only keeps the proofs",
+            "since the latest checkpoint, replacing older ones by a compact record",
+            "every time that many proofs accumulate.",
+            "If a key was set with `microchain-set-key`, the microchain is owned",
+            "by it and the server only accepts transitions signed with it.",
         ],
-        format: "!(microchain-start <addr_expr> <state_expr>)",
+        format: "!(microchain-start <addr_expr> <state_expr> <checkpoint_interval_expr>?)",
         example: &[
             "!(defq id !(microchain-start \"127.0.0.1:1234\" state0))",
             "!(assert-eq state0 (open id))",
+            "!(defq pruned-id !(microchain-start \"127.0.0.1:1234\" state0 100))",
         ],
         returns: "The microchain's ID",
         run: |repl, args, _dir| {
-            let [&addr_expr, &state_expr] = repl.take(args)?;
+            let (&addr_expr, rest) = repl.car_cdr(args);
+            let (&state_expr, &rest) = repl.car_cdr(rest);
+            let retention = if &rest != repl.zstore.nil() {
+                let [&interval_expr] = repl.take(&rest)?;
+                let interval = Self::reduce_usize(repl, &interval_expr)?;
+                if interval == 0 {
+                    bail!("Checkpoint interval must be positive");
+                }
+                Retention::Checkpoint(interval)
+            } else {
+                Retention::Full
+            };
             let (addr, _) = repl.reduce_aux(&addr_expr)?;
             if addr.tag != Tag::Str {
                 bail!("Address must be a string");
@@ -1217,14 +1476,17 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
                 callable_data,
             };
 
+            let owner = repl.microchain_key.as_ref().map(public_key_bytes);
+
             let addr_str = repl.zstore.fetch_string(&addr);
-            let stream = &mut TcpStream::connect(addr_str)?;
-            write_data(stream, Request::Start(genesis))?;
+            let stream = &mut connect(&addr_str)?;
+            write_data(stream, Request::Start(genesis, retention, owner))?;
             let Response::IdSecret(id_secret) = read_data(stream)? else {
                 bail!("Could not read ID secret from server");
             };
 
             let id_digest = CommData::hash(&id_secret, &state, &mut repl.zstore);
+            repl.microchain_heads.insert(id_digest, (state, 0));
 
             let id = repl.zstore.intern_comm(id_digest);
             Ok(id)
@@ -1235,7 +1497,7 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         repl: &mut Repl<F, C1, C2>,
         args: &ZPtr<F>,
         mk_request: fn([F; DIGEST_SIZE]) -> Request,
-    ) -> Result<TcpStream> {
+    ) -> Result<(TcpStream, [F; DIGEST_SIZE])> {
         let [&addr_expr, &id_expr] = repl.take(args)?;
         let (addr, _) = repl.reduce_aux(&addr_expr)?;
         if addr.tag != Tag::Str {
@@ -1243,9 +1505,9 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         }
         let (id, _) = repl.reduce_aux(&id_expr)?;
         let addr_str = repl.zstore.fetch_string(&addr);
-        let mut stream = TcpStream::connect(addr_str)?;
+        let mut stream = connect(&addr_str)?;
         write_data(&mut stream, mk_request(id.digest))?;
-        Ok(stream)
+        Ok((stream, id.digest))
     }
 
     const MICROCHAIN_GET_GENESIS: Self = Self {
@@ -1262,11 +1524,12 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         ],
         returns: "The microchain's genesis state",
         run: |repl, args, _dir| {
-            let mut stream = Self::send_get_state_request(repl, args, Request::GetGenesis)?;
+            let (mut stream, id) = Self::send_get_state_request(repl, args, Request::GetGenesis)?;
             let Response::Genesis(id_secret, chain_state) = read_data(&mut stream)? else {
                 bail!("Could not read state from server");
             };
             let state = chain_state.into_zptr(&mut repl.zstore);
+            repl.microchain_heads.entry(id).or_insert((state, 0));
 
             // memoize preimg so it's possible to open the ID
             CommData::hash(&id_secret, &state, &mut repl.zstore);
@@ -1283,11 +1546,12 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
         example: &["!(microchain-get-state \"127.0.0.1:1234\" #c0x123)"],
         returns: "The microchain's latest state",
         run: |repl, args, _dir| {
-            let mut stream = Self::send_get_state_request(repl, args, Request::GetState)?;
-            let Response::State(chain_state) = read_data(&mut stream)? else {
+            let (mut stream, id) = Self::send_get_state_request(repl, args, Request::GetState)?;
+            let Response::State(height, chain_state) = read_data(&mut stream)? else {
                 bail!("Could not read state from server");
             };
             let state = chain_state.into_zptr(&mut repl.zstore);
+            repl.microchain_heads.insert(id, (state, height));
             Ok(state)
         },
     };
@@ -1300,6 +1564,12 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
             "The transition is successful iff the proof is accepted by the server.",
             "Unlike in the `transition` meta command, the call arguments will be",
             "evaluated w.r.t. the empty environment.",
+            "The server rejects transitions made from a state that is no longer",
+            "the current one, reporting the current height and state.",
+            "Transitions are signed with the key set with `microchain-set-key`,",
+            "if any, which is required for microchains that have an owner.",
+            "Signing requires the height of the given state, which is known if the",
+            "state was obtained from the server by this REPL.",
         ],
         format: "!(microchain-transition <addr_expr> <id_expr> <state_expr> <call_args>)",
         example: &["!(microchain-transition \"127.0.0.1:1234\" #c0x123 state arg0 arg1)"],
@@ -1313,9 +1583,9 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
             }
             let (id, _) = repl.reduce_aux(&id_expr)?;
             let (&current_state_expr, &call_args) = repl.car_cdr(&rest);
+            let current_state = Self::reduce_current_state(repl, &current_state_expr)?;
             let empty_env = repl.zstore.intern_empty_env();
-            let state =
-                Self::transition_call(repl, &current_state_expr, call_args, Some(empty_env))?;
+            let state = Self::transition_call(repl, &current_state, call_args, Some(empty_env))?;
             if state.tag != Tag::Cons {
                 bail!("New state is not a pair");
             }
@@ -1340,22 +1610,56 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
                 next_callable,
             };
             let addr_str = repl.zstore.fetch_string(&addr);
-            let stream = &mut TcpStream::connect(addr_str)?;
-            write_data(stream, Request::Transition(id.digest, chain_proof))?;
+            let height = match repl.microchain_heads.get(&id.digest) {
+                Some((head, height)) if head == &current_state => Some(*height),
+                _ => None,
+            };
+            let signature = match &repl.microchain_key {
+                Some(key) => {
+                    let Some(height) = height else {
+                        bail!("Unknown height for the given state. Fetch it with `microchain-get-state` first");
+                    };
+                    Some(sign_transition(key, &id.digest, height, &state))
+                }
+                None => None,
+            };
+            let stream = &mut connect(&addr_str)?;
+            write_data(
+                stream,
+                Request::Transition(id.digest, current_state, chain_proof, signature),
+            )?;
             match read_data::<Response>(stream)? {
                 Response::ProofAccepted => {
-                    println!("Proof accepted by the server");
+                    text_println!(repl, "Proof accepted by the server");
+                    if let Some(height) = height {
+                        repl.microchain_heads.insert(id.digest, (state, height + 1));
+                    }
                     Ok(state)
                 }
                 Response::ProofVerificationFailed(verifier_version) => {
                     let mut msg = "Proof verification failed".to_string();
-                    if verifier_version != get_verifier_version() {
+                    if verifier_version != repl.verifier_version {
                         msg.push_str(
                             "\nWarning: proof was created for a different verifier version",
                         );
                     }
                     bail!(msg);
                 }
+                Response::PersistenceFailed => bail!("Server failed to persist the transition"),
+                Response::Unauthorized => {
+                    bail!("The transition wasn't signed by the owner of the microchain")
+                }
+                Response::MessageTooLarge(max_size) => {
+                    bail!("The proof exceeds the limit of {max_size} bytes of the server")
+                }
+                Response::StaleParent(height, head) => {
+                    let head = head.into_zptr(&mut repl.zstore);
+                    repl.microchain_heads.insert(id.digest, (head, height));
+                    bail!(
+                        "The microchain has moved on from the given state\nCurrent height: {height}\nCurrent state: {}",
+                        repl.fmt(&head)
+                    );
+                }
                 _ => bail!("Bad server response"),
             }
         },
@@ -1381,15 +1685,21 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
                 bail!("Initial state must be a pair");
             }
             let addr_str = repl.zstore.fetch_string(&addr);
-            let stream = &mut TcpStream::connect(addr_str)?;
+            let stream = &mut connect(&addr_str)?;
             write_data(stream, Request::GetProofs(id.digest))?;
-            let Response::Proofs(proofs) = read_data(stream)? else {
-                bail!("Could not read proofs from server");
+            let proofs = match read_data(stream)? {
+                Response::Proofs(proofs) => proofs,
+                Response::HistoryPruned(checkpoint) => Self::bail_history_pruned(&checkpoint)?,
+                Response::ResponseTooLarge(max_size) => bail!(
+                    "The proofs exceed the limit of {max_size} bytes of the server. Use `microchain-verify-range` instead"
+                ),
+                _ => bail!("Could not read proofs from server"),
             };
             repl.memoize_dag(genesis_state.tag, &genesis_state.digest);
             let (_, &(mut callable)) = repl.zstore.fetch_tuple11(&genesis_state);
             let mut state = genesis_state;
             let empty_env = repl.zstore.intern_empty_env();
+            let verifier = Verifier::native();
             for (i, proof) in proofs.into_iter().enumerate() {
                 let OpaqueChainProof {
                     crypto_proof,
@@ -1400,10 +1710,7 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
                 let expr = repl.zstore.intern_cons(callable, call_args);
                 let result = repl.zstore.intern_cons(next_chain_result, next_callable);
                 let machine_proof = crypto_proof.into_machine_proof(&expr, &empty_env, &result);
-                let machine = new_machine(&repl.toplevel);
-                let (_, vk) = machine.setup(&LairMachineProgram);
-                let challenger = &mut machine.config().challenger();
-                if machine.verify(&vk, &machine_proof, challenger).is_err() {
+                if !verifier.verify(&machine_proof) {
                     bail!("{}-th transition proof doesn't verify", i + 1);
                 }
                 callable = next_callable;
@@ -1413,7 +1720,324 @@ impl<C1: Chipset<F>, C2: Chipset<F>> MetaCmd<F, C1, C2> {
             if state != current_state {
                 bail!("Chain final state doesn't match target final state");
             }
-            println!("Microchain verification succeeded");
+            text_println!(repl, "Microchain verification succeeded");
+            Ok(*repl.zstore.t())
+        },
+    };
+
+    fn bail_history_pruned<T>(checkpoint: &Checkpoint) -> Result<T> {
+        bail!(
+            "The microchain history was pruned up to height {}",
+            checkpoint.height
+        )
+    }
+
+    const MICROCHAIN_GET_HISTORY: Self = Self {
+        name: "microchain-get-history",
+        summary: "Verifies a span of microchain transitions and returns the states along it",
+        info: &[
+            "The transition proofs from height `from` to height `to` are fetched",
+            "from the server in pages and verified locally, starting from the state",
+            "at height `from`. Past states are opaque because the server only keeps",
+            "their digests.",
+        ],
+        format: "!(microchain-get-history <addr_expr> <id_expr> <from_expr> <to_expr>)",
+        example: &["!(microchain-get-history \"127.0.0.1:1234\" #c0x123 0 10)"],
+        returns: "The list of states from height `from` to height `to`",
+        run: |repl, args, _dir| {
+            let [&addr_expr, &id_expr, &from_expr, &to_expr] = repl.take(args)?;
+            let (addr, _) = repl.reduce_aux(&addr_expr)?;
+            if addr.tag != Tag::Str {
+                bail!("Address must be a string");
+            }
+            let (id, _) = repl.reduce_aux(&id_expr)?;
+            let from = Self::reduce_usize(repl, &from_expr)?;
+            let to = Self::reduce_usize(repl, &to_expr)?;
+            if from > to {
+                bail!("Invalid height range");
+            }
+            let addr_str = repl.zstore.fetch_string(&addr);
+
+            let stream = &mut connect(&addr_str)?;
+            write_data(stream, Request::GetStateAt(id.digest, from))?;
+            let (chain_result, mut callable) = match read_data(stream)? {
+                Response::PastState(chain_result, callable) => (chain_result, callable),
+                Response::HistoryPruned(checkpoint) => Self::bail_history_pruned(&checkpoint)?,
+                _ => bail!("Could not read state from server"),
+            };
+            let mut states = vec![repl.zstore.intern_cons(chain_result, callable)];
+
+            let verifier = Verifier::native();
+            let empty_env = repl.zstore.intern_empty_env();
+            let mut height = from;
+            while height < to {
+                let stream = &mut connect(&addr_str)?;
+                write_data(
+                    stream,
+                    Request::GetProofRange(id.digest, height, to - height),
+                )?;
+                let proofs = match read_data(stream)? {
+                    Response::ProofRange(_, proofs) => proofs,
+                    Response::HistoryPruned(checkpoint) => Self::bail_history_pruned(&checkpoint)?,
+                    _ => bail!("Could not read proofs from server"),
+                };
+                if proofs.is_empty() {
+                    bail!("Missing transitions after height {height}");
+                }
+                for proof in proofs.into_iter().take(to - height) {
+                    let OpaqueChainProof {
+                        crypto_proof,
+                        call_args,
+                        next_chain_result,
+                        next_callable,
+                    } = proof;
+                    let expr = repl.zstore.intern_cons(callable, call_args);
+                    let result = repl.zstore.intern_cons(next_chain_result, next_callable);
+                    let machine_proof = crypto_proof.into_machine_proof(&expr, &empty_env, &result);
+                    if !verifier.verify(&machine_proof) {
+                        bail!("Transition at height {height} doesn't verify");
+                    }
+                    callable = next_callable;
+                    states.push(result);
+                    height += 1;
+                }
+            }
+            text_println!(repl, "Verified {} microchain transition(s)", to - from);
+            Ok(repl.zstore.intern_list(states))
+        },
+    };
+
+    const MICROCHAIN_VERIFY_RANGE: Self = Self {
+        name: "microchain-verify-range",
+        summary: "Asks the server to re-verify a span of microchain transitions",
+        info: &[
+            "The server verifies the transition proofs from height `from` to height",
+            "`to` against the states it stores, in pages, so auditing the history",
+            "doesn't require downloading the proofs.",
+        ],
+        format: "!(microchain-verify-range <addr_expr> <id_expr> <from_expr> <to_expr>)",
+        example: &["!(microchain-verify-range \"127.0.0.1:1234\" #c0x123 0 10)"],
+        returns: "t",
+        run: |repl, args, _dir| {
+            let [&addr_expr, &id_expr, &from_expr, &to_expr] = repl.take(args)?;
+            let (addr, _) = repl.reduce_aux(&addr_expr)?;
+            if addr.tag != Tag::Str {
+                bail!("Address must be a string");
+            }
+            let (id, _) = repl.reduce_aux(&id_expr)?;
+            let from = Self::reduce_usize(repl, &from_expr)?;
+            let to = Self::reduce_usize(repl, &to_expr)?;
+            if from > to {
+                bail!("Invalid height range");
+            }
+            let addr_str = repl.zstore.fetch_string(&addr);
+            let mut height = from;
+            while height < to {
+                let end = to.min(height + MAX_RANGE_SIZE);
+                let stream = &mut connect(&addr_str)?;
+                write_data(stream, Request::VerifyRange(id.digest, height, end))?;
+                match read_data(stream)? {
+                    Response::RangeVerified => height = end,
+                    Response::TransitionFailed(height) => {
+                        bail!("Transition at height {height} doesn't verify")
+                    }
+                    Response::HistoryPruned(checkpoint) => Self::bail_history_pruned(&checkpoint)?,
+                    Response::InvalidHeight => bail!("Invalid height range"),
+                    Response::NoDataForId => bail!("No microchain found for the ID"),
+                    _ => bail!("Bad server response"),
+                }
+            }
+            text_println!(
+                repl,
+                "Server verified {} microchain transition(s)",
+                to - from
+            );
+            Ok(*repl.zstore.t())
+        },
+    };
+
+    const MICROCHAIN_TAIL: Self = Self {
+        name: "microchain-tail",
+        summary: "Follows the transitions of a microchain as they're accepted by the server",
+        info: &[
+            "Prints the current state of the microchain and then every new state",
+            "accepted by the server.",
+            "If a number of transitions is provided, stops after that many.",
+            "Otherwise, runs until the server closes the connection.",
+        ],
+        format: "!(microchain-tail <addr_expr> <id_expr> <count_expr>?)",
+        example: &[
+            "!(microchain-tail \"127.0.0.1:1234\" #c0x123)",
+            "!(defq state !(microchain-tail \"127.0.0.1:1234\" #c0x123 1))",
+        ],
+        returns: "The last state received",
+        run: |repl, args, _dir| {
+            let (&addr_expr, rest) = repl.car_cdr(args);
+            let (&id_expr, &rest) = repl.car_cdr(rest);
+            let count = if &rest != repl.zstore.nil() {
+                let [&count_expr] = repl.take(&rest)?;
+                Some(Self::reduce_usize(repl, &count_expr)?)
+            } else {
+                None
+            };
+            let (addr, _) = repl.reduce_aux(&addr_expr)?;
+            if addr.tag != Tag::Str {
+                bail!("Address must be a string");
+            }
+            let (id, _) = repl.reduce_aux(&id_expr)?;
+            let addr_str = repl.zstore.fetch_string(&addr);
+            let stream = &mut connect(&addr_str)?;
+            write_data(stream, Request::Subscribe(id.digest))?;
+            let (height, chain_state) = match read_data(stream)? {
+                Response::Subscribed(height, chain_state) => (height, chain_state),
+                Response::NoDataForId => bail!("No microchain found for the ID"),
+                Response::TooManySubscribers => {
+                    bail!("The server reached its limit of subscribers")
+                }
+                _ => bail!("Could not subscribe to the microchain"),
+            };
+            let mut state = chain_state.into_zptr(&mut repl.zstore);
+            repl.microchain_heads.insert(id.digest, (state, height));
+            text_println!(repl, "Height {height}: {}", repl.fmt(&state));
+            for _ in 0..count.unwrap_or(usize::MAX) {
+                // the server only closes the connection when it shuts down
+                let Ok(response) = read_data(stream) else {
+                    break;
+                };
+                let Response::Event(TransitionEvent {
+                    height,
+                    state: chain_state,
+                    ..
+                }) = response
+                else {
+                    bail!("Bad server response");
+                };
+                state = chain_state.into_zptr(&mut repl.zstore);
+                repl.microchain_heads.insert(id.digest, (state, height));
+                text_println!(repl, "Height {height}: {}", repl.fmt(&state));
+            }
+            Ok(state)
+        },
+    };
+
+    const MICROCHAIN_EXPORT: Self = Self {
+        name: "microchain-export",
+        summary: "Exports a microchain from a server to an archive file",
+        info: &[
+            "The archive contains the genesis and current states along with every",
+            "transition proof kept by the server, which is enough to move the",
+            "microchain to another server or to audit it offline.",
+        ],
+        format: "!(microchain-export <addr_expr> <id_expr> <string>)",
+        example: &["!(microchain-export \"127.0.0.1:1234\" #c0x123 \"my_chain\")"],
+        returns: "t",
+        run: |repl, args, _dir| {
+            let [&addr_expr, &id_expr, &path] = repl.take(args)?;
+            Self::validate_path_type(&path)?;
+            let (addr, _) = repl.reduce_aux(&addr_expr)?;
+            if addr.tag != Tag::Str {
+                bail!("Address must be a string");
+            }
+            let (id, _) = repl.reduce_aux(&id_expr)?;
+            let addr_str = repl.zstore.fetch_string(&addr);
+            let path_str = repl.zstore.fetch_string(&path);
+            let archive = request_export(&addr_str, id.digest)?;
+            archive.write(Utf8Path::new(&path_str))?;
+            text_println!(repl, "Microchain exported to `{path_str}`");
+            Ok(*repl.zstore.t())
+        },
+    };
+
+    const MICROCHAIN_IMPORT: Self = Self {
+        name: "microchain-import",
+        summary: "Imports a microchain from an archive file into a server",
+        info: &[
+            "The server verifies every transition proof in the archive, starting",
+            "from genesis, before accepting the microchain. Archives of microchains",
+            "whose history was pruned into a checkpoint are rejected.",
+        ],
+        format: "!(microchain-import <addr_expr> <string>)",
+        example: &["!(defq id !(microchain-import \"127.0.0.1:1234\" \"my_chain\"))"],
+        returns: "The microchain's ID",
+        run: |repl, args, _dir| {
+            let [&addr_expr, &path] = repl.take(args)?;
+            Self::validate_path_type(&path)?;
+            let (addr, _) = repl.reduce_aux(&addr_expr)?;
+            if addr.tag != Tag::Str {
+                bail!("Address must be a string");
+            }
+            let addr_str = repl.zstore.fetch_string(&addr);
+            let path_str = repl.zstore.fetch_string(&path);
+            let archive = ChainArchive::read(Utf8Path::new(&path_str))?;
+            let (&id_secret, genesis) = archive.genesis();
+            let genesis = genesis.clone().into_zptr(&mut repl.zstore);
+            let id = archive.id;
+            request_import(&addr_str, archive)?;
+
+            // memoize preimg so it's possible to open the ID
+            CommData::hash(&id_secret, &genesis, &mut repl.zstore);
+
+            Ok(repl.zstore.intern_comm(id))
+        },
+    };
+
+    fn print_public_key(repl: &Repl<F, C1, C2>, key: &SigningKey) {
+        let public_key: String = public_key_bytes(key)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        text_println!(repl, "Public key: {public_key}");
+    }
+
+    const MICROCHAIN_KEYGEN: Self = Self {
+        name: "microchain-keygen",
+        summary: "Generates a key for owning microchains and writes it to a file",
+        info: &[
+            "The new key is also set as the one used by `microchain-start` and",
+            "`microchain-transition`. Existing files aren't overwritten.",
+        ],
+        format: "!(microchain-keygen <string>)",
+        example: &["!(microchain-keygen \"my_key\")"],
+        returns: "t",
+        run: |repl, args, _dir| {
+            let [&path] = repl.take(args)?;
+            Self::validate_path_type(&path)?;
+            let path_str = repl.zstore.fetch_string(&path);
+            let path = Utf8Path::new(&path_str);
+            if path.exists() {
+                bail!("`{path_str}` already exists");
+            }
+            let key = generate_key();
+            write_key(&key, path)?;
+            text_println!(repl, "Key written to `{path_str}`");
+            Self::print_public_key(repl, &key);
+            repl.microchain_key = Some(key);
+            Ok(*repl.zstore.t())
+        },
+    };
+
+    const MICROCHAIN_SET_KEY: Self = Self {
+        name: "microchain-set-key",
+        summary: "Sets the key used to own microchains and sign their transitions",
+        info: &[
+            "Microchains started with a key can only transition with signatures",
+            "made with the same key. Without arguments, the key is unset.",
+        ],
+        format: "!(microchain-set-key <string>?)",
+        example: &["!(microchain-set-key \"my_key\")", "!(microchain-set-key)"],
+        returns: "t",
+        run: |repl, args, _dir| {
+            if args == repl.zstore.nil() {
+                repl.microchain_key = None;
+                text_println!(repl, "Microchain key unset");
+            } else {
+                let [&path] = repl.take(args)?;
+                Self::validate_path_type(&path)?;
+                let path_str = repl.zstore.fetch_string(&path);
+                let key = read_key(Utf8Path::new(&path_str))?;
+                Self::print_public_key(repl, &key);
+                repl.microchain_key = Some(key);
+            }
             Ok(*repl.zstore.t())
         },
     };
@@ -1507,9 +2131,15 @@ pub(crate) fn meta_cmds<C1: Chipset<F>, C2: Chipset<F>>() -> MetaCmdsMap<F, C1,
         MetaCmd::IN_PACKAGE,
         MetaCmd::DUMP_EXPR,
         MetaCmd::LOAD_EXPR,
+        MetaCmd::TO_JSON,
+        MetaCmd::LOAD_JSON,
         MetaCmd::PROVE,
+        MetaCmd::PROVE_DISCLOSING,
+        MetaCmd::PROVE_BATCH,
         MetaCmd::VERIFY,
         MetaCmd::INSPECT,
+        MetaCmd::PROOFS,
+        MetaCmd::TAG_PROOF,
         MetaCmd::DEFPROTOCOL,
         MetaCmd::PROVE_PROTOCOL,
         MetaCmd::VERIFY_PROTOCOL,
@@ -1518,6 +2148,13 @@ pub(crate) fn meta_cmds<C1: Chipset<F>, C2: Chipset<F>>() -> MetaCmdsMap<F, C1,
         MetaCmd::MICROCHAIN_GET_STATE,
         MetaCmd::MICROCHAIN_TRANSITION,
         MetaCmd::MICROCHAIN_VERIFY,
+        MetaCmd::MICROCHAIN_GET_HISTORY,
+        MetaCmd::MICROCHAIN_VERIFY_RANGE,
+        MetaCmd::MICROCHAIN_TAIL,
+        MetaCmd::MICROCHAIN_EXPORT,
+        MetaCmd::MICROCHAIN_IMPORT,
+        MetaCmd::MICROCHAIN_KEYGEN,
+        MetaCmd::MICROCHAIN_SET_KEY,
         MetaCmd::LOAD_OCAML,
         MetaCmd::LOAD_OCAML_EXPR,
         MetaCmd::HELP,
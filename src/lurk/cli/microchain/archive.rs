@@ -0,0 +1,221 @@
+use anyhow::{bail, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::lurk::{
+    parser::{syntax::parse, Span},
+    state::State,
+    syntax::Syntax,
+    zstore::DIGEST_SIZE,
+};
+
+use super::{connect, read_data, write_data, ChainData, ChainState, Request, Response, F};
+
+/// Identifies microchain archive files
+const ARCHIVE_MAGIC: &[u8; 8] = b"LURKMCHA";
+
+/// Bumped whenever the layout of `ChainArchive` changes
+const ARCHIVE_VERSION: u32 = 2;
+
+/// Size of an archive header: the magic bytes, the format version and the
+/// SHA-256 checksum of the payload
+const ARCHIVE_HEADER_SIZE: usize = 8 + 4 + 32;
+
+/// A complete microchain, meant to be moved between servers or audited offline.
+///
+/// The genesis and current states carry their `ZDag`s, so the archive is enough
+/// to resume the chain. Intermediate states are only needed as digests for the
+/// verification of the proofs.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ChainArchive {
+    pub(crate) id: [F; DIGEST_SIZE],
+    pub(crate) chain_data: ChainData,
+}
+
+impl ChainArchive {
+    /// The secret used to generate the microchain ID along with the genesis state
+    #[inline]
+    pub(crate) fn genesis(&self) -> (&[F; DIGEST_SIZE], &ChainState) {
+        let (id_secret, state) = &self.chain_data.genesis;
+        (id_secret, state)
+    }
+
+    pub(crate) fn write(&self, path: &Utf8Path) -> Result<()> {
+        let payload = bincode::serialize(self)?;
+        let mut bytes = Vec::with_capacity(ARCHIVE_HEADER_SIZE + payload.len());
+        bytes.extend(ARCHIVE_MAGIC);
+        bytes.extend(ARCHIVE_VERSION.to_le_bytes());
+        bytes.extend(Sha256::digest(&payload));
+        bytes.extend(payload);
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    pub(crate) fn read(path: &Utf8Path) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let Some((magic, rest)) = bytes.split_at_checked(ARCHIVE_MAGIC.len()) else {
+            bail!("{path} is too short to be a microchain archive");
+        };
+        if magic != ARCHIVE_MAGIC {
+            bail!("{path} is not a microchain archive");
+        }
+        let Some((version_bytes, rest)) = rest.split_at_checked(4) else {
+            bail!("{path} is too short to be a microchain archive");
+        };
+        let version = u32::from_le_bytes(version_bytes.try_into().unwrap());
+        if version != ARCHIVE_VERSION {
+            bail!("Unsupported microchain archive version {version}");
+        }
+        let Some((checksum, payload)) = rest.split_at_checked(32) else {
+            bail!("{path} is too short to be a microchain archive");
+        };
+        if Sha256::digest(payload).as_slice() != checksum {
+            bail!("Integrity check failed for {path}");
+        }
+        Ok(bincode::deserialize(payload)?)
+    }
+}
+
+/// Requests the archive of a microchain from a server
+pub(crate) fn request_export(addr: &str, id: [F; DIGEST_SIZE]) -> Result<ChainArchive> {
+    let stream = &mut connect(addr)?;
+    write_data(stream, Request::Export(id))?;
+    match read_data(stream)? {
+        Response::Archive(archive) => Ok(archive),
+        Response::NoDataForId => bail!("No microchain found for the ID"),
+        Response::ResponseTooLarge(max_size) => {
+            bail!("The archive exceeds the limit of {max_size} bytes of the server")
+        }
+        Response::HistoryPruned(checkpoint) => bail!(
+            "The history of the microchain was pruned up to height {}, so it can't be exported",
+            checkpoint.height
+        ),
+        _ => bail!("Could not read archive from server"),
+    }
+}
+
+/// Sends a microchain archive to a server, which verifies it before accepting
+pub(crate) fn request_import(addr: &str, archive: ChainArchive) -> Result<()> {
+    let stream = &mut connect(addr)?;
+    write_data(stream, Request::Import(archive))?;
+    match read_data(stream)? {
+        Response::Imported => Ok(()),
+        Response::IdAlreadyInUse => bail!("The server already has a microchain with the same ID"),
+        Response::InvalidArchive(reason) => bail!("Archive rejected by the server: {reason}"),
+        Response::TransitionFailed(height) => {
+            bail!("Transition at height {height} doesn't verify")
+        }
+        Response::PersistenceFailed => bail!("Server failed to persist the microchain"),
+        Response::MessageTooLarge(max_size) => {
+            bail!("The archive exceeds the limit of {max_size} bytes of the server")
+        }
+        _ => bail!("Bad server response"),
+    }
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct ExportArgs {
+    // The IP address with the port. E.g. "127.0.0.1:1234"
+    #[clap(value_parser)]
+    addr: String,
+
+    /// The microchain ID. E.g. "#c0x123"
+    #[clap(value_parser)]
+    id: String,
+
+    /// The archive file to be written
+    #[clap(value_parser)]
+    archive: Utf8PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct ImportArgs {
+    // The IP address with the port. E.g. "127.0.0.1:1234"
+    #[clap(value_parser)]
+    addr: String,
+
+    /// The archive file to be read
+    #[clap(value_parser)]
+    archive: Utf8PathBuf,
+}
+
+/// Parses a microchain ID written as a commitment literal
+fn parse_id(id: &str) -> Result<[F; DIGEST_SIZE]> {
+    let Some((_, Syntax::Comm(_, digest))) =
+        parse(Span::new(id), State::init_lurk_state().rccell(), false)?
+    else {
+        bail!("The microchain ID must be a commitment, as in \"#c0x123\"");
+    };
+    Ok(digest)
+}
+
+impl ExportArgs {
+    pub(crate) fn run(self) -> Result<()> {
+        let Self { addr, id, archive } = self;
+        request_export(&addr, parse_id(&id)?)?.write(&archive)?;
+        println!("Microchain exported to {archive}");
+        Ok(())
+    }
+}
+
+impl ImportArgs {
+    pub(crate) fn run(self) -> Result<()> {
+        let Self { addr, archive } = self;
+        request_import(&addr, ChainArchive::read(&archive)?)?;
+        println!("Microchain imported from {archive}");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use camino::Utf8PathBuf;
+    use p3_field::AbstractField;
+    use tempfile::tempdir;
+
+    use crate::lurk::{
+        cli::lurk_data::LurkData,
+        zstore::{lurk_zstore, DIGEST_SIZE},
+    };
+
+    use super::{
+        super::{CallableData, ChainData, ChainState, Retention, F},
+        ChainArchive,
+    };
+
+    #[test]
+    fn test_archive_integrity() {
+        let zstore = lurk_zstore();
+        let nil = *zstore.nil();
+        let state = ChainState {
+            chain_result: LurkData::new(nil, &zstore),
+            callable_data: CallableData::Fun(LurkData::new(nil, &zstore)),
+        };
+        let archive = ChainArchive {
+            id: [F::zero(); DIGEST_SIZE],
+            chain_data: ChainData {
+                genesis: ([F::one(); DIGEST_SIZE], state.clone()),
+                proofs: vec![],
+                retention: Retention::Full,
+                checkpoint: None,
+                owner: None,
+                state,
+            },
+        };
+
+        let dir = tempdir().unwrap();
+        let path = Utf8PathBuf::try_from(dir.path().join("chain")).unwrap();
+        archive.write(&path).unwrap();
+        let read_archive = ChainArchive::read(&path).unwrap();
+        assert_eq!(read_archive.id, archive.id);
+        assert_eq!(read_archive.genesis().0, archive.genesis().0);
+
+        // flip a bit of the payload
+        let mut bytes = std::fs::read(&path).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        std::fs::write(&path, bytes).unwrap();
+        assert!(ChainArchive::read(&path).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use camino::Utf8Path;
+use k256::ecdsa::{
+    signature::{Signer, Verifier},
+    Signature, SigningKey, VerifyingKey,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use std::{fs::OpenOptions, io::Write};
+
+use crate::lurk::zstore::{ZPtr, DIGEST_SIZE};
+
+use super::F;
+
+/// Generates a new key for signing microchain transitions, with randomness
+/// initiated from system entropy
+pub(crate) fn generate_key() -> SigningKey {
+    SigningKey::random(&mut ChaCha20Rng::from_entropy())
+}
+
+/// Writes a key to a new file, which is only readable by its owner on unix.
+/// Fails if the file already exists, so that no key is ever overwritten.
+pub(crate) fn write_key(key: &SigningKey, path: &Utf8Path) -> Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    file.write_all(&key.to_bytes())?;
+    file.sync_all()?;
+    Ok(())
+}
+
+pub(crate) fn read_key(path: &Utf8Path) -> Result<SigningKey> {
+    Ok(SigningKey::from_slice(&std::fs::read(path)?)?)
+}
+
+/// The SEC1-encoded public key that identifies the owner of a microchain
+pub(crate) fn public_key_bytes(key: &SigningKey) -> Vec<u8> {
+    key.verifying_key().to_sec1_bytes().into_vec()
+}
+
+#[inline]
+pub(crate) fn is_valid_public_key(bytes: &[u8]) -> bool {
+    VerifyingKey::from_sec1_bytes(bytes).is_ok()
+}
+
+/// The message signed by the owner of a microchain to authorize a transition:
+/// the chain ID, the number of transitions that precede it and the state it
+/// leads to
+fn transition_message(id: &[F; DIGEST_SIZE], height: usize, next_state: &ZPtr<F>) -> Vec<u8> {
+    bincode::serialize(&(id, height as u64, next_state)).expect("Failed to serialize transition")
+}
+
+pub(crate) fn sign_transition(
+    key: &SigningKey,
+    id: &[F; DIGEST_SIZE],
+    height: usize,
+    next_state: &ZPtr<F>,
+) -> Vec<u8> {
+    let signature: Signature = key.sign(&transition_message(id, height, next_state));
+    signature.to_vec()
+}
+
+pub(crate) fn verify_transition(
+    public_key: &[u8],
+    signature: &[u8],
+    id: &[F; DIGEST_SIZE],
+    height: usize,
+    next_state: &ZPtr<F>,
+) -> bool {
+    let (Ok(public_key), Ok(signature)) = (
+        VerifyingKey::from_sec1_bytes(public_key),
+        Signature::from_slice(signature),
+    ) else {
+        return false;
+    };
+    let message = transition_message(id, height, next_state);
+    public_key.verify(&message, &signature).is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use camino::Utf8PathBuf;
+    use p3_field::AbstractField;
+    use tempfile::tempdir;
+
+    use crate::lurk::zstore::{ZPtr, DIGEST_SIZE};
+
+    use super::{
+        super::F, generate_key, public_key_bytes, read_key, sign_transition, verify_transition,
+        write_key,
+    };
+
+    #[test]
+    fn test_key_files() {
+        let dir = tempdir().unwrap();
+        let path = Utf8PathBuf::try_from(dir.path().join("key")).unwrap();
+        let key = generate_key();
+        write_key(&key, &path).unwrap();
+        assert_eq!(read_key(&path).unwrap().to_bytes(), key.to_bytes());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // existing keys are never overwritten
+        assert!(write_key(&generate_key(), &path).is_err());
+        assert_eq!(read_key(&path).unwrap().to_bytes(), key.to_bytes());
+    }
+
+    #[test]
+    fn test_transition_signatures() {
+        let key = generate_key();
+        let public_key = public_key_bytes(&key);
+        let id = [F::one(); DIGEST_SIZE];
+        let next_state = ZPtr::u64(42);
+        let signature = sign_transition(&key, &id, 3, &next_state);
+        assert!(verify_transition(
+            &public_key,
+            &signature,
+            &id,
+            3,
+            &next_state
+        ));
+
+        // the signature is bound to the height, the state and the signer
+        assert!(!verify_transition(
+            &public_key,
+            &signature,
+            &id,
+            4,
+            &next_state
+        ));
+        let other_state = ZPtr::u64(43);
+        assert!(!verify_transition(
+            &public_key,
+            &signature,
+            &id,
+            3,
+            &other_state
+        ));
+        let other_public_key = public_key_bytes(&generate_key());
+        assert!(!verify_transition(
+            &other_public_key,
+            &signature,
+            &id,
+            3,
+            &next_state
+        ));
+    }
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::lurk::zstore::ZPtr;
+
+use super::{super::proofs::OpaqueChainProof, F};
+
+/// How much of the history of a microchain is kept by the server
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub(crate) enum Retention {
+    /// Every transition proof is kept
+    Full,
+    /// Once this many proofs accumulate after the latest checkpoint, they're
+    /// pruned and replaced by a new checkpoint
+    Checkpoint(usize),
+}
+
+impl Retention {
+    #[inline]
+    pub(crate) fn is_valid(&self) -> bool {
+        !matches!(self, Self::Checkpoint(0))
+    }
+}
+
+/// A compact record of the pruned history of a microchain
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct Checkpoint {
+    /// The number of transitions covered by the checkpoint
+    pub(crate) height: usize,
+    /// The chain result at `height`
+    pub(crate) chain_result: ZPtr<F>,
+    /// The callable at `height`
+    pub(crate) callable: ZPtr<F>,
+    /// A hash chain over every pruned proof, from the first transition on. Each
+    /// link is the SHA-256 digest of the previous link followed by the bincode
+    /// serialization of a proof, starting from 32 zero bytes.
+    pub(crate) proofs_hash: [u8; 32],
+}
+
+impl Checkpoint {
+    /// Builds the checkpoint that follows `previous` by pruning `proofs`, which
+    /// must be non-empty
+    pub(crate) fn new(previous: Option<&Checkpoint>, proofs: &[OpaqueChainProof]) -> Self {
+        let last_proof = proofs.last().expect("Can't checkpoint without proofs");
+        let (base_height, proofs_hash) =
+            previous.map_or((0, [0; 32]), |c| (c.height, c.proofs_hash));
+        Self {
+            height: base_height + proofs.len(),
+            chain_result: last_proof.next_chain_result,
+            callable: last_proof.next_callable,
+            proofs_hash: extend_proofs_hash(proofs_hash, proofs),
+        }
+    }
+}
+
+/// Extends a hash chain of proofs, as described in `Checkpoint::proofs_hash`
+fn extend_proofs_hash(mut hash: [u8; 32], proofs: &[OpaqueChainProof]) -> [u8; 32] {
+    for proof in proofs {
+        let proof_bytes = bincode::serialize(proof).expect("Failed to serialize proof");
+        let mut hasher = Sha256::new();
+        hasher.update(hash);
+        hasher.update(proof_bytes);
+        hash = hasher.finalize().into();
+    }
+    hash
+}
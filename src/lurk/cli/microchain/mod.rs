@@ -0,0 +1,1154 @@
+mod archive;
+mod auth;
+mod checkpoint;
+mod protocol;
+mod store;
+
+use anyhow::{bail, Result};
+use camino::Utf8PathBuf;
+use clap::{Args, Subcommand};
+use p3_baby_bear::BabyBear;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    net::{TcpListener, TcpStream},
+    num::NonZeroUsize,
+    sync::{
+        mpsc::{self, sync_channel, Receiver, RecvTimeoutError, SyncSender},
+        Arc, Mutex, RwLock,
+    },
+    thread::available_parallelism,
+    time::Duration,
+};
+
+use crate::{
+    lair::chipset::Chipset,
+    lurk::{
+        chipset::LurkChip,
+        cli::rdg::rand_digest,
+        eval_direct::build_lurk_toplevel_native,
+        verifier::{get_verifier_version, CryptoProof, Verifier},
+        zstore::{ZPtr, ZStore, DIGEST_SIZE},
+    },
+};
+
+use super::{
+    comm_data::CommData,
+    lurk_data::LurkData,
+    paths::microchains_dir,
+    proofs::{ChainProof, OpaqueChainProof},
+};
+
+pub(crate) use archive::{request_export, request_import, ChainArchive};
+use archive::{ExportArgs, ImportArgs};
+pub(crate) use auth::{generate_key, public_key_bytes, read_key, sign_transition, write_key};
+use auth::{is_valid_public_key, verify_transition};
+pub(crate) use checkpoint::{Checkpoint, Retention};
+use protocol::{accept, max_message_size, read_data_within, MessageTooLarge};
+pub(crate) use protocol::{connect, read_data, write_data};
+use store::{ChainStore, Recovery, TransitionRecord};
+
+#[derive(Args, Debug)]
+#[clap(args_conflicts_with_subcommands = true)]
+pub(crate) struct MicrochainArgs {
+    #[clap(subcommand)]
+    command: Option<MicrochainCommand>,
+
+    #[clap(flatten)]
+    server_args: Option<ServerArgs>,
+}
+
+#[derive(Subcommand, Debug)]
+enum MicrochainCommand {
+    /// Exports a microchain from a server to an archive file
+    Export(ExportArgs),
+    /// Imports a microchain from an archive file into a server, which verifies
+    /// every proof from genesis before accepting it
+    Import(ImportArgs),
+}
+
+#[derive(Args, Debug)]
+struct ServerArgs {
+    // The IP address with the port. E.g. "127.0.0.1:1234"
+    #[clap(value_parser)]
+    addr: String,
+
+    /// Directory where the microchains are persisted (defaults to ~/.lurk/microchains)
+    #[clap(long, value_parser)]
+    data_dir: Option<Utf8PathBuf>,
+
+    /// Number of transitions between snapshots of a microchain
+    #[clap(long, value_parser, default_value_t = 64)]
+    snapshot_interval: usize,
+
+    /// Number of threads handling connections (defaults to the available parallelism)
+    #[clap(long, value_parser)]
+    workers: Option<usize>,
+
+    /// Time limit for receiving a whole message and for each write, in seconds.
+    /// Zero disables it
+    #[clap(long, value_parser, default_value_t = 60)]
+    timeout: u64,
+
+    /// Maximum size of a request, in bytes (defaults to 64 MiB, or to the value
+    /// of the LURK_MAX_MESSAGE_SIZE environment variable)
+    #[clap(long, value_parser, default_value_t = max_message_size())]
+    max_message_size: usize,
+}
+
+type F = BabyBear;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) enum CallableData {
+    Comm(CommData<F>),
+    Fun(LurkData<F>),
+}
+
+impl CallableData {
+    fn has_opaque_data(&self) -> bool {
+        match self {
+            Self::Comm(comm_data) => comm_data.payload_has_opaque_data(),
+            Self::Fun(lurk_data) => lurk_data.has_opaque_data(),
+        }
+    }
+
+    fn zptr(&self, zstore: &mut ZStore<F, LurkChip>) -> ZPtr<F> {
+        match self {
+            Self::Comm(comm_data) => comm_data.commit(zstore),
+            Self::Fun(lurk_data) => lurk_data.zptr,
+        }
+    }
+}
+
+/// Encodes a `(chain-result . callable)` pair, the result of chaining a callable.
+/// The pair components carry the corresponding `ZDag`s in order to be fully
+/// transferable between clients (through the server)
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct ChainState {
+    pub(crate) chain_result: LurkData<F>,
+    pub(crate) callable_data: CallableData,
+}
+
+impl ChainState {
+    pub(crate) fn into_zptr<C1: Chipset<F>>(self, zstore: &mut ZStore<F, C1>) -> ZPtr<F> {
+        let Self {
+            chain_result,
+            callable_data,
+        } = self;
+        let chain_result_zptr = chain_result.populate_zstore(zstore);
+        let callable_zptr = match callable_data {
+            CallableData::Comm(comm_data) => {
+                let zptr = comm_data.commit(zstore);
+                comm_data.populate_zstore(zstore);
+                zptr
+            }
+            CallableData::Fun(lurk_data) => lurk_data.populate_zstore(zstore),
+        };
+        zstore.intern_cons(chain_result_zptr, callable_zptr)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) enum Request {
+    /// Starts a microchain, optionally owned by the holder of the key matching
+    /// a SEC1-encoded public key
+    Start(ChainState, Retention, Option<Vec<u8>>),
+    GetGenesis([F; DIGEST_SIZE]),
+    GetState([F; DIGEST_SIZE]),
+    /// Submits a transition proof along with the state it was made from, which
+    /// must be the current state of the microchain. Transitions on owned
+    /// microchains must be signed by the owner, as in `sign_transition`.
+    Transition([F; DIGEST_SIZE], ZPtr<F>, ChainProof, Option<Vec<u8>>),
+    /// Requests the number of transitions since genesis
+    GetHeight([F; DIGEST_SIZE]),
+    GetProofs([F; DIGEST_SIZE]),
+    /// Requests up to a number of proofs, starting from a given height. Pages
+    /// are capped at `MAX_RANGE_SIZE` proofs.
+    GetProofRange([F; DIGEST_SIZE], usize, usize),
+    /// Requests the state of a microchain right after a number of transitions
+    GetStateAt([F; DIGEST_SIZE], usize),
+    /// Requests the server to re-verify the transitions in the height range
+    /// `[start, end)`, starting from the stored genesis state
+    VerifyRange([F; DIGEST_SIZE], usize, usize),
+    /// Requests the full data of a microchain, to be moved to another server
+    /// or audited offline. Chains whose history was pruned can't be exported.
+    Export([F; DIGEST_SIZE]),
+    /// Submits a microchain to be verified and accepted by the server. The
+    /// archive must carry the full history, without checkpoints.
+    Import(ChainArchive),
+    /// Keeps the connection open to receive a `Response::Subscribed` followed
+    /// by a `Response::Event` for every transition accepted from then on. The
+    /// server closes the connection if the subscriber falls behind or sends
+    /// anything else.
+    Subscribe([F; DIGEST_SIZE]),
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) enum Response {
+    BadRequest,
+    IdSecret([F; DIGEST_SIZE]),
+    NoDataForId,
+    Genesis([F; DIGEST_SIZE], ChainState),
+    /// The current height and state of a microchain
+    State(usize, ChainState),
+    ChainResultIsOpaque,
+    NextCallableIsOpaque,
+    ProofVerificationFailed(String),
+    ProofAccepted,
+    Proofs(Vec<OpaqueChainProof>),
+    PersistenceFailed,
+    /// The height of a microchain along with a page of its proofs
+    ProofRange(usize, Vec<OpaqueChainProof>),
+    /// The chain result and the callable of a past state, which are opaque
+    /// since the server only keeps the data for the genesis and current states
+    PastState(ZPtr<F>, ZPtr<F>),
+    InvalidHeight,
+    RangeVerified,
+    /// The height of the first transition that failed to verify
+    TransitionFailed(usize),
+    /// A transition was made from a state other than the current one. Carries
+    /// the current height and state so the client can rebase and retry.
+    StaleParent(usize, ChainState),
+    /// The requested history is no longer available, having been replaced by
+    /// the latest checkpoint
+    HistoryPruned(Checkpoint),
+    Archive(ChainArchive),
+    Imported,
+    IdAlreadyInUse,
+    /// The reason why an archive was rejected
+    InvalidArchive(String),
+    /// Carries the maximum size of a request accepted by the server
+    MessageTooLarge(usize),
+    /// The microchain or the server reached its limit of subscribers
+    TooManySubscribers,
+    /// The response would exceed the maximum size of a message, which it
+    /// carries. Ranges of proofs can still be requested with `GetProofRange`.
+    ResponseTooLarge(usize),
+    /// The transition lacks a valid signature from the owner of the microchain
+    Unauthorized,
+    Height(usize),
+    /// The current height and state of a microchain, sent when a subscription
+    /// starts
+    Subscribed(usize, ChainState),
+    Event(TransitionEvent),
+}
+
+impl Response {
+    /// Replaces the response by `ResponseTooLarge` if its frame would exceed
+    /// `max_size` bytes, which clients with the same limit would refuse
+    fn within(self, max_size: usize) -> Self {
+        match bincode::serialized_size(&self) {
+            Ok(size) if size <= max_size as u64 => self,
+            _ => Self::ResponseTooLarge(max_size),
+        }
+    }
+}
+
+/// Sent to the subscribers of a microchain whenever a transition is accepted
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct TransitionEvent {
+    /// The height reached by the transition
+    pub(crate) height: usize,
+    /// The arguments of the call that led to the new state
+    pub(crate) call_args: ZPtr<F>,
+    pub(crate) state: ChainState,
+}
+
+/// Maximum number of events queued for a subscriber. Subscribers that fall
+/// further behind are dropped.
+const SUBSCRIBER_QUEUE_SIZE: usize = 64;
+
+/// Maximum number of subscribers of a single microchain
+const MAX_CHAIN_SUBSCRIBERS: usize = 64;
+
+/// Maximum number of subscribers across all microchains, each of which takes a
+/// thread of its own
+const MAX_SUBSCRIBERS: usize = 1024;
+
+/// How long a subscriber can go without events before its connection is
+/// checked for a hangup
+const SUBSCRIBER_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Maximum number of proofs served or verified by a single request
+pub(crate) const MAX_RANGE_SIZE: usize = 64;
+
+/// Holds the data for a microchain, mapped from an ID
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct ChainData {
+    /// The data for the genesis state also contains the secret used to generate
+    /// the microchain ID
+    genesis: ([F; DIGEST_SIZE], ChainState),
+    /// Sequence of chain proofs, from the first transition after the latest
+    /// checkpoint (or genesis) to the latest transition
+    proofs: Vec<OpaqueChainProof>,
+    /// How much of the chain history is kept
+    retention: Retention,
+    /// Summary of the pruned history, if any
+    checkpoint: Option<Checkpoint>,
+    /// The SEC1-encoded public key of the owner, whose signature is required
+    /// for transitions. Microchains without an owner accept any valid proof.
+    owner: Option<Vec<u8>>,
+    /// Current state of a microchain
+    state: ChainState,
+}
+
+impl ChainData {
+    /// The number of transitions covered by the latest checkpoint
+    fn base_height(&self) -> usize {
+        self.checkpoint
+            .as_ref()
+            .map_or(0, |checkpoint| checkpoint.height)
+    }
+
+    /// The number of transitions since genesis
+    pub(crate) fn height(&self) -> usize {
+        self.base_height() + self.proofs.len()
+    }
+
+    /// Returns the chain result and the callable right after `height` transitions
+    /// or the response to be sent if that state isn't available
+    fn state_zptrs_at(
+        &self,
+        height: usize,
+        zstore: &mut ZStore<F, LurkChip>,
+    ) -> Result<(ZPtr<F>, ZPtr<F>), Response> {
+        if height > self.height() {
+            return Err(Response::InvalidHeight);
+        }
+        match &self.checkpoint {
+            Some(checkpoint) if height < checkpoint.height => {
+                Err(Response::HistoryPruned(checkpoint.clone()))
+            }
+            Some(checkpoint) if height == checkpoint.height => {
+                Ok((checkpoint.chain_result, checkpoint.callable))
+            }
+            None if height == 0 => {
+                let (
+                    _,
+                    ChainState {
+                        chain_result,
+                        callable_data,
+                    },
+                ) = &self.genesis;
+                Ok((chain_result.zptr, callable_data.zptr(zstore)))
+            }
+            _ => {
+                let proof = &self.proofs[height - self.base_height() - 1];
+                Ok((proof.next_chain_result, proof.next_callable))
+            }
+        }
+    }
+
+    /// Returns the proofs in the height range `[start, end)` or the response to
+    /// be sent if they aren't available
+    fn proofs_in(&self, start: usize, end: usize) -> Result<&[OpaqueChainProof], Response> {
+        if start > end || end > self.height() {
+            return Err(Response::InvalidHeight);
+        }
+        if let Some(checkpoint) = &self.checkpoint {
+            if start < checkpoint.height {
+                return Err(Response::HistoryPruned(checkpoint.clone()));
+            }
+        }
+        let base_height = self.base_height();
+        Ok(&self.proofs[start - base_height..end - base_height])
+    }
+
+    /// Replaces the proofs by a new checkpoint if the retention policy asks for
+    /// it. Returns whether that was the case.
+    fn maybe_checkpoint(&mut self) -> bool {
+        let Retention::Checkpoint(interval) = self.retention else {
+            return false;
+        };
+        if self.proofs.len() < interval {
+            return false;
+        }
+        let checkpoint = Checkpoint::new(self.checkpoint.as_ref(), &self.proofs);
+        self.checkpoint = Some(checkpoint);
+        self.proofs.clear();
+        true
+    }
+}
+
+impl MicrochainArgs {
+    pub(crate) fn run(self) -> Result<()> {
+        match (self.command, self.server_args) {
+            (Some(MicrochainCommand::Export(export_args)), _) => export_args.run(),
+            (Some(MicrochainCommand::Import(import_args)), _) => import_args.run(),
+            (None, Some(server_args)) => server_args.run(),
+            (None, None) => unreachable!("The server arguments are required without a subcommand"),
+        }
+    }
+}
+
+impl ServerArgs {
+    fn run(self) -> Result<()> {
+        let ServerArgs {
+            addr,
+            data_dir,
+            snapshot_interval,
+            workers,
+            timeout,
+            max_message_size,
+        } = self;
+        let workers = match workers {
+            Some(0) => bail!("The number of workers must be positive"),
+            Some(workers) => workers,
+            None => available_parallelism().map_or(1, NonZeroUsize::get),
+        };
+        let timeout = (timeout > 0).then_some(Duration::from_secs(timeout));
+        let data_dir = match data_dir {
+            Some(data_dir) => data_dir,
+            None => microchains_dir()?,
+        };
+        let store = ChainStore::new(data_dir, snapshot_interval)?;
+
+        // chain id -> chain data
+        let (chains, recovery) = store.load()?;
+        println!("Loaded {} microchain(s)", chains.len());
+        if !recovery.is_empty() {
+            report_recovery(&recovery);
+        }
+
+        let listener = TcpListener::bind(&addr)?;
+        println!("Listening at {addr} with {workers} worker(s)");
+
+        let (_, zstore, _) = build_lurk_toplevel_native();
+        let server = Server {
+            verifier: Verifier::native(),
+            store,
+            chains: RwLock::new(
+                chains
+                    .into_iter()
+                    .map(|(id, chain_data)| (id, Arc::new(Mutex::new(chain_data))))
+                    .collect(),
+            ),
+            subscribers: Arc::default(),
+        };
+
+        let connection = ConnectionConfig {
+            timeout,
+            max_message_size,
+        };
+        let (sender, receiver) = mpsc::channel::<TcpStream>();
+        let receiver = Mutex::new(receiver);
+        std::thread::scope(|s| {
+            for _ in 0..workers {
+                // each worker interns data on its own copy of the store
+                let mut zstore = zstore.clone();
+                let (server, receiver) = (&server, &receiver);
+                s.spawn(move || loop {
+                    let Ok(stream) = receiver.lock().unwrap().recv() else {
+                        break;
+                    };
+                    if let Err(e) = server.handle_connection(stream, connection, &mut zstore) {
+                        eprintln!("Connection failed: {e}");
+                    }
+                });
+            }
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => sender
+                        .send(stream)
+                        .expect("The receiver outlives the listener"),
+                    Err(e) => eprintln!("Connection failed: {e}"),
+                }
+            }
+            drop(sender);
+        });
+
+        Ok(())
+    }
+}
+
+/// Warns about the data discarded while loading the persisted microchains
+fn report_recovery(recovery: &Recovery) {
+    let Recovery { skipped, truncated } = recovery;
+    for chain_dir in skipped {
+        eprintln!("Warning: skipped {chain_dir}, whose creation didn't complete");
+    }
+    for (log_path, discarded) in truncated {
+        eprintln!("Warning: discarded {discarded} bytes of an incomplete record from {log_path}");
+    }
+}
+
+/// Writes the queued events to the connection of a subscriber until its queue
+/// is dropped, a write fails or the peer hangs up, which is checked whenever no
+/// event arrives for `SUBSCRIBER_CHECK_INTERVAL`
+fn serve_subscriber(stream: &mut TcpStream, receiver: &Receiver<Response>) {
+    loop {
+        match receiver.recv_timeout(SUBSCRIBER_CHECK_INTERVAL) {
+            Ok(response) => {
+                if write_data(stream, response).is_err() {
+                    return;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                if has_hung_up(stream) {
+                    return;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+/// Whether the peer of a subscription closed the connection, without blocking.
+/// Subscribers aren't meant to send anything, so that counts as a hangup too.
+fn has_hung_up(stream: &TcpStream) -> bool {
+    if stream.set_nonblocking(true).is_err() {
+        return true;
+    }
+    let hung_up = match stream.peek(&mut [0]) {
+        Ok(_) => true,
+        Err(e) => e.kind() != io::ErrorKind::WouldBlock,
+    };
+    stream.set_nonblocking(false).is_err() || hung_up
+}
+
+/// Limits applied to every connection accepted by the server
+#[derive(Clone, Copy)]
+struct ConnectionConfig {
+    timeout: Option<Duration>,
+    max_message_size: usize,
+}
+
+/// The state of a microchain server, shared by its workers
+struct Server {
+    verifier: &'static Verifier,
+    store: ChainStore,
+    /// Each chain has its own lock so that transitions on unrelated chains can
+    /// be verified in parallel. The outer lock is only held for lookups and
+    /// insertions.
+    chains: RwLock<FxHashMap<[F; DIGEST_SIZE], Arc<Mutex<ChainData>>>>,
+    /// Each subscriber has a thread of its own that writes the queued events to
+    /// its connection, so they don't occupy workers and slow connections don't
+    /// hold the locks. The threads unregister their subscribers when they stop.
+    subscribers: Arc<Mutex<Subscribers>>,
+}
+
+/// Event queues of subscribers, by chain, identified by keys
+#[derive(Default)]
+struct Subscribers {
+    queues: FxHashMap<[F; DIGEST_SIZE], Vec<(u64, SyncSender<Response>)>>,
+    /// The number of queues across all chains
+    len: usize,
+    next_key: u64,
+}
+
+impl Subscribers {
+    /// Registers a queue for a chain and returns its key, unless the chain or
+    /// the server reached its limit of subscribers
+    fn insert(&mut self, id: [F; DIGEST_SIZE], sender: SyncSender<Response>) -> Option<u64> {
+        let chain_len = self.queues.get(&id).map_or(0, Vec::len);
+        if chain_len >= MAX_CHAIN_SUBSCRIBERS || self.len >= MAX_SUBSCRIBERS {
+            return None;
+        }
+        let key = self.next_key;
+        self.next_key += 1;
+        self.queues.entry(id).or_default().push((key, sender));
+        self.len += 1;
+        Some(key)
+    }
+
+    /// Keeps the queues of a chain for which `f` returns `true`
+    fn retain(
+        &mut self,
+        id: &[F; DIGEST_SIZE],
+        mut f: impl FnMut(u64, &SyncSender<Response>) -> bool,
+    ) {
+        let Some(queues) = self.queues.get_mut(id) else {
+            return;
+        };
+        let len = queues.len();
+        queues.retain(|(key, sender)| f(*key, sender));
+        self.len -= len - queues.len();
+        if queues.is_empty() {
+            self.queues.remove(id);
+        }
+    }
+
+    #[inline]
+    fn remove(&mut self, id: &[F; DIGEST_SIZE], key: u64) {
+        self.retain(id, |queue_key, _| queue_key != key);
+    }
+}
+
+impl Server {
+    fn handle_connection(
+        &self,
+        mut stream: TcpStream,
+        connection: ConnectionConfig,
+        zstore: &mut ZStore<F, LurkChip>,
+    ) -> Result<()> {
+        let ConnectionConfig {
+            timeout,
+            max_message_size,
+        } = connection;
+        stream.set_write_timeout(timeout)?;
+        if !accept(&stream, timeout)? {
+            return Ok(());
+        }
+        let response = match read_data_within(&stream, max_message_size, timeout) {
+            Ok(Request::Subscribe(id)) => return self.subscribe(&id, stream),
+            Ok(request) => self.handle_request(request, zstore),
+            Err(e) if e.is::<MessageTooLarge>() => Response::MessageTooLarge(max_message_size),
+            Err(_) => Response::BadRequest,
+        };
+        write_data(&mut stream, response.within(max_message_size))
+    }
+
+    fn handle_request(&self, request: Request, zstore: &mut ZStore<F, LurkChip>) -> Response {
+        match request {
+            Request::Start(chain_state, retention, owner) => {
+                self.start(chain_state, retention, owner, zstore)
+            }
+            Request::GetGenesis(id) => self.with_chain(&id, |chain_data| {
+                let (id_secret, state) = &chain_data.genesis;
+                Response::Genesis(*id_secret, state.clone())
+            }),
+            Request::GetState(id) => self.with_chain(&id, |chain_data| {
+                Response::State(chain_data.height(), chain_data.state.clone())
+            }),
+            Request::Transition(id, parent, chain_proof, signature) => {
+                self.transition(&id, &parent, chain_proof, signature.as_deref(), zstore)
+            }
+            Request::GetHeight(id) => {
+                self.with_chain(&id, |chain_data| Response::Height(chain_data.height()))
+            }
+            Request::GetProofs(id) => self.with_chain(&id, |chain_data| {
+                match chain_data.proofs_in(0, chain_data.height()) {
+                    Ok(proofs) => Response::Proofs(proofs.to_vec()),
+                    Err(response) => response,
+                }
+            }),
+            Request::GetProofRange(id, start, count) => self.with_chain(&id, |chain_data| {
+                let height = chain_data.height();
+                let end = height.min(start.saturating_add(count.min(MAX_RANGE_SIZE)));
+                match chain_data.proofs_in(start, end) {
+                    Ok(proofs) => Response::ProofRange(height, proofs.to_vec()),
+                    Err(response) => response,
+                }
+            }),
+            Request::GetStateAt(id, height) => self.with_chain(&id, |chain_data| match chain_data
+                .state_zptrs_at(height, zstore)
+            {
+                Ok((chain_result, callable)) => Response::PastState(chain_result, callable),
+                Err(response) => response,
+            }),
+            Request::VerifyRange(id, start, end) => self.verify_range(&id, start, end, zstore),
+            Request::Export(id) => self.with_chain(&id, |chain_data| {
+                // archives must be verifiable from genesis when imported
+                if let Some(checkpoint) = &chain_data.checkpoint {
+                    return Response::HistoryPruned(checkpoint.clone());
+                }
+                Response::Archive(ChainArchive {
+                    id,
+                    chain_data: chain_data.clone(),
+                })
+            }),
+            Request::Import(archive) => self.import(archive, zstore),
+            Request::Subscribe(_) => unreachable!("Subscriptions keep the connection"),
+        }
+    }
+
+    fn get_chain(&self, id: &[F; DIGEST_SIZE]) -> Option<Arc<Mutex<ChainData>>> {
+        self.chains.read().unwrap().get(id).cloned()
+    }
+
+    /// Builds a response from the data of a chain, holding its lock meanwhile
+    fn with_chain(
+        &self,
+        id: &[F; DIGEST_SIZE],
+        f: impl FnOnce(&ChainData) -> Response,
+    ) -> Response {
+        let Some(chain) = self.get_chain(id) else {
+            return Response::NoDataForId;
+        };
+        let chain_data = chain.lock().unwrap();
+        f(&chain_data)
+    }
+
+    /// Sends the current state of a chain and registers the connection to be
+    /// notified of future transitions
+    fn subscribe(&self, id: &[F; DIGEST_SIZE], mut stream: TcpStream) -> Result<()> {
+        let Some(chain) = self.get_chain(id) else {
+            return write_data(&mut stream, Response::NoDataForId);
+        };
+        let (sender, receiver) = sync_channel(SUBSCRIBER_QUEUE_SIZE);
+        let key = {
+            // holding the chain lock guarantees that no transition is missed
+            let chain_data = chain.lock().unwrap();
+            let state = chain_data.state.clone();
+            sender
+                .try_send(Response::Subscribed(chain_data.height(), state))
+                .expect("The queue is empty");
+            self.subscribers.lock().unwrap().insert(*id, sender)
+        };
+        let Some(key) = key else {
+            return write_data(&mut stream, Response::TooManySubscribers);
+        };
+        let (id, subscribers) = (*id, self.subscribers.clone());
+        std::thread::spawn(move || {
+            serve_subscriber(&mut stream, &receiver);
+            subscribers.lock().unwrap().remove(&id, key);
+        });
+        Ok(())
+    }
+
+    /// Queues an event for the subscribers of a chain, dropping the ones whose
+    /// connections failed or whose queues are full. Must be called with the
+    /// lock of the chain held, so the events are queued in order.
+    fn notify(&self, id: &[F; DIGEST_SIZE], event: TransitionEvent) {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain(id, |_, sender| {
+            sender.try_send(Response::Event(event.clone())).is_ok()
+        });
+    }
+
+    fn start(
+        &self,
+        chain_state: ChainState,
+        retention: Retention,
+        owner: Option<Vec<u8>>,
+        zstore: &mut ZStore<F, LurkChip>,
+    ) -> Response {
+        if !retention.is_valid() {
+            return Response::BadRequest;
+        }
+        if owner
+            .as_deref()
+            .is_some_and(|owner| !is_valid_public_key(owner))
+        {
+            return Response::BadRequest;
+        }
+        if chain_state.chain_result.has_opaque_data() {
+            return Response::ChainResultIsOpaque;
+        }
+        if chain_state.callable_data.has_opaque_data() {
+            return Response::NextCallableIsOpaque;
+        }
+
+        let id_secret = rand_digest();
+        let callable_zptr = chain_state.callable_data.zptr(zstore);
+        let state_cons = zstore.intern_cons(chain_state.chain_result.zptr, callable_zptr);
+        let id = CommData::hash(&id_secret, &state_cons, zstore);
+
+        let chain_data = ChainData {
+            genesis: (id_secret, chain_state.clone()),
+            proofs: vec![],
+            retention,
+            checkpoint: None,
+            owner,
+            state: chain_state,
+        };
+        if let Err(e) = self.store.create(&id, &chain_data) {
+            eprintln!("Failed to persist microchain: {e}");
+            return Response::PersistenceFailed;
+        }
+        let chain = Arc::new(Mutex::new(chain_data));
+        assert!(self.chains.write().unwrap().insert(id, chain).is_none());
+        Response::IdSecret(id_secret)
+    }
+
+    fn transition(
+        &self,
+        id: &[F; DIGEST_SIZE],
+        parent: &ZPtr<F>,
+        chain_proof: ChainProof,
+        signature: Option<&[u8]>,
+        zstore: &mut ZStore<F, LurkChip>,
+    ) -> Response {
+        let Some(chain) = self.get_chain(id) else {
+            return Response::NoDataForId;
+        };
+
+        let ChainProof {
+            crypto_proof,
+            call_args,
+            next_chain_result,
+            next_callable,
+        } = chain_proof;
+
+        let next_chain_result_zptr = {
+            if next_chain_result.has_opaque_data() {
+                return Response::ChainResultIsOpaque;
+            }
+            next_chain_result.zptr
+        };
+
+        let next_callable_zptr = match &next_callable {
+            CallableData::Comm(comm_data) => {
+                if comm_data.payload_has_opaque_data() {
+                    return Response::NextCallableIsOpaque;
+                }
+                comm_data.commit(zstore)
+            }
+            CallableData::Fun(lurk_data) => {
+                if lurk_data.has_opaque_data() {
+                    return Response::NextCallableIsOpaque;
+                }
+                lurk_data.zptr
+            }
+        };
+
+        // transitions on the same chain are serialized from here on
+        let mut chain_data = chain.lock().unwrap();
+
+        // reject the transition upfront if the chain has moved on from the state
+        // the client started from, which happens when clients race
+        let callable_zptr = chain_data.state.callable_data.zptr(zstore);
+        let head = zstore.intern_cons(chain_data.state.chain_result.zptr, callable_zptr);
+        if head != *parent {
+            return Response::StaleParent(chain_data.height(), chain_data.state.clone());
+        }
+
+        // the expression is a call whose callable is part of the server state
+        // and the arguments are provided by the client
+        let expr = zstore.intern_cons(callable_zptr, call_args);
+
+        // the result is a pair composed by the chain result and next callable
+        // provided by the client
+        let result = zstore.intern_cons(next_chain_result_zptr, next_callable_zptr);
+
+        // the owner must have signed the transition, which is much cheaper to
+        // check than the proof
+        if let Some(owner) = &chain_data.owner {
+            let Some(signature) = signature else {
+                return Response::Unauthorized;
+            };
+            if !verify_transition(owner, signature, id, chain_data.height(), &result) {
+                return Response::Unauthorized;
+            }
+        }
+
+        // and now the proof must verify, meaning that the user must have
+        // used the correct callable from the server state
+        let empty_env = zstore.intern_empty_env();
+        let machine_proof = crypto_proof.into_machine_proof(&expr, &empty_env, &result);
+        if !self.verifier.verify(&machine_proof) {
+            let verifier_version = get_verifier_version().to_string();
+            return Response::ProofVerificationFailed(verifier_version);
+        }
+
+        // everything went okay... transition to the next state
+
+        // persist the transition before applying it
+        let record = TransitionRecord {
+            height: chain_data.height(),
+            proof: OpaqueChainProof {
+                crypto_proof: CryptoProof::new(machine_proof, get_verifier_version().to_string()),
+                call_args,
+                next_chain_result: next_chain_result_zptr,
+                next_callable: next_callable_zptr,
+            },
+            state: ChainState {
+                chain_result: next_chain_result,
+                callable_data: next_callable,
+            },
+        };
+        if let Err(e) = self.store.append(id, &record) {
+            eprintln!("Failed to persist transition: {e}");
+            return Response::PersistenceFailed;
+        }
+
+        // store new proof and update the state
+        let TransitionRecord { proof, state, .. } = record;
+        chain_data.proofs.push(proof);
+        chain_data.state = state;
+
+        // a failed snapshot is harmless since the log is kept
+        let snapshot = if chain_data.maybe_checkpoint() {
+            // the pruned proofs only go away from the disk with a new snapshot
+            self.store.snapshot(id, &chain_data)
+        } else {
+            self.store.maybe_snapshot(id, &chain_data)
+        };
+        if let Err(e) = snapshot {
+            eprintln!("Failed to snapshot microchain: {e}");
+        }
+
+        self.notify(
+            id,
+            TransitionEvent {
+                height: chain_data.height(),
+                call_args,
+                state: chain_data.state.clone(),
+            },
+        );
+
+        Response::ProofAccepted
+    }
+
+    fn verify_range(
+        &self,
+        id: &[F; DIGEST_SIZE],
+        start: usize,
+        end: usize,
+        zstore: &mut ZStore<F, LurkChip>,
+    ) -> Response {
+        if start > end || end - start > MAX_RANGE_SIZE {
+            return Response::BadRequest;
+        }
+        let Some(chain) = self.get_chain(id) else {
+            return Response::NoDataForId;
+        };
+
+        // copy what's needed so the chain isn't locked during verification
+        let (callable, proofs) = {
+            let chain_data = chain.lock().unwrap();
+            match (
+                chain_data.proofs_in(start, end),
+                chain_data.state_zptrs_at(start, zstore),
+            ) {
+                (Ok(proofs), Ok((_, callable))) => (callable, proofs.to_vec()),
+                (Err(response), _) | (_, Err(response)) => return response,
+            }
+        };
+
+        match self.verify_proofs(start, callable, &proofs, zstore) {
+            Ok(()) => Response::RangeVerified,
+            Err(height) => Response::TransitionFailed(height),
+        }
+    }
+
+    /// Verifies a sequence of proofs for the transitions that follow the state
+    /// at height `start`, whose callable is `callable`. Returns the height of
+    /// the first transition that fails to verify, if any.
+    fn verify_proofs(
+        &self,
+        start: usize,
+        mut callable: ZPtr<F>,
+        proofs: &[OpaqueChainProof],
+        zstore: &mut ZStore<F, LurkChip>,
+    ) -> Result<(), usize> {
+        let empty_env = zstore.intern_empty_env();
+        for (height, proof) in (start..).zip(proofs) {
+            let OpaqueChainProof {
+                crypto_proof,
+                call_args,
+                next_chain_result,
+                next_callable,
+            } = proof;
+            let expr = zstore.intern_cons(callable, *call_args);
+            let result = zstore.intern_cons(*next_chain_result, *next_callable);
+            let machine_proof = crypto_proof
+                .clone()
+                .into_machine_proof(&expr, &empty_env, &result);
+            if !self.verifier.verify(&machine_proof) {
+                return Err(height);
+            }
+            callable = *next_callable;
+        }
+        Ok(())
+    }
+
+    fn import(&self, archive: ChainArchive, zstore: &mut ZStore<F, LurkChip>) -> Response {
+        macro_rules! reject {
+            ($reason:expr) => {
+                return Response::InvalidArchive($reason.to_string())
+            };
+        }
+        let ChainArchive { id, chain_data } = archive;
+        if self.get_chain(&id).is_some() {
+            return Response::IdAlreadyInUse;
+        }
+        let (id_secret, genesis) = &chain_data.genesis;
+        if genesis.chain_result.has_opaque_data() || genesis.callable_data.has_opaque_data() {
+            reject!("The genesis state has opaque data");
+        }
+        if chain_data.state.chain_result.has_opaque_data()
+            || chain_data.state.callable_data.has_opaque_data()
+        {
+            reject!("The current state has opaque data");
+        }
+        if !chain_data.retention.is_valid() {
+            reject!("Invalid retention policy");
+        }
+        if let Some(owner) = &chain_data.owner {
+            if !is_valid_public_key(owner) {
+                reject!("Invalid owner public key");
+            }
+        }
+
+        // the ID must be a commitment to the genesis state
+        let genesis_callable = genesis.callable_data.zptr(zstore);
+        let genesis_cons = zstore.intern_cons(genesis.chain_result.zptr, genesis_callable);
+        if CommData::hash(id_secret, &genesis_cons, zstore) != id {
+            reject!("The ID doesn't match the genesis state");
+        }
+
+        // a checkpoint can't be trusted without the proofs it replaced, so only
+        // chains with their full history can be imported
+        if chain_data.checkpoint.is_some() {
+            reject!("The history was pruned, so it can't be verified from genesis");
+        }
+
+        // every proof must verify, starting from genesis
+        if let Err(height) = self.verify_proofs(0, genesis_callable, &chain_data.proofs, zstore) {
+            return Response::TransitionFailed(height);
+        }
+
+        // and the proofs must lead to the current state
+        let Ok(last_state) = chain_data.state_zptrs_at(chain_data.height(), zstore) else {
+            reject!("Inconsistent chain height");
+        };
+        let current_callable = chain_data.state.callable_data.zptr(zstore);
+        if last_state != (chain_data.state.chain_result.zptr, current_callable) {
+            reject!("The proofs don't lead to the current state");
+        }
+
+        let mut chains = self.chains.write().unwrap();
+        if chains.contains_key(&id) {
+            return Response::IdAlreadyInUse;
+        }
+        if let Err(e) = self.store.create(&id, &chain_data) {
+            eprintln!("Failed to persist microchain: {e}");
+            return Response::PersistenceFailed;
+        }
+        chains.insert(id, Arc::new(Mutex::new(chain_data)));
+        Response::Imported
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use camino::Utf8PathBuf;
+    use p3_field::AbstractField;
+    use std::{
+        net::{TcpListener, TcpStream},
+        sync::{mpsc::sync_channel, RwLock},
+        time::Duration,
+    };
+    use tempfile::tempdir;
+
+    use crate::lurk::{
+        cli::{comm_data::CommData, lurk_data::LurkData, proofs::OpaqueChainProof},
+        verifier::{CryptoProof, Verifier},
+        zstore::{lurk_zstore, DIGEST_SIZE},
+    };
+
+    use super::{
+        has_hung_up, store::ChainStore, CallableData, ChainState, Request, Response, Retention,
+        Server, Subscribers, F, MAX_CHAIN_SUBSCRIBERS, MAX_SUBSCRIBERS,
+    };
+
+    fn new_server() -> (Server, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let store_dir = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        let server = Server {
+            verifier: Verifier::native(),
+            store: ChainStore::new(store_dir, 64).unwrap(),
+            chains: RwLock::default(),
+            subscribers: Arc::default(),
+        };
+        (server, dir)
+    }
+
+    #[test]
+    fn test_subscriber_limits() {
+        let mut subscribers = Subscribers::default();
+        let (sender, _receiver) = sync_channel(1);
+        let (id, other_id) = ([F::zero(); DIGEST_SIZE], [F::one(); DIGEST_SIZE]);
+        let keys = (0..MAX_CHAIN_SUBSCRIBERS)
+            .map(|_| subscribers.insert(id, sender.clone()).unwrap())
+            .collect::<Vec<_>>();
+        assert!(subscribers.insert(id, sender.clone()).is_none());
+        assert!(subscribers.insert(other_id, sender.clone()).is_some());
+        subscribers.remove(&id, keys[0]);
+        assert_eq!(subscribers.len, MAX_CHAIN_SUBSCRIBERS);
+        assert!(subscribers.insert(id, sender.clone()).is_some());
+
+        // the limit across chains
+        for i in 2..=(MAX_SUBSCRIBERS / MAX_CHAIN_SUBSCRIBERS) {
+            let id = [F::from_canonical_usize(i); DIGEST_SIZE];
+            while subscribers.insert(id, sender.clone()).is_some() {}
+        }
+        assert_eq!(subscribers.len, MAX_SUBSCRIBERS);
+        let id = [F::two(); DIGEST_SIZE];
+        subscribers.retain(&id, |_, _| false);
+        assert!(!subscribers.queues.contains_key(&id));
+        assert_eq!(subscribers.len, MAX_SUBSCRIBERS - MAX_CHAIN_SUBSCRIBERS);
+    }
+
+    #[test]
+    fn test_subscriber_hangup() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        assert!(!has_hung_up(&stream));
+        drop(client);
+        let hung_up = (0..100).any(|_| {
+            std::thread::sleep(Duration::from_millis(10));
+            has_hung_up(&stream)
+        });
+        assert!(hung_up);
+    }
+
+    #[test]
+    fn test_response_size() {
+        let zstore = lurk_zstore();
+        let nil = *zstore.nil();
+        let proof = OpaqueChainProof {
+            crypto_proof: CryptoProof::empty(),
+            call_args: nil,
+            next_chain_result: nil,
+            next_callable: nil,
+        };
+        let response = Response::Proofs(vec![proof; 8]);
+        let size = bincode::serialized_size(&response).unwrap() as usize;
+        let response = response.within(size);
+        assert!(matches!(response, Response::Proofs(ref proofs) if proofs.len() == 8));
+        let response = response.within(size - 1);
+        assert!(matches!(response, Response::ResponseTooLarge(max_size) if max_size == size - 1));
+    }
+
+    #[test]
+    fn test_export_pruned_chain() {
+        let zstore = &mut lurk_zstore();
+        let nil = *zstore.nil();
+        let state = ChainState {
+            chain_result: LurkData::new(nil, zstore),
+            callable_data: CallableData::Fun(LurkData::new(nil, zstore)),
+        };
+        let state_cons = zstore.intern_cons(nil, nil);
+        let (server, _dir) = new_server();
+        let mut start = |retention| {
+            let Response::IdSecret(id_secret) =
+                server.start(state.clone(), retention, None, zstore)
+            else {
+                panic!("Failed to start microchain");
+            };
+            CommData::hash(&id_secret, &state_cons, zstore)
+        };
+        let full_id = start(Retention::Full);
+        let pruned_id = start(Retention::Checkpoint(1));
+        // a sham transition back to the genesis state, only to be pruned
+        {
+            let chain = server.get_chain(&pruned_id).unwrap();
+            let mut chain_data = chain.lock().unwrap();
+            chain_data.proofs.push(OpaqueChainProof {
+                crypto_proof: CryptoProof::empty(),
+                call_args: nil,
+                next_chain_result: nil,
+                next_callable: nil,
+            });
+            assert!(chain_data.maybe_checkpoint());
+        }
+
+        let response = server.handle_request(Request::Export(pruned_id), zstore);
+        assert!(matches!(response, Response::HistoryPruned(checkpoint) if checkpoint.height == 1));
+
+        // chains with their full history go back and forth between servers
+        let Response::Archive(archive) = server.handle_request(Request::Export(full_id), zstore)
+        else {
+            panic!("Failed to export microchain");
+        };
+        let (other_server, _other_dir) = new_server();
+        let response = other_server.handle_request(Request::Import(archive), zstore);
+        assert!(matches!(response, Response::Imported));
+        let response = other_server.handle_request(Request::Export(full_id), zstore);
+        assert!(matches!(response, Response::Archive(archive) if archive.id == full_id));
+    }
+}
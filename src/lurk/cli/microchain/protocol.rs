@@ -0,0 +1,262 @@
+use anyhow::{bail, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    io::{self, Read, Write},
+    net::TcpStream,
+    time::{Duration, Instant},
+};
+
+use crate::lurk::verifier::get_verifier_version;
+
+/// Bumped whenever `Request` or `Response` change
+pub(crate) const PROTOCOL_VERSION: u32 = 5;
+
+/// Prefixes every frame, to quickly tell apart peers that speak something else
+const FRAME_MAGIC: [u8; 4] = *b"LMCP";
+
+/// Default upper bound for the size of a message, in bytes. The largest message
+/// in regular use is a `Transition` request, which carries a single proof whose
+/// size grows with the number of shards (see `CryptoProof`), so this fits the
+/// proof of a reduction spanning dozens of shards. Messages carrying several
+/// proofs, such as archives, may need a higher limit. Servers apply their limit
+/// to responses as well, answering with `Response::ResponseTooLarge` instead.
+pub(crate) const MAX_MESSAGE_SIZE: usize = 1 << 26;
+
+/// Environment variable that overrides `MAX_MESSAGE_SIZE`
+const MAX_MESSAGE_SIZE_VAR: &str = "LURK_MAX_MESSAGE_SIZE";
+
+/// The upper bound for the size of a message, which is `MAX_MESSAGE_SIZE`
+/// unless overridden by the `LURK_MAX_MESSAGE_SIZE` environment variable
+pub(crate) fn max_message_size() -> usize {
+    std::env::var(MAX_MESSAGE_SIZE_VAR)
+        .map_or(MAX_MESSAGE_SIZE, |s| s.parse().unwrap_or(MAX_MESSAGE_SIZE))
+}
+
+/// Payloads are read into a buffer that starts with at most this capacity and
+/// grows as bytes arrive, so a size prefix alone can't make the receiver
+/// allocate much
+const INITIAL_BUFFER_SIZE: usize = 1 << 16;
+
+/// Upper bound for the size of a handshake, which is read before the peer is
+/// known to speak the same protocol
+const MAX_HANDSHAKE_SIZE: usize = 1 << 10;
+
+/// The first message sent by a client on every connection. Its layout must
+/// never change, so that both ends can always detect a version mismatch.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Handshake {
+    protocol_version: u32,
+    verifier_version: String,
+}
+
+/// The server's answer to a `Handshake`
+#[derive(Serialize, Deserialize)]
+pub(crate) enum HandshakeResponse {
+    Accepted,
+    /// Carries the protocol version of the server
+    ProtocolMismatch(u32),
+    /// Carries the verifier version of the server
+    VerifierMismatch(String),
+}
+
+impl Handshake {
+    fn new() -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            verifier_version: get_verifier_version().to_string(),
+        }
+    }
+
+    fn check(&self) -> HandshakeResponse {
+        if self.protocol_version != PROTOCOL_VERSION {
+            HandshakeResponse::ProtocolMismatch(PROTOCOL_VERSION)
+        } else if self.verifier_version != get_verifier_version() {
+            HandshakeResponse::VerifierMismatch(get_verifier_version().to_string())
+        } else {
+            HandshakeResponse::Accepted
+        }
+    }
+}
+
+/// Connects to a microchain server, making sure both ends speak the same
+/// protocol and verify proofs in the same way
+pub(crate) fn connect(addr: &str) -> Result<TcpStream> {
+    let mut stream = TcpStream::connect(addr)?;
+    write_data(&mut stream, Handshake::new())?;
+    match read_data_limited(&mut stream, MAX_HANDSHAKE_SIZE)? {
+        HandshakeResponse::Accepted => Ok(stream),
+        HandshakeResponse::ProtocolMismatch(version) => bail!(
+            "The server speaks microchain protocol version {version} but this client speaks version {PROTOCOL_VERSION}"
+        ),
+        HandshakeResponse::VerifierMismatch(version) => bail!(
+            "The server runs verifier version {version} but this client runs version {}",
+            get_verifier_version()
+        ),
+    }
+}
+
+/// Reads the handshake of a client and answers it, within `timeout` if set.
+/// Returns whether the connection can move on to requests.
+pub(crate) fn accept(stream: &TcpStream, timeout: Option<Duration>) -> Result<bool> {
+    let response = read_data_within::<Handshake>(stream, MAX_HANDSHAKE_SIZE, timeout)?.check();
+    let accepted = matches!(response, HandshakeResponse::Accepted);
+    write_data(&mut &*stream, response)?;
+    Ok(accepted)
+}
+
+/// Reads from a `TcpStream` under a deadline for a whole message rather than
+/// for each read, so a peer can't hold the connection by trickling bytes
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+/// Like `read_data_limited`, but fails if the whole message isn't received
+/// within `timeout`, if set
+pub(crate) fn read_data_within<T: DeserializeOwned>(
+    stream: &TcpStream,
+    max_size: usize,
+    timeout: Option<Duration>,
+) -> Result<T> {
+    match timeout {
+        Some(timeout) => {
+            let deadline = Instant::now() + timeout;
+            read_data_limited(&mut DeadlineReader { stream, deadline }, max_size)
+        }
+        None => read_data_limited(&mut &*stream, max_size),
+    }
+}
+
+/// The error for messages whose size exceeds the limit of the receiver
+#[derive(Debug)]
+pub(crate) struct MessageTooLarge {
+    size: u64,
+    max_size: usize,
+}
+
+impl std::fmt::Display for MessageTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Message of {} bytes exceeds the limit of {} bytes",
+            self.size, self.max_size
+        )
+    }
+}
+
+impl std::error::Error for MessageTooLarge {}
+
+pub(crate) fn read_data<T: DeserializeOwned>(stream: &mut impl Read) -> Result<T> {
+    read_data_limited(stream, max_message_size())
+}
+
+/// Reads a frame, refusing messages larger than `max_size` bytes. A frame is
+/// made of `FRAME_MAGIC`, the payload size as an 8-byte little-endian integer
+/// and the bincode-serialized payload.
+pub(crate) fn read_data_limited<T: DeserializeOwned>(
+    stream: &mut impl Read,
+    max_size: usize,
+) -> Result<T> {
+    let mut magic = [0; FRAME_MAGIC.len()];
+    stream.read_exact(&mut magic)?;
+    if magic != FRAME_MAGIC {
+        bail!("Invalid frame");
+    }
+    let mut size_bytes = [0; 8];
+    stream.read_exact(&mut size_bytes)?;
+    let size = u64::from_le_bytes(size_bytes);
+    let Some(size) = usize::try_from(size).ok().filter(|size| *size <= max_size) else {
+        return Err(MessageTooLarge { size, max_size }.into());
+    };
+    let mut data_buffer = Vec::with_capacity(size.min(INITIAL_BUFFER_SIZE));
+    stream.take(size as u64).read_to_end(&mut data_buffer)?;
+    if data_buffer.len() < size {
+        bail!("Truncated frame");
+    }
+    let data = bincode::deserialize(&data_buffer)?;
+    Ok(data)
+}
+
+pub(crate) fn write_data<T: Serialize>(stream: &mut impl Write, data: T) -> Result<()> {
+    let data_bytes = bincode::serialize(&data)?;
+    stream.write_all(&FRAME_MAGIC)?;
+    stream.write_all(&(data_bytes.len() as u64).to_le_bytes())?;
+    stream.write_all(&data_bytes)?;
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::{Cursor, Read};
+
+    use super::{read_data_limited, write_data, MessageTooLarge, FRAME_MAGIC};
+
+    /// Counts the bytes read from the inner reader
+    struct CountingReader<R> {
+        inner: R,
+        count: usize,
+    }
+
+    impl<R: Read> Read for CountingReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.count += n;
+            Ok(n)
+        }
+    }
+
+    fn frame_header(size: u64) -> Vec<u8> {
+        [&FRAME_MAGIC[..], &size.to_le_bytes()].concat()
+    }
+
+    #[test]
+    fn test_frames() {
+        let message = (42u32, "microchain".to_string());
+        let mut bytes = vec![];
+        write_data(&mut bytes, &message).unwrap();
+
+        let read_message: (u32, String) = read_data_limited(&mut Cursor::new(&bytes), 64).unwrap();
+        assert_eq!(read_message, message);
+
+        // the size is checked before the payload is read
+        let err = read_data_limited::<(u32, String)>(&mut Cursor::new(&bytes), 8).unwrap_err();
+        assert!(err.is::<MessageTooLarge>());
+
+        // frames must start with the magic bytes
+        bytes[0] ^= 1;
+        let err = read_data_limited::<(u32, String)>(&mut Cursor::new(&bytes), 64).unwrap_err();
+        assert!(!err.is::<MessageTooLarge>());
+    }
+
+    #[test]
+    fn test_oversized_frames() {
+        // a size prefix over the limit is rejected before reading the payload
+        let bytes = [frame_header(u64::MAX), vec![0; 16]].concat();
+        let mut reader = CountingReader {
+            inner: Cursor::new(&bytes),
+            count: 0,
+        };
+        let err = read_data_limited::<Vec<u8>>(&mut reader, 1 << 20).unwrap_err();
+        assert!(err.is::<MessageTooLarge>());
+        assert_eq!(reader.count, FRAME_MAGIC.len() + 8);
+
+        // a size prefix within the limit doesn't cause an allocation of that
+        // size upfront, so a peer can't make the receiver run out of memory
+        // without actually sending the bytes
+        let bytes = [frame_header(1 << 40), vec![0; 16]].concat();
+        let err = read_data_limited::<Vec<u8>>(&mut Cursor::new(&bytes), usize::MAX).unwrap_err();
+        assert!(!err.is::<MessageTooLarge>());
+    }
+}
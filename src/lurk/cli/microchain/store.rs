@@ -0,0 +1,362 @@
+use anyhow::{bail, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use rustc_hash::FxHashMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+};
+
+use crate::lurk::{big_num::field_elts_to_biguint, zstore::DIGEST_SIZE};
+
+use super::{super::proofs::OpaqueChainProof, ChainData, ChainState, F};
+
+const SNAPSHOT_FILE: &str = "snapshot";
+const SNAPSHOT_TMP_FILE: &str = "snapshot.tmp";
+const LOG_FILE: &str = "log";
+
+/// Size of the header of a log record: 8 bytes for the payload length followed
+/// by the 32 bytes of its SHA-256 checksum
+const RECORD_HEADER_SIZE: usize = 8 + 32;
+
+/// The full data of a microchain, as persisted on snapshots
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    id: [F; DIGEST_SIZE],
+    chain_data: ChainData,
+}
+
+/// An accepted transition, as appended to the log of a microchain
+#[derive(Serialize, Deserialize)]
+pub(crate) struct TransitionRecord {
+    /// The number of transitions that preceded this one
+    pub(crate) height: usize,
+    pub(crate) proof: OpaqueChainProof,
+    pub(crate) state: ChainState,
+}
+
+/// What had to be discarded while loading the persisted microchains
+#[derive(Default)]
+pub(crate) struct Recovery {
+    /// Chain directories without a snapshot, left by chain creations that
+    /// didn't complete
+    pub(crate) skipped: Vec<Utf8PathBuf>,
+    /// Logs that ended with an incomplete record, along with the number of
+    /// bytes truncated from them
+    pub(crate) truncated: Vec<(Utf8PathBuf, usize)>,
+}
+
+impl Recovery {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.skipped.is_empty() && self.truncated.is_empty()
+    }
+}
+
+/// Persists microchains on the file system so they survive server restarts.
+///
+/// Each microchain has its own directory, which contains a snapshot of the full
+/// chain data and an append-only log with the transitions accepted after that
+/// snapshot. Snapshots are replaced atomically and log records are checksummed,
+/// so a crash in the middle of a write can only lose the transition that was
+/// being persisted, which is discarded when the chains are loaded.
+pub(crate) struct ChainStore {
+    dir: Utf8PathBuf,
+    /// The number of transitions between periodic snapshots, which bounds the
+    /// number of records in a log
+    snapshot_interval: usize,
+}
+
+impl ChainStore {
+    pub(crate) fn new(dir: Utf8PathBuf, snapshot_interval: usize) -> Result<Self> {
+        if snapshot_interval == 0 {
+            bail!("Snapshot interval must be positive");
+        }
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            snapshot_interval,
+        })
+    }
+
+    fn chain_dir(&self, id: &[F; DIGEST_SIZE]) -> Utf8PathBuf {
+        self.dir.join(format!("{:x}", field_elts_to_biguint(id)))
+    }
+
+    /// Loads every persisted microchain, replaying the transitions from their
+    /// logs on top of their latest snapshots and checkpointing them according
+    /// to their retention policies. Also returns what was discarded in the
+    /// process, for the caller to report.
+    pub(crate) fn load(&self) -> Result<(FxHashMap<[F; DIGEST_SIZE], ChainData>, Recovery)> {
+        let mut chains = FxHashMap::default();
+        let mut recovery = Recovery::default();
+        for entry in self.dir.read_dir_utf8()? {
+            let chain_dir = entry?.into_path();
+            if !chain_dir.is_dir() {
+                continue;
+            }
+            let snapshot_path = chain_dir.join(SNAPSHOT_FILE);
+            if !snapshot_path.exists() {
+                // the server stopped before the chain creation was completed
+                recovery.skipped.push(chain_dir);
+                continue;
+            }
+            let Snapshot { id, mut chain_data } =
+                bincode::deserialize(&std::fs::read(&snapshot_path)?)?;
+            let log_path = chain_dir.join(LOG_FILE);
+            let (records, discarded) = read_log::<TransitionRecord>(&log_path)?;
+            if discarded > 0 {
+                recovery.truncated.push((log_path, discarded));
+            }
+            let mut checkpointed = false;
+            for record in records {
+                let TransitionRecord {
+                    height,
+                    proof,
+                    state,
+                } = record;
+                let current_height = chain_data.height();
+                if height < current_height {
+                    // already covered by the snapshot
+                    continue;
+                }
+                if height > current_height {
+                    bail!("Missing transitions for {chain_dir}: expected height {current_height} but found {height}");
+                }
+                chain_data.proofs.push(proof);
+                chain_data.state = state;
+                // the retention policy is applied as when the transition was
+                // accepted, in case the server stopped before snapshotting
+                checkpointed |= chain_data.maybe_checkpoint();
+            }
+            if checkpointed {
+                // the pruned proofs only go away from the disk with a new snapshot
+                self.snapshot(&id, &chain_data)?;
+            }
+            chains.insert(id, chain_data);
+        }
+        Ok((chains, recovery))
+    }
+
+    /// Persists a new microchain
+    pub(crate) fn create(&self, id: &[F; DIGEST_SIZE], chain_data: &ChainData) -> Result<()> {
+        let chain_dir = self.chain_dir(id);
+        std::fs::create_dir_all(&chain_dir)?;
+        self.write_snapshot(&chain_dir, id, chain_data)
+    }
+
+    /// Durably appends a transition to the log of a microchain. This must
+    /// succeed before the transition is reflected in memory.
+    pub(crate) fn append(&self, id: &[F; DIGEST_SIZE], record: &TransitionRecord) -> Result<()> {
+        let log_path = self.chain_dir(id).join(LOG_FILE);
+        let mut log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_path)?;
+        log.write_all(&encode_record(record)?)?;
+        log.sync_data()?;
+        Ok(())
+    }
+
+    /// Writes a new snapshot for a microchain if its height is a multiple of the
+    /// snapshot interval. Snapshots may also happen in between, on checkpoints,
+    /// so the log never holds more than that many records. Must be called after
+    /// the chain data has been updated.
+    pub(crate) fn maybe_snapshot(
+        &self,
+        id: &[F; DIGEST_SIZE],
+        chain_data: &ChainData,
+    ) -> Result<()> {
+        if chain_data.height() % self.snapshot_interval != 0 {
+            return Ok(());
+        }
+        self.snapshot(id, chain_data)
+    }
+
+    /// Writes a new snapshot for a microchain and clears its log
+    pub(crate) fn snapshot(&self, id: &[F; DIGEST_SIZE], chain_data: &ChainData) -> Result<()> {
+        let chain_dir = self.chain_dir(id);
+        self.write_snapshot(&chain_dir, id, chain_data)?;
+        // the records in the log are now redundant. if the server stops before
+        // truncation, they will be skipped on replay because of their heights
+        File::create(chain_dir.join(LOG_FILE))?.sync_all()?;
+        Ok(())
+    }
+
+    /// Replaces the snapshot of a microchain atomically, by writing a temporary
+    /// file first and then renaming it
+    fn write_snapshot(
+        &self,
+        chain_dir: &Utf8Path,
+        id: &[F; DIGEST_SIZE],
+        chain_data: &ChainData,
+    ) -> Result<()> {
+        let tmp_path = chain_dir.join(SNAPSHOT_TMP_FILE);
+        let snapshot = SnapshotRef { id, chain_data };
+        let mut tmp_file = File::create(&tmp_path)?;
+        tmp_file.write_all(&bincode::serialize(&snapshot)?)?;
+        tmp_file.sync_all()?;
+        std::fs::rename(tmp_path, chain_dir.join(SNAPSHOT_FILE))?;
+        Ok(())
+    }
+}
+
+/// Borrowed version of `Snapshot`, to avoid cloning the chain data on writes
+#[derive(Serialize)]
+struct SnapshotRef<'a> {
+    id: &'a [F; DIGEST_SIZE],
+    chain_data: &'a ChainData,
+}
+
+fn encode_record<T: Serialize>(record: &T) -> Result<Vec<u8>> {
+    let payload = bincode::serialize(record)?;
+    let mut bytes = Vec::with_capacity(RECORD_HEADER_SIZE + payload.len());
+    bytes.extend((payload.len() as u64).to_le_bytes());
+    bytes.extend(Sha256::digest(&payload));
+    bytes.extend(payload);
+    Ok(bytes)
+}
+
+/// Tries to decode a record from the beginning of `bytes`, returning it along
+/// with the number of bytes it spans. Returns `None` if the record is incomplete
+/// or corrupted.
+fn decode_record<T: DeserializeOwned>(bytes: &[u8]) -> Option<(T, usize)> {
+    let (size_bytes, rest) = bytes.split_at_checked(8)?;
+    let (checksum, rest) = rest.split_at_checked(32)?;
+    let size = u64::from_le_bytes(size_bytes.try_into().unwrap());
+    let payload = rest.get(..usize::try_from(size).ok()?)?;
+    if Sha256::digest(payload).as_slice() != checksum {
+        return None;
+    }
+    let record = bincode::deserialize(payload).ok()?;
+    Some((record, RECORD_HEADER_SIZE + payload.len()))
+}
+
+/// Reads the records of a log file. If the log ends with a partially written
+/// record, the file is truncated right after the last valid one and the number
+/// of discarded bytes is returned along with the records.
+fn read_log<T: DeserializeOwned>(path: &Utf8Path) -> Result<(Vec<T>, usize)> {
+    if !path.exists() {
+        return Ok((vec![], 0));
+    }
+    let bytes = std::fs::read(path)?;
+    let mut records = vec![];
+    let mut offset = 0;
+    while let Some((record, size)) = decode_record(&bytes[offset..]) {
+        records.push(record);
+        offset += size;
+    }
+    let discarded = bytes.len() - offset;
+    if discarded > 0 {
+        let log = OpenOptions::new().write(true).open(path)?;
+        log.set_len(offset as u64)?;
+        log.sync_all()?;
+    }
+    Ok((records, discarded))
+}
+
+#[cfg(test)]
+mod test {
+    use camino::Utf8PathBuf;
+    use p3_field::AbstractField;
+    use std::{
+        fs::{File, OpenOptions},
+        io::Write,
+    };
+    use tempfile::tempdir;
+
+    use crate::lurk::{
+        cli::{lurk_data::LurkData, proofs::OpaqueChainProof},
+        verifier::CryptoProof,
+        zstore::{lurk_zstore, ZPtr, DIGEST_SIZE},
+    };
+
+    use super::{
+        super::{CallableData, ChainData, ChainState, Retention, F},
+        encode_record, read_log, ChainStore, TransitionRecord, LOG_FILE,
+    };
+
+    #[test]
+    fn test_log_recovery() {
+        let dir = tempdir().unwrap();
+        let path = Utf8PathBuf::try_from(dir.path().join("log")).unwrap();
+        let mut log = File::create(&path).unwrap();
+        for i in 0..3u64 {
+            log.write_all(&encode_record(&(i, format!("record {i}"))).unwrap())
+                .unwrap();
+        }
+        // simulate a crash in the middle of a write
+        let partial = encode_record(&(3u64, "record 3".to_string())).unwrap();
+        log.write_all(&partial[..partial.len() - 1]).unwrap();
+        drop(log);
+
+        let (records, discarded) = read_log::<(u64, String)>(&path).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(discarded, partial.len() - 1);
+        assert_eq!(records[2], (2, "record 2".to_string()));
+
+        // the incomplete record is gone and new records can be appended
+        let mut log = OpenOptions::new().append(true).open(&path).unwrap();
+        log.write_all(&encode_record(&(3u64, "record 3".to_string())).unwrap())
+            .unwrap();
+        drop(log);
+        let (records, discarded) = read_log::<(u64, String)>(&path).unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(discarded, 0);
+    }
+
+    #[test]
+    fn test_replay_checkpoints() {
+        let zstore = lurk_zstore();
+        let nil = *zstore.nil();
+        let state = ChainState {
+            chain_result: LurkData::new(nil, &zstore),
+            callable_data: CallableData::Fun(LurkData::new(nil, &zstore)),
+        };
+        let id = [F::zero(); DIGEST_SIZE];
+        let chain_data = ChainData {
+            genesis: ([F::one(); DIGEST_SIZE], state.clone()),
+            proofs: vec![],
+            retention: Retention::Checkpoint(2),
+            checkpoint: None,
+            owner: None,
+            state: state.clone(),
+        };
+
+        let dir = tempdir().unwrap();
+        let store_dir = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        let store = ChainStore::new(store_dir, 100).unwrap();
+        store.create(&id, &chain_data).unwrap();
+        // the server stopped without checkpointing the persisted transitions
+        for height in 0..5 {
+            let proof = OpaqueChainProof {
+                crypto_proof: CryptoProof::empty(),
+                call_args: nil,
+                next_chain_result: ZPtr::u64(height as u64),
+                next_callable: nil,
+            };
+            let record = TransitionRecord {
+                height,
+                proof,
+                state: state.clone(),
+            };
+            store.append(&id, &record).unwrap();
+        }
+
+        for _ in 0..2 {
+            let (chains, recovery) = store.load().unwrap();
+            assert!(recovery.is_empty());
+            let chain_data = &chains[&id];
+            assert_eq!(chain_data.height(), 5);
+            assert_eq!(chain_data.proofs.len(), 1);
+            let checkpoint = chain_data.checkpoint.as_ref().unwrap();
+            assert_eq!(checkpoint.height, 4);
+            assert_eq!(checkpoint.chain_result, ZPtr::u64(3));
+        }
+
+        // the pruned proofs are gone from the disk
+        let log_path = store.chain_dir(&id).join(LOG_FILE);
+        let (records, _) = read_log::<TransitionRecord>(&log_path).unwrap();
+        assert!(records.is_empty());
+    }
+}
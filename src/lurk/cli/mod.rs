@@ -1,23 +1,29 @@
 mod comm_data;
 mod config;
 mod debug;
+mod json;
 mod lurk_data;
 mod meta;
 mod microchain;
 mod paths;
+mod proof_store;
 mod proofs;
 mod rdg;
 pub mod repl;
 #[cfg(test)]
 mod tests;
-mod zdag;
+mod verify;
 
 use anyhow::{bail, Result};
 use camino::Utf8PathBuf;
 use clap::{Args, Parser, Subcommand};
 use config::{set_config, Config};
+use json::OutputFormat;
 use microchain::MicrochainArgs;
+use paths::current_dir;
+use proof_store::ProofsArgs;
 use repl::Repl;
+use verify::VerifyArgs;
 
 #[derive(Parser, Debug)]
 #[clap(version)]
@@ -32,8 +38,14 @@ enum Command {
     Repl(ReplArgs),
     /// Loads a file, processing forms sequentially ("load" can be elided)
     Load(LoadArgs),
-    /// Starts the microchain server
+    /// Evaluates expressions in order, failing on reduction errors
+    Eval(EvalArgs),
+    /// Starts the microchain server or moves microchains in and out of one
     Microchain(MicrochainArgs),
+    /// Verifies a proof file without entering the REPL
+    Verify(VerifyArgs),
+    /// Lists, tags and garbage-collects persisted proofs
+    Proofs(ProofsArgs),
 }
 
 #[derive(Args, Debug)]
@@ -59,9 +71,18 @@ struct LoadArgs {
     #[arg(long)]
     prove: bool,
 
+    /// Flag to prove all reductions, writing an index of the proof keys to a
+    /// file with the ".proofs" extension beside the loaded file
+    #[arg(long, conflicts_with = "prove")]
+    prove_all: bool,
+
     /// Flag to load the file in demo mode
     #[arg(long)]
     demo: bool,
+
+    /// The format in which results are printed
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
 }
 
 #[derive(Parser, Debug)]
@@ -72,12 +93,37 @@ struct LoadCli {
     #[arg(long)]
     prove: bool,
 
+    #[arg(long, conflicts_with = "prove")]
+    prove_all: bool,
+
     #[arg(long)]
     demo: bool,
+
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+}
+
+#[derive(Args, Debug)]
+struct EvalArgs {
+    /// The expressions to be evaluated
+    #[clap(value_parser, required_unless_present = "file")]
+    expr: Option<String>,
+
+    /// A file with the expressions to be evaluated
+    #[arg(short, long, value_parser, conflicts_with = "expr")]
+    file: Option<Utf8PathBuf>,
+
+    /// Flag to prove the last reduction
+    #[arg(long)]
+    prove: bool,
+
+    /// The format in which results are printed
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
 }
 
 fn parse_filename(file: &str) -> Result<Utf8PathBuf> {
-    if ["help", "microchain"].contains(&file) {
+    if ["help", "microchain", "verify", "proofs", "eval"].contains(&file) {
         bail!("Invalid file name");
     }
     Ok(file.into())
@@ -95,12 +141,16 @@ impl LoadArgs {
         let Self {
             lurk_file,
             prove,
+            prove_all,
             demo,
+            output,
         } = self;
         LoadCli {
             lurk_file,
             prove,
+            prove_all,
             demo,
+            output,
         }
     }
 }
@@ -110,7 +160,10 @@ impl Cli {
         match self.command {
             Command::Repl(repl_args) => repl_args.into_cli().run(),
             Command::Load(load_args) => load_args.into_cli().run(),
+            Command::Eval(eval_args) => eval_args.run(),
             Command::Microchain(microchain_args) => microchain_args.run(),
+            Command::Verify(verify_args) => verify_args.run(),
+            Command::Proofs(proofs_args) => proofs_args.run(),
         }
     }
 }
@@ -128,6 +181,10 @@ impl ReplCli {
 impl LoadCli {
     fn run(&self) -> Result<()> {
         let mut repl = Repl::new_native();
+        repl.output_format = self.output;
+        if self.prove_all {
+            return repl.load_file_and_prove_all(&self.lurk_file, self.demo);
+        }
         repl.load_file(&self.lurk_file, self.demo)?;
         if self.prove {
             repl.prove_last_reduction()?;
@@ -136,6 +193,25 @@ impl LoadCli {
     }
 }
 
+impl EvalArgs {
+    fn run(&self) -> Result<()> {
+        let mut repl = Repl::new_native();
+        repl.output_format = self.output;
+        if let Some(file) = &self.file {
+            let Some(dir) = file.parent() else {
+                bail!("Can't get the parent of {file}");
+            };
+            repl.eval_source(&std::fs::read_to_string(file)?, dir)?;
+        } else if let Some(expr) = &self.expr {
+            repl.eval_source(expr, &current_dir()?)?;
+        }
+        if self.prove {
+            repl.prove_last_reduction()?;
+        }
+        Ok(())
+    }
+}
+
 pub fn run() -> Result<()> {
     set_config(Config::default());
     if let Ok(cli) = Cli::try_parse() {
@@ -0,0 +1,386 @@
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::BufReader,
+    time::{Duration, SystemTime},
+};
+
+use crate::lurk::{
+    verifier::{get_lang_verifier_version, CachedProofHeader},
+    zstore::lurk_zstore,
+};
+
+use super::paths::{proof_tags_file, proofs_dir};
+
+/// Claims longer than this many characters are truncated when listed
+const MAX_CLAIM_LENGTH: usize = 64;
+
+/// A summary of a proof persisted in the proofs directory
+pub(crate) struct ProofEntry {
+    pub(crate) key: String,
+    /// The formatted expression and result of the claim, if the proof could be
+    /// deserialized
+    pub(crate) claim: Option<(String, String)>,
+    /// The ID of the `Lang` the proof was created with, if the proof could be
+    /// deserialized
+    pub(crate) lang_id: Option<String>,
+    /// The verifier version the proof was created for, if the proof could be
+    /// deserialized
+    pub(crate) verifier_version: Option<String>,
+    /// Size of the proof file, in bytes
+    pub(crate) size: u64,
+    pub(crate) created: SystemTime,
+    pub(crate) tags: Vec<String>,
+}
+
+impl ProofEntry {
+    /// Whether the proof was created for another version of the verifier of its
+    /// `Lang`. Proofs that can't be deserialized or whose `Lang`s haven't been
+    /// seen by this process aren't considered outdated since the versions they
+    /// should have are unknown.
+    pub(crate) fn is_outdated(&self) -> bool {
+        let (Some(lang_id), Some(verifier_version)) = (&self.lang_id, &self.verifier_version)
+        else {
+            return false;
+        };
+        get_lang_verifier_version(lang_id).is_some_and(|current| &current != verifier_version)
+    }
+
+    /// Whether the proof header could be deserialized
+    #[inline]
+    fn is_readable(&self) -> bool {
+        self.verifier_version.is_some()
+    }
+
+    fn age(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.created)
+            .unwrap_or_default()
+    }
+}
+
+/// User-assigned aliases for proof keys, persisted in `proof_tags_file()`
+#[derive(Default)]
+pub(crate) struct ProofTags(BTreeMap<String, String>);
+
+impl ProofTags {
+    pub(crate) fn load() -> Result<Self> {
+        let path = proof_tags_file()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(Self(bincode::deserialize(&std::fs::read(path)?)?))
+    }
+
+    fn save(&self) -> Result<()> {
+        std::fs::write(proof_tags_file()?, bincode::serialize(&self.0)?)?;
+        Ok(())
+    }
+
+    /// Points a tag to a proof key, replacing its previous target
+    pub(crate) fn tag(tag: String, key: String) -> Result<()> {
+        if !is_file_name(&tag) {
+            bail!("Invalid tag {tag}: tags can't be empty nor contain path separators or \"..\"");
+        }
+        if !is_file_name(&key) || !proofs_dir()?.join(&key).exists() {
+            bail!("Proof not found");
+        }
+        let mut tags = Self::load()?;
+        tags.0.insert(tag, key);
+        tags.save()
+    }
+
+    pub(crate) fn untag(tag: &str) -> Result<()> {
+        let mut tags = Self::load()?;
+        if tags.0.remove(tag).is_none() {
+            bail!("Unknown tag {tag}");
+        }
+        tags.save()
+    }
+
+    /// Returns the proof key for a tag, or the input itself if it's not a tag
+    pub(crate) fn resolve(key_or_tag: &str) -> Result<String> {
+        let tags = Self::load()?;
+        Ok(tags
+            .0
+            .get(key_or_tag)
+            .cloned()
+            .unwrap_or_else(|| key_or_tag.to_string()))
+    }
+
+    fn tags_for(&self, key: &str) -> Vec<String> {
+        self.0
+            .iter()
+            .filter(|(_, k)| *k == key)
+            .map(|(tag, _)| tag.clone())
+            .collect()
+    }
+}
+
+/// Whether `name` can only refer to a file right inside a directory when joined
+/// to its path, which is required from proof keys and from the tags that stand
+/// in for them
+fn is_file_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && !name.contains("..")
+        && !name
+            .chars()
+            .any(|c| c == '/' || c == '\\' || std::path::is_separator(c) || c.is_control())
+}
+
+fn truncate(mut s: String) -> String {
+    if let Some((idx, _)) = s.char_indices().nth(MAX_CLAIM_LENGTH) {
+        s.truncate(idx);
+        s.push_str("...");
+    }
+    s
+}
+
+/// Lists the proofs in the proofs directory, from the oldest to the newest. Only
+/// the headers of the proofs are read and their claims are formatted with the
+/// same ZStore.
+pub(crate) fn list_proofs() -> Result<Vec<ProofEntry>> {
+    let tags = ProofTags::load()?;
+    let mut zstore = lurk_zstore();
+    let mut entries = vec![];
+    for dir_entry in proofs_dir()?.read_dir_utf8()? {
+        let dir_entry = dir_entry?;
+        let metadata = dir_entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let key = dir_entry.file_name().to_string();
+        let reader = BufReader::new(File::open(dir_entry.path())?);
+        let (claim, lang_id, verifier_version) =
+            match bincode::deserialize_from::<_, CachedProofHeader>(reader) {
+                Ok(header) => {
+                    let CachedProofHeader {
+                        expr,
+                        result,
+                        zdag,
+                        lang_id,
+                        verifier_version,
+                        ..
+                    } = header;
+                    zdag.populate_zstore(&mut zstore);
+                    let claim = (truncate(zstore.fmt(&expr)), truncate(zstore.fmt(&result)));
+                    (Some(claim), Some(lang_id), Some(verifier_version))
+                }
+                Err(_) => (None, None, None),
+            };
+        let tags = tags.tags_for(&key);
+        entries.push(ProofEntry {
+            key,
+            claim,
+            lang_id,
+            verifier_version,
+            size: metadata.len(),
+            created: metadata.created().or_else(|_| metadata.modified())?,
+            tags,
+        });
+    }
+    entries.sort_by_key(|entry| entry.created);
+    Ok(entries)
+}
+
+fn fmt_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..60 => format!("{secs}s ago"),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
+
+pub(crate) fn fmt_proofs(entries: &[ProofEntry]) -> String {
+    if entries.is_empty() {
+        return "No proofs found\n".into();
+    }
+    let mut out = String::new();
+    for entry in entries {
+        out.push_str(&format!("{}\n", entry.key));
+        if !entry.tags.is_empty() {
+            out.push_str(&format!("  Tags: {}\n", entry.tags.join(", ")));
+        }
+        match &entry.claim {
+            Some((expr, result)) => out.push_str(&format!("  Claim: {expr} => {result}\n")),
+            None => out.push_str("  Claim: <unreadable proof>\n"),
+        }
+        let verifier_version = entry.verifier_version.as_deref().unwrap_or("unknown");
+        if entry.is_outdated() {
+            out.push_str(&format!(
+                "  Verifier version: {verifier_version} (outdated)\n"
+            ));
+        } else {
+            out.push_str(&format!("  Verifier version: {verifier_version}\n"));
+        }
+        out.push_str(&format!("  Size: {} bytes\n", entry.size));
+        out.push_str(&format!("  Created: {}\n", fmt_age(entry.age())));
+    }
+    out
+}
+
+/// Removes the proofs that are outdated, if `outdated` is set, or older than
+/// `max_age`, if provided. Tags pointing to removed proofs are removed too.
+/// Files that can't be deserialized as proofs are never removed. Returns the
+/// keys of the removed proofs and of the unreadable files.
+pub(crate) fn gc_proofs(
+    outdated: bool,
+    max_age: Option<Duration>,
+) -> Result<(Vec<String>, Vec<String>)> {
+    let dir = proofs_dir()?;
+    let mut removed = vec![];
+    let mut unreadable = vec![];
+    for entry in list_proofs()? {
+        if !entry.is_readable() {
+            unreadable.push(entry.key);
+            continue;
+        }
+        let too_old = max_age.is_some_and(|max_age| entry.age() > max_age);
+        if (outdated && entry.is_outdated()) || too_old {
+            std::fs::remove_file(dir.join(&entry.key))?;
+            removed.push(entry.key);
+        }
+    }
+    let mut tags = ProofTags::load()?;
+    let num_tags = tags.0.len();
+    tags.0.retain(|_, key| !removed.contains(key));
+    if tags.0.len() != num_tags {
+        tags.save()?;
+    }
+    Ok((removed, unreadable))
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct ProofsArgs {
+    #[clap(subcommand)]
+    command: ProofsCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum ProofsCommand {
+    /// Lists the persisted proofs with their claims, verifier versions, sizes
+    /// and ages
+    List,
+    /// Assigns a tag to a proof key, which can then be used in place of the key
+    Tag {
+        #[clap(value_parser)]
+        tag: String,
+        #[clap(value_parser)]
+        key: String,
+    },
+    /// Removes a tag
+    Untag {
+        #[clap(value_parser)]
+        tag: String,
+    },
+    /// Removes outdated or old proofs
+    Gc(GcArgs),
+}
+
+#[derive(Args, Debug)]
+#[group(required = true, multiple = true)]
+struct GcArgs {
+    /// Removes proofs created for other verifier versions
+    #[clap(long)]
+    outdated: bool,
+
+    /// Removes proofs older than this many days
+    #[clap(long, value_parser)]
+    older_than: Option<u64>,
+}
+
+impl ProofsArgs {
+    pub(crate) fn run(self) -> Result<()> {
+        match self.command {
+            ProofsCommand::List => print!("{}", fmt_proofs(&list_proofs()?)),
+            ProofsCommand::Tag { tag, key } => {
+                ProofTags::tag(tag.clone(), key.clone())?;
+                println!("Tagged proof {key} as {tag}");
+            }
+            ProofsCommand::Untag { tag } => {
+                ProofTags::untag(&tag)?;
+                println!("Removed tag {tag}");
+            }
+            ProofsCommand::Gc(GcArgs {
+                outdated,
+                older_than,
+            }) => {
+                let max_age = older_than.map(|days| Duration::from_secs(days * 86400));
+                let (removed, unreadable) = gc_proofs(outdated, max_age)?;
+                println!("Removed {} proof(s) from {}", removed.len(), proofs_dir()?);
+                if !unreadable.is_empty() {
+                    println!("Kept {} unreadable file(s):", unreadable.len());
+                    for key in unreadable {
+                        println!("  {key}");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::SystemTime;
+
+    use p3_baby_bear::BabyBear;
+
+    use crate::{
+        lair::chipset::NoChip,
+        lurk::{
+            lang::Lang,
+            verifier::{get_verifier_version, register_lang_verifier_version},
+        },
+    };
+
+    use super::{is_file_name, truncate, ProofEntry};
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("(+ 1 2)".to_string()), "(+ 1 2)");
+        let long = "a".repeat(100);
+        assert_eq!(truncate(long), format!("{}...", "a".repeat(64)));
+    }
+
+    #[test]
+    fn test_file_names() {
+        assert!(is_file_name("my-proof"));
+        assert!(is_file_name("2ae20412c6f4740f"));
+        for name in [
+            "", ".", "..", "../proof", "a/b", "a\\b", "/proof", "a..b", "a\nb",
+        ] {
+            assert!(!is_file_name(name));
+        }
+    }
+
+    #[test]
+    fn test_outdated() {
+        let native_id = Lang::<BabyBear, NoChip>::empty().id();
+        let entry = |lang_id: &str, verifier_version: Option<&str>| ProofEntry {
+            key: "key".into(),
+            claim: None,
+            lang_id: verifier_version.map(|_| lang_id.to_string()),
+            verifier_version: verifier_version.map(str::to_string),
+            size: 0,
+            created: SystemTime::now(),
+            tags: vec![],
+        };
+        assert!(!entry(&native_id, Some(get_verifier_version())).is_outdated());
+        assert!(entry(&native_id, Some("other")).is_outdated());
+        // proofs for other `Lang`s are checked against their own versions
+        assert!(!entry("custom", Some("custom version")).is_outdated());
+        register_lang_verifier_version("custom".into(), "custom version".into());
+        assert!(!entry("custom", Some("custom version")).is_outdated());
+        assert!(entry("custom", Some(get_verifier_version())).is_outdated());
+        // unreadable proofs have unknown versions, so they're kept by `gc_proofs`
+        let unreadable = entry(&native_id, None);
+        assert!(!unreadable.is_outdated());
+        assert!(!unreadable.is_readable());
+    }
+}
@@ -1,9 +1,10 @@
 use anyhow::{bail, Result};
 use camino::Utf8Path;
+use k256::ecdsa::SigningKey;
 use nom::sequence::delimited;
 use nom::Parser;
 use p3_baby_bear::BabyBear;
-use p3_field::{Field, PrimeField32};
+use p3_field::{AbstractField, Field, PrimeField32};
 use rustc_hash::{FxHashMap, FxHashSet};
 use rustyline::{
     error::ReadlineError,
@@ -11,17 +12,18 @@ use rustyline::{
     validate::{ValidationContext, ValidationResult, Validator},
     Completer, Editor, Helper, Highlighter, Hinter,
 };
+use serde_json::json;
 use sphinx_core::{
-    stark::{LocalProver, StarkGenericConfig},
-    utils::SphinxCoreOpts,
+    stark::{LocalProver, StarkGenericConfig, StarkMachine, StarkProvingKey, StarkVerifyingKey},
+    utils::{BabyBearPoseidon2, SphinxCoreOpts},
 };
-use std::{fmt::Debug, io::Write, marker::PhantomData};
+use std::{fmt::Debug, io::Write, marker::PhantomData, rc::Rc};
 
 use crate::{
     lair::{
         chipset::{Chipset, NoChip},
         execute::{DebugEntry, DebugEntryKind, QueryRecord, QueryResult, Shard},
-        lair_chip::LairMachineProgram,
+        lair_chip::{LairChip, LairMachineProgram},
         toplevel::Toplevel,
     },
     lurk::{
@@ -29,9 +31,10 @@ use crate::{
         chipset::LurkChip,
         cli::{
             debug::{FormattedDebugData, FormattedDebugEntry},
+            json::{to_json, OutputFormat},
             meta::{meta_cmds, MetaCmdsMap},
             paths::{current_dir, proofs_dir, repl_history},
-            proofs::{CachedProof, CryptoProof},
+            rdg::rand_digest,
         },
         eval_direct::build_lurk_toplevel,
         lang::Lang,
@@ -44,10 +47,36 @@ use crate::{
         symbol::Symbol,
         syntax::Syntax,
         tag::Tag,
-        zstore::{quote, ZPtr, ZStore, DIGEST_SIZE},
+        verifier::{register_lang_verifier_version, verifier_version, CachedProof, CryptoProof},
+        zstore::{quote, ZPtr, ZStore, DIGEST_SIZE, HASH3_SIZE},
     },
 };
 
+/// Prints human-readable output to stdout, or to stderr when the REPL prints
+/// JSON so that stdout only carries JSON objects
+macro_rules! text_println {
+    ($repl:expr, $($arg:tt)*) => {
+        if $repl.output_format == $crate::lurk::cli::json::OutputFormat::Json {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+/// Like `text_println`, without the new line
+macro_rules! text_print {
+    ($repl:expr, $($arg:tt)*) => {
+        if $repl.output_format == $crate::lurk::cli::json::OutputFormat::Json {
+            eprint!($($arg)*)
+        } else {
+            print!($($arg)*)
+        }
+    };
+}
+
+pub(crate) use {text_print, text_println};
+
 #[derive(Helper, Highlighter, Hinter, Completer)]
 struct InputValidator<F: Field> {
     state: StateRcCell,
@@ -123,28 +152,48 @@ impl FuncIndices {
 pub(crate) struct Repl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> {
     pub(crate) zstore: ZStore<F, C1>,
     pub(crate) queries: QueryRecord<F>,
-    pub(crate) toplevel: Toplevel<F, C1, C2>,
+    /// Shared so that a STARK machine can borrow it while the REPL is mutated
+    pub(crate) toplevel: Rc<Toplevel<F, C1, C2>>,
     func_indices: FuncIndices,
     pub(crate) env: ZPtr<F>,
     pub(crate) state: StateRcCell,
     pub(crate) meta_cmds: MetaCmdsMap<F, C1, C2>,
     pub(crate) lang_symbols: FxHashSet<Symbol>,
+    /// The content-addressed identifier of the `Lang`, as in `Lang::id`
+    pub(crate) lang_id: String,
+    /// The version of the circuit for the toplevel, as in `verifier_version`
+    pub(crate) verifier_version: String,
+    /// How reduction results are printed
+    pub(crate) output_format: OutputFormat,
+    /// The key used to own microchains and sign their transitions
+    pub(crate) microchain_key: Option<SigningKey>,
+    /// The latest state seen for each microchain, by ID, along with its height.
+    /// Transitions from these states can be signed without asking the server.
+    pub(crate) microchain_heads: FxHashMap<[F; DIGEST_SIZE], (ZPtr<F>, usize)>,
 }
 
 impl<C2: Chipset<BabyBear>> Repl<BabyBear, LurkChip, C2> {
     pub(crate) fn new(lang: Lang<BabyBear, C2>) -> Self {
+        let lang_id = lang.id();
         let (toplevel, mut zstore, lang_symbols) = build_lurk_toplevel(lang);
         let func_indices = FuncIndices::new(&toplevel);
+        let verifier_version = verifier_version(&toplevel);
+        register_lang_verifier_version(lang_id.clone(), verifier_version.clone());
         let env = zstore.intern_empty_env();
         Self {
             zstore,
             queries: QueryRecord::new(&toplevel),
-            toplevel,
+            toplevel: Rc::new(toplevel),
             func_indices,
             env,
             state: State::init_lurk_state().rccell(),
             meta_cmds: meta_cmds(),
             lang_symbols,
+            lang_id,
+            verifier_version,
+            output_format: OutputFormat::default(),
+            microchain_key: None,
+            microchain_heads: FxHashMap::default(),
         }
     }
 }
@@ -157,52 +206,221 @@ impl Repl<BabyBear, LurkChip, NoChip> {
     }
 }
 
+/// A STARK machine for the toplevel of a REPL, set up once in order to prove
+/// many reductions
+pub(crate) struct ReductionProver<'a, C1: Chipset<BabyBear>, C2: Chipset<BabyBear>> {
+    machine: StarkMachine<BabyBearPoseidon2, LairChip<'a, BabyBear, C1, C2>>,
+    pk: StarkProvingKey<BabyBearPoseidon2>,
+    vk: StarkVerifyingKey<BabyBearPoseidon2>,
+}
+
+impl<'a, C1: Chipset<BabyBear>, C2: Chipset<BabyBear>> ReductionProver<'a, C1, C2> {
+    pub(crate) fn new(toplevel: &'a Toplevel<BabyBear, C1, C2>) -> Self {
+        let machine = new_machine(toplevel);
+        let (pk, vk) = machine.setup(&LairMachineProgram);
+        Self { machine, pk, vk }
+    }
+
+    /// Proves the latest reduction of `repl` and persists the proof. Returns the
+    /// corresponding proof key. If `num_disclosed` is provided, the proof only
+    /// carries that many outermost environment bindings.
+    pub(crate) fn prove(
+        &self,
+        repl: &mut Repl<BabyBear, C1, C2>,
+        num_disclosed: Option<usize>,
+    ) -> Result<String> {
+        // make env DAG available so `IOProof` can carry it
+        repl.memoize_env_dag();
+        let proof_key = repl.proof_key(&repl.queries.public_values.clone(), num_disclosed)?;
+        repl.prove_queries(self, &repl.queries, &proof_key, num_disclosed)?;
+        Ok(proof_key)
+    }
+}
+
 impl<C1: Chipset<BabyBear>, C2: Chipset<BabyBear>> Repl<BabyBear, C1, C2> {
     /// Generates a STARK proof for the latest Lurk reduction, persists it and
     /// returns the corresponding proof key
     pub(crate) fn prove_last_reduction(&mut self) -> Result<String> {
-        // make env DAG available so `IOProof` can carry it
-        self.memoize_env_dag();
-        let Some(public_values) = self.queries.public_values.as_ref() else {
+        let toplevel = self.toplevel.clone();
+        ReductionProver::new(&toplevel).prove(self, None)
+    }
+
+    /// Reduces `expr` and proves it, but the persisted proof only discloses the
+    /// environment bindings for the variables in `disclosed`. To that end, the
+    /// reduction happens in a rearrangement of the REPL's environment with the
+    /// disclosed bindings on top of the hidden ones, which are salted with a
+    /// random binding at the bottom. The proof thus carries the spine of the
+    /// disclosed bindings and only the digest of a salted environment for the
+    /// hidden ones.
+    pub(crate) fn prove_disclosing(
+        &mut self,
+        expr: &ZPtr<BabyBear>,
+        disclosed: &[ZPtr<BabyBear>],
+    ) -> Result<String> {
+        let env = self.disclosure_env(disclosed)?;
+        self.handle_non_meta(expr, Some(env))?;
+        let toplevel = self.toplevel.clone();
+        ReductionProver::new(&toplevel).prove(self, Some(disclosed.len()))
+    }
+
+    /// Rearranges the visible bindings of the REPL's environment so that the
+    /// ones for `disclosed` are the outermost, in the same order. If there are
+    /// bindings left to hide, a binding of a random commitment is added below
+    /// them so their digest can't be brute-forced.
+    fn disclosure_env(&mut self, disclosed: &[ZPtr<BabyBear>]) -> Result<ZPtr<BabyBear>> {
+        let mut seen = FxHashSet::default();
+        let mut hidden = vec![];
+        let mut disclosed_bindings = FxHashMap::default();
+        for (var, val) in self.zstore.fetch_env(&self.env) {
+            // shadowed bindings aren't visible, so they're dropped
+            if !seen.insert(*var) {
+                continue;
+            }
+            if disclosed.contains(var) {
+                disclosed_bindings.insert(*var, *val);
+            } else {
+                hidden.push((*var, *val));
+            }
+        }
+        let mut env = self.zstore.intern_empty_env();
+        if !hidden.is_empty() {
+            let salt = self.zstore.intern_comm(rand_digest());
+            env = self.zstore.intern_env(salt, salt, env);
+        }
+        for (var, val) in hidden.into_iter().rev() {
+            env = self.zstore.intern_env(var, val, env);
+        }
+        for var in disclosed.iter().rev() {
+            let Some(val) = disclosed_bindings.remove(var) else {
+                bail!("{} isn't bound or is disclosed twice", self.fmt(var));
+            };
+            env = self.zstore.intern_env(*var, val, env);
+        }
+        Ok(env)
+    }
+
+    /// Reduces and proves each expression in `exprs` as soon as its reduction
+    /// is over, reusing the same machine setup for all of them. Returns the
+    /// proof keys in the same order. The latest reduction is restored afterwards
+    /// so it can still be proved on its own.
+    pub(crate) fn prove_batch(&mut self, exprs: &[ZPtr<BabyBear>]) -> Result<Vec<String>> {
+        let toplevel = self.toplevel.clone();
+        let prover = ReductionProver::new(&toplevel);
+        let last_queries = self.queries.clone();
+        let mut prove_all = || -> Result<Vec<String>> {
+            let mut proof_keys = Vec::with_capacity(exprs.len());
+            for expr in exprs {
+                self.handle_non_meta(expr, None)?;
+                let proof_key = prover.prove(self, None)?;
+                text_print!(self, "{}", self.proof_index_entry(expr, &proof_key));
+                proof_keys.push(proof_key);
+            }
+            Ok(proof_keys)
+        };
+        let proof_keys = prove_all();
+        // keep the inverse queries gathered in the meantime so commitments
+        // aren't lost
+        let inv_func_queries = std::mem::take(&mut self.queries.inv_func_queries);
+        self.queries = last_queries;
+        self.queries.inv_func_queries = inv_func_queries;
+        proof_keys
+    }
+
+    /// Loads a file and proves each of its reductions as soon as it's over,
+    /// reusing the same machine setup for all of them. An index with their
+    /// proof keys is written to a file with the ".proofs" extension beside it.
+    pub(crate) fn load_file_and_prove_all(
+        &mut self,
+        file_path: &Utf8Path,
+        demo: bool,
+    ) -> Result<()> {
+        let toplevel = self.toplevel.clone();
+        let prover = ReductionProver::new(&toplevel);
+        let mut index = String::new();
+        self.load_file_with(file_path, demo, &mut |repl, expr| {
+            let proof_key = prover.prove(repl, None)?;
+            index.push_str(&repl.proof_index_entry(expr, &proof_key));
+            Ok(())
+        })?;
+        let index_path = file_path.with_extension("proofs");
+        std::fs::write(&index_path, index)?;
+        text_println!(self, "Proof index written to {index_path}");
+        Ok(())
+    }
+
+    /// The key of the proof for a reduction with the given public values. Proofs
+    /// that only disclose some environment bindings have different keys for
+    /// different numbers of disclosed bindings.
+    fn proof_key(
+        &mut self,
+        public_values: &Option<Vec<BabyBear>>,
+        num_disclosed: Option<usize>,
+    ) -> Result<String> {
+        let Some(public_values) = public_values else {
             bail!("No data found for latest computation");
         };
-        let proof_key_img: &[BabyBear; DIGEST_SIZE] = &self
+        let mut proof_key_img = self
             .zstore
             .hash3(public_values[..INPUT_SIZE].try_into().unwrap());
-        let proof_key = format!("{:x}", field_elts_to_biguint(proof_key_img));
-        let proof_path = proofs_dir()?.join(&proof_key);
-        let machine = new_machine(&self.toplevel);
-        let (pk, vk) = machine.setup(&LairMachineProgram);
+        if let Some(num_disclosed) = num_disclosed {
+            let mut preimg = [BabyBear::zero(); HASH3_SIZE];
+            preimg[..DIGEST_SIZE].copy_from_slice(&proof_key_img);
+            preimg[DIGEST_SIZE] = BabyBear::from_canonical_usize(num_disclosed + 1);
+            proof_key_img = self.zstore.hash3(preimg);
+        }
+        Ok(format!("{:x}", field_elts_to_biguint(&proof_key_img)))
+    }
+
+    /// Proves the reduction recorded in `queries` and persists the proof with
+    /// `proof_key`. A valid persisted proof is reused, only having its Lurk data
+    /// replaced according to `num_disclosed`, as in `CachedProof::new`.
+    fn prove_queries(
+        &self,
+        prover: &ReductionProver<'_, C1, C2>,
+        queries: &QueryRecord<BabyBear>,
+        proof_key: &str,
+        num_disclosed: Option<usize>,
+    ) -> Result<()> {
+        let ReductionProver { machine, pk, vk } = prover;
+        let public_values = queries.expect_public_values();
+        let proof_path = proofs_dir()?.join(proof_key);
         let challenger_p = &mut machine.config().challenger();
-        let must_prove = if !proof_path.exists() {
-            true
-        } else {
+        let mut valid_crypto_proof = None;
+        if proof_path.exists() {
             let cached_proof_bytes = std::fs::read(&proof_path)?;
+            // force an overwrite if deserialization or verification go wrong
             if let Ok(cached_proof) = bincode::deserialize::<CachedProof>(&cached_proof_bytes) {
+                let crypto_proof = cached_proof.crypto_proof.clone();
                 let machine_proof = cached_proof.into_machine_proof();
                 let challenger_v = &mut challenger_p.clone();
-                // force an overwrite if verification goes wrong
-                machine.verify(&vk, &machine_proof, challenger_v).is_err()
-            } else {
-                // force an overwrite if deserialization goes wrong
-                true
+                if machine.verify(vk, &machine_proof, challenger_v).is_ok() {
+                    valid_crypto_proof = Some(crypto_proof);
+                }
             }
-        };
-        if must_prove {
+        }
+        let crypto_proof = if let Some(crypto_proof) = valid_crypto_proof {
+            crypto_proof
+        } else {
             let challenger_v = &mut challenger_p.clone();
-            let shard = Shard::new(&self.queries);
+            let shard = Shard::new(queries);
             let opts = SphinxCoreOpts::default();
-            let machine_proof = machine.prove::<LocalProver<_, _>>(&pk, shard, challenger_p, opts);
+            let machine_proof = machine.prove::<LocalProver<_, _>>(pk, shard, challenger_p, opts);
             machine
-                .verify(&vk, &machine_proof, challenger_v)
+                .verify(vk, &machine_proof, challenger_v)
                 .expect("Proof verification failed");
-            let crypto_proof: CryptoProof = machine_proof.into();
-            let cached_proof = CachedProof::new(crypto_proof, public_values, &self.zstore);
-            let cached_proof_bytes = bincode::serialize(&cached_proof)?;
-            std::fs::write(proof_path, cached_proof_bytes)?;
-        }
-        println!("Proof key: \"{proof_key}\"");
-        Ok(proof_key)
+            CryptoProof::new(machine_proof, self.verifier_version.clone())
+        };
+        let cached_proof = CachedProof::new(
+            crypto_proof,
+            public_values,
+            num_disclosed,
+            self.lang_id.clone(),
+            &self.zstore,
+        );
+        let cached_proof_bytes = bincode::serialize(&cached_proof)?;
+        std::fs::write(proof_path, cached_proof_bytes)?;
+        text_println!(self, "Proof key: \"{proof_key}\"");
+        Ok(())
     }
 }
 
@@ -260,6 +478,18 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> Repl<F, C1, C2> {
         self.zstore.fmt_with_state(&self.state, zptr)
     }
 
+    #[inline]
+    pub(crate) fn to_json(&self, zptr: &ZPtr<F>) -> serde_json::Value {
+        to_json(&self.zstore, &self.state, zptr)
+    }
+
+    fn print_emitted(&self, zptr: &ZPtr<F>) {
+        match self.output_format {
+            OutputFormat::Text => println!("{}", self.fmt(zptr)),
+            OutputFormat::Json => println!("{}", json!({ "emitted": self.to_json(zptr) })),
+        }
+    }
+
     fn prepare_queries(&mut self) {
         self.queries.clean();
         let hashes3 = std::mem::take(&mut self.zstore.hashes3_diff);
@@ -450,7 +680,7 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> Repl<F, C1, C2> {
         self.queries.inv_func_queries = queries_tmp.inv_func_queries;
         for zptr in &emitted {
             self.memoize_dag(zptr.tag, &zptr.digest);
-            println!("{}", self.fmt(zptr));
+            self.print_emitted(zptr);
         }
         result_data.map(|data| (ZPtr::from_flat_data(&data), emitted))
     }
@@ -478,7 +708,7 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> Repl<F, C1, C2> {
             self.retrieve_inv_query_data_from_tmp_queries(queries_tmp);
             for zptr in &emitted {
                 self.memoize_dag(zptr.tag, &zptr.digest);
-                println!("{}", self.fmt(zptr));
+                self.print_emitted(zptr);
             }
         }
         result_data.map(|data| ZPtr::from_flat_data(&data))
@@ -493,11 +723,17 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> Repl<F, C1, C2> {
         let result = self.reduce_with_env(expr, &env)?;
         self.memoize_dag(result.tag, &result.digest);
         let iterations = self.queries.func_queries[self.func_indices.eval].len();
-        println!(
-            "[{}] => {}",
-            pretty_iterations_display(iterations),
-            self.fmt(&result)
-        );
+        match self.output_format {
+            OutputFormat::Text => println!(
+                "[{}] => {}",
+                pretty_iterations_display(iterations),
+                self.fmt(&result)
+            ),
+            OutputFormat::Json => {
+                let json = json!({ "iterations": iterations, "result": self.to_json(&result) });
+                println!("{json}");
+            }
+        }
         Ok(result)
     }
 
@@ -522,11 +758,11 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> Repl<F, C1, C2> {
                 } else {
                     bail!("Invalid meta command: {sym}")
                 }
-            },
+            }
             Syntax::Num(_, f) => self.zstore.intern_num(*f),
             Syntax::Char(_, c) => self.zstore.intern_char(*c),
             Syntax::U64(_, u) => self.zstore.intern_u64(*u),
-            Syntax::I64(..) => bail!("Transient error: Signed integers are not yet supported. Using `(- 0 x)` instead of `-x` might work as a temporary workaround."),
+            Syntax::I64(_, i) => self.zstore.intern_i64(*i),
             Syntax::BigNum(_, c) => self.zstore.intern_big_num(*c),
             Syntax::Comm(_, c) => self.zstore.intern_comm(*c),
             Syntax::String(_, s) => self.zstore.intern_string(s),
@@ -574,11 +810,17 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> Repl<F, C1, C2> {
         Ok(Some((offset, rest, zptr, meta)))
     }
 
+    /// Renders a line with the key of a proof and the expression it reduces
+    pub(crate) fn proof_index_entry(&self, expr: &ZPtr<F>, proof_key: &str) -> String {
+        format!("{proof_key} {}\n", self.fmt(expr))
+    }
+
     fn handle_form<'a>(
         &mut self,
         input: Span<'a>,
         file_dir: &Utf8Path,
         demo: bool,
+        on_reduction: &mut impl FnMut(&mut Self, &ZPtr<F>) -> Result<()>,
     ) -> Result<Option<Span<'a>>> {
         let Some((syntax_start, mut new_input, zptr, meta)) = self.process(input, file_dir)? else {
             return Ok(None);
@@ -589,41 +831,77 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> Repl<F, C1, C2> {
             let prompt_marker = &self.prompt_marker();
             if actual_syntax.contains('\n') {
                 // print the expression on a new line to avoid messing with the user's formatting
-                print!("{potential_commentaries}{prompt_marker}\n{actual_syntax}");
+                text_print!(
+                    self,
+                    "{potential_commentaries}{prompt_marker}\n{actual_syntax}"
+                );
             } else {
-                print!("{potential_commentaries}{prompt_marker}{actual_syntax}");
+                text_print!(
+                    self,
+                    "{potential_commentaries}{prompt_marker}{actual_syntax}"
+                );
             }
             std::io::stdout().flush()?;
+            std::io::stderr().flush()?;
             // wait for ENTER to be pressed
             std::io::stdin().read_line(&mut String::new())?;
             // ENTER already prints a new line so we can remove it from the start of incoming input
             new_input = new_input.trim_start_matches('\n').into();
         }
         if meta {
-            println!("{}", self.fmt(&zptr));
+            match self.output_format {
+                OutputFormat::Text => println!("{}", self.fmt(&zptr)),
+                OutputFormat::Json => println!("{}", json!({ "result": self.to_json(&zptr) })),
+            }
         } else {
             let result = self.handle_non_meta(&zptr, None)?;
             if result.tag == Tag::Err {
                 // error out when loading a file
                 bail!("Reduction error: {}", self.fmt(&result));
             }
+            on_reduction(self, &zptr)?;
         }
         Ok(Some(new_input))
     }
 
+    /// Evaluates the forms in `source` in order, erroring out on reduction
+    /// errors just like `load_file`
+    pub(crate) fn eval_source(&mut self, source: &str, dir: &Utf8Path) -> Result<()> {
+        let mut input = Span::new(source);
+        if parse::<F>(input, self.state.clone(), false)?.is_none() {
+            bail!("Missing expression to evaluate");
+        }
+        while let Some(new_input) = self.handle_form(input, dir, false, &mut |_, _| Ok(()))? {
+            input = new_input;
+        }
+        Ok(())
+    }
+
+    #[inline]
     pub(crate) fn load_file(&mut self, file_path: &Utf8Path, demo: bool) -> Result<()> {
+        self.load_file_with(file_path, demo, &mut |_, _| Ok(()))
+    }
+
+    /// Loads a file, calling `on_reduction` after each successful reduction of a
+    /// non-meta form with the reduced expression
+    fn load_file_with(
+        &mut self,
+        file_path: &Utf8Path,
+        demo: bool,
+        on_reduction: &mut impl FnMut(&mut Self, &ZPtr<F>) -> Result<()>,
+    ) -> Result<()> {
         let input = std::fs::read_to_string(file_path)?;
         let Some(file_dir) = file_path.parent() else {
             bail!("Can't get the parent of {file_path}");
         };
         if demo {
-            println!("Loading {file_path} in demo mode");
+            text_println!(self, "Loading {file_path} in demo mode");
         } else {
-            println!("Loading {file_path}");
+            text_println!(self, "Loading {file_path}");
         }
         let mut input = Span::new(&input);
         loop {
-            match self.handle_form(input, file_dir, demo) {
+            match self.handle_form(input, file_dir, demo, on_reduction) {
                 Ok(None) => return Ok(()),
                 Ok(Some(new_input)) => input = new_input,
                 Err(e) => return Err(e),
@@ -633,6 +911,11 @@ impl<F: PrimeField32, C1: Chipset<F>, C2: Chipset<F>> Repl<F, C1, C2> {
 
     pub(crate) fn run(&mut self) -> Result<()> {
         println!("Lurk REPL welcomes you.");
+        println!(
+            "commit: {} {}",
+            env!("VERGEN_GIT_COMMIT_DATE"),
+            env!("VERGEN_GIT_SHA")
+        );
 
         let mut editor: Editor<InputValidator<F>, DefaultHistory> = Editor::new()?;
 
@@ -1,6 +1,15 @@
-use crate::lurk::cli::{
-    config::{set_config_if_unset, Config},
-    repl::Repl,
+use camino::Utf8Path;
+
+use crate::lurk::{
+    cli::{
+        config::{set_config_if_unset, Config},
+        paths::proofs_dir,
+        repl::Repl,
+    },
+    verifier::{
+        get_verifier_version, verify_cached_proof, verify_crypto_proof, CachedProof, Claim,
+    },
+    zstore::ZPtr,
 };
 
 #[test]
@@ -17,6 +26,30 @@ fn test_meta_commands() {
     std::fs::remove_file("repl-test-two").unwrap();
 }
 
+#[test]
+fn test_eval_source() {
+    set_config_if_unset(Config::default());
+    let dir = Utf8Path::new(".");
+    let mut repl = Repl::new_native();
+    assert!(repl.eval_source("(+ 1 2)", dir).is_ok());
+    assert!(repl.eval_source("!(assert-eq 3 (+ 1 2))", dir).is_ok());
+    assert!(repl.eval_source("!(assert-eq 4 (+ 1 2))", dir).is_err());
+    assert!(repl.eval_source("(car 1)", dir).is_err());
+    assert!(repl
+        .eval_source("!(def x 2) (+ x 1) !(assert-eq 3 (+ x 1))", dir)
+        .is_ok());
+    assert!(repl.eval_source("1 (car 1)", dir).is_err());
+    assert!(repl.eval_source("", dir).is_err());
+}
+
+#[test]
+fn test_verifier_version() {
+    // the native REPL proves for the same circuit as the standalone verifier
+    let repl = Repl::new_native();
+    assert_eq!(repl.verifier_version, get_verifier_version());
+    assert_eq!(repl.verifier_version.len(), 64);
+}
+
 #[ignore]
 #[test]
 fn test_meta_commands_with_proofs() {
@@ -33,6 +66,47 @@ fn test_meta_commands_with_proofs() {
     std::fs::remove_file("repl-test-protocol").unwrap();
 }
 
+#[ignore]
+#[test]
+fn test_standalone_verifier() {
+    set_config_if_unset(Config::default());
+    let mut repl = Repl::new_native();
+    let lang_symbols = repl.lang_symbols.clone();
+    let expr = repl.zstore.read("(+ 1 2)", &lang_symbols);
+    repl.handle_non_meta(&expr, None).unwrap();
+    let proof_key = repl.prove_last_reduction().unwrap();
+    let proof_bytes = std::fs::read(proofs_dir().unwrap().join(proof_key)).unwrap();
+
+    let (claim, verdict) = verify_cached_proof(&proof_bytes).unwrap();
+    assert!(verdict.accepted);
+    assert!(verdict.has_same_verifier_version());
+    assert_eq!(claim.result, ZPtr::u64(3));
+
+    // the proof doesn't hold for a different claim
+    let cached_proof: CachedProof = bincode::deserialize(&proof_bytes).unwrap();
+    let crypto_proof_bytes = bincode::serialize(&cached_proof.crypto_proof).unwrap();
+    let wrong_claim = Claim {
+        result: ZPtr::u64(4),
+        ..claim
+    };
+    let verdict = verify_crypto_proof(&crypto_proof_bytes, &wrong_claim).unwrap();
+    assert!(!verdict.accepted);
+}
+
+#[ignore]
+#[test]
+fn test_prove_batch_keeps_last_reduction() {
+    set_config_if_unset(Config::default());
+    let mut repl = Repl::new_native();
+    let lang_symbols = repl.lang_symbols.clone();
+    let expr = repl.zstore.read("(+ 5 5)", &lang_symbols);
+    repl.handle_non_meta(&expr, None).unwrap();
+    let public_values = repl.queries.public_values.clone();
+    let batch = [repl.zstore.read("(+ 1 2)", &lang_symbols)];
+    assert_eq!(repl.prove_batch(&batch).unwrap().len(), 1);
+    assert_eq!(repl.queries.public_values, public_values);
+}
+
 #[test]
 fn test_lib() {
     set_config_if_unset(Config::default());
@@ -0,0 +1,49 @@
+use anyhow::{bail, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+
+use crate::lurk::{
+    big_num::field_elts_to_biguint,
+    verifier::{CachedProof, Verifier},
+    zstore::lurk_zstore,
+};
+
+#[derive(Args, Debug)]
+pub(crate) struct VerifyArgs {
+    /// The proof file, as persisted in ~/.lurk/proofs
+    #[clap(value_parser)]
+    proof_file: Utf8PathBuf,
+}
+
+impl VerifyArgs {
+    pub(crate) fn run(self) -> Result<()> {
+        let Self { proof_file } = self;
+        let cached_proof: CachedProof = bincode::deserialize(&std::fs::read(&proof_file)?)?;
+        let claim = cached_proof.claim();
+        let CachedProof {
+            crypto_proof, zdag, ..
+        } = cached_proof;
+        let env_is_hidden = zdag.has_opaque_data(&claim.env);
+        let mut zstore = lurk_zstore();
+        zdag.populate_zstore(&mut zstore);
+        println!("Expr: {}", zstore.fmt(&claim.expr));
+        if env_is_hidden {
+            let env_digest = field_elts_to_biguint(&claim.env.digest);
+            println!("Env: <Env digest {env_digest:#x}>");
+        } else {
+            println!("Env: {}", zstore.fmt(&claim.env));
+        }
+        println!("Result: {}", zstore.fmt(&claim.result));
+        let verdict = Verifier::native().verify_claim(crypto_proof, &claim);
+        if verdict.accepted {
+            println!("✓ Proof \"{proof_file}\" verified");
+            Ok(())
+        } else {
+            let mut msg = format!("✗ Proof \"{proof_file}\" failed on verification");
+            if !verdict.has_same_verifier_version() {
+                msg.push_str("\nWarning: proof was created for a different verifier version");
+            }
+            bail!(msg)
+        }
+    }
+}
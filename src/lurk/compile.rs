@@ -45,12 +45,24 @@ pub enum Op {
     LessEq,
     Great,
     GreatEq,
+    ModExp,
+    // Bitwise
+    LogAnd,
+    LogOr,
+    LogXor,
+    LogNot,
+    Ash,
+    Rotl,
+    Rotr,
     // Lists and strings
     MkCons,
     Car,
     Cdr,
     Atom,
     MkStrcons,
+    StrLength,
+    Substring,
+    StrLess,
     // Commitments
     Hide,
     Open,
@@ -66,10 +78,14 @@ pub enum Op {
     Char,
     Comm,
     Bignum,
+    StrToSym,
+    SymToStr,
+    NumToStr,
     // Misc
     Emit,
     Begin,
     Fail,
+    Catch,
     Breakpoint,
 }
 
@@ -121,7 +137,7 @@ pub fn compile<F: AbstractField + Ord>(digests: &SymbolsDigests<F>) -> FuncE<F>
                             let op = call(symbol_to_op, head);
                             match head [|sym| digests.builtin_symbol_ptr(sym).to_field()] {
                                 // zero elements
-                                "current-env", "empty-env", "fail" => {
+                                "current-env", "empty-env" => {
                                     let rest_not_nil = sub(rest_tag, nil_tag);
                                     if rest_not_nil {
                                         return (err_tag, invalid_form)
@@ -130,7 +146,8 @@ pub fn compile<F: AbstractField + Ord>(digests: &SymbolsDigests<F>) -> FuncE<F>
                                     return (op, null)
                                 }
                                 // one element
-                                "car", "cdr", "u64", "char", "atom", "emit", "commit", "comm", "open", "secret", "bignum" => {
+                                "car", "cdr", "u64", "char", "atom", "emit", "commit", "comm", "open", "secret", "bignum",
+                                "string-length", "string->symbol", "symbol->string", "num->string", "lognot" => {
                                     let rest_not_cons = sub(rest_tag, cons_tag);
                                     if rest_not_cons {
                                         return (err_tag, invalid_form)
@@ -159,7 +176,8 @@ pub fn compile<F: AbstractField + Ord>(digests: &SymbolsDigests<F>) -> FuncE<F>
                                     return (op, ptr)
                                 }
                                 // two elements
-                                "apply", "cons", "strcons", "hide", "eq", "eqq", "type-eq", "type-eqq"  => {
+                                "apply", "cons", "strcons", "string<", "hide", "eq", "eqq", "type-eq", "type-eqq",
+                                "logand", "logor", "logxor", "ash", "rotl", "rotr", "catch" => {
                                     let rest_not_cons = sub(rest_tag, cons_tag);
                                     if rest_not_cons {
                                         return (err_tag, invalid_form)
@@ -189,6 +207,48 @@ pub fn compile<F: AbstractField + Ord>(digests: &SymbolsDigests<F>) -> FuncE<F>
                                     let ptr = store(cfst_tag, cfst, csnd_tag, csnd);
                                     return (op, ptr)
                                 }
+                                // three elements
+                                "substring", "modexp" => {
+                                    let rest_not_cons = sub(rest_tag, cons_tag);
+                                    if rest_not_cons {
+                                        return (err_tag, invalid_form)
+                                    }
+                                    let (fst_tag, fst, rest_tag, rest) = load(rest);
+                                    let rest_not_cons = sub(rest_tag, cons_tag);
+                                    if rest_not_cons {
+                                        return (err_tag, invalid_form)
+                                    }
+                                    let (snd_tag, snd, rest_tag, rest) = load(rest);
+                                    let rest_not_cons = sub(rest_tag, cons_tag);
+                                    if rest_not_cons {
+                                        return (err_tag, invalid_form)
+                                    }
+                                    let (trd_tag, trd, rest_tag, _rest) = load(rest);
+                                    let rest_not_nil = sub(rest_tag, nil_tag);
+                                    if rest_not_nil {
+                                        return (err_tag, invalid_form)
+                                    }
+                                    let (cfst_tag, cfst) = call(compile, fst_tag, fst);
+                                    match cfst_tag {
+                                        Tag::Err => {
+                                            return (cfst_tag, cfst)
+                                        }
+                                    };
+                                    let (csnd_tag, csnd) = call(compile, snd_tag, snd);
+                                    match csnd_tag {
+                                        Tag::Err => {
+                                            return (csnd_tag, csnd)
+                                        }
+                                    };
+                                    let (ctrd_tag, ctrd) = call(compile, trd_tag, trd);
+                                    match ctrd_tag {
+                                        Tag::Err => {
+                                            return (ctrd_tag, ctrd)
+                                        }
+                                    };
+                                    let ptr = store(cfst_tag, cfst, csnd_tag, csnd, ctrd_tag, ctrd);
+                                    return (op, ptr)
+                                }
                                 // variadic
                                 "begin", "+", "-", "*", "/", "%" => {
                                     let u64_tag = Tag::U64;
@@ -392,6 +452,31 @@ pub fn compile<F: AbstractField + Ord>(digests: &SymbolsDigests<F>) -> FuncE<F>
                                     };
                                     return (err_tag, invalid_form)
                                 }
+                                "fail" => {
+                                    // `(fail)` aborts while `(fail x)` results in an error
+                                    match rest_tag {
+                                        InternalTag::Nil => {
+                                            let null = 0;
+                                            return (op, null)
+                                        }
+                                        Tag::Cons => {
+                                            let (expr_tag, expr, rest_tag, _rest) = load(rest);
+                                            let rest_not_nil = sub(rest_tag, nil_tag);
+                                            if rest_not_nil {
+                                                return (err_tag, invalid_form)
+                                            }
+                                            let (cexpr_tag, cexpr) = call(compile, expr_tag, expr);
+                                            match cexpr_tag {
+                                                Tag::Err => {
+                                                    return (cexpr_tag, cexpr)
+                                                }
+                                            };
+                                            let ptr = store(cexpr_tag, cexpr);
+                                            return (op, ptr)
+                                        }
+                                    };
+                                    return (err_tag, invalid_form)
+                                }
                                 "breakpoint" => {
                                     // TODO
                                     return (err_tag, invalid_form)
@@ -593,6 +678,66 @@ pub fn symbol_to_op<F: AbstractField + Ord>(digests: &SymbolsDigests<F>) -> Func
                     let tag = Op::Fail;
                     return tag
                 }
+                "catch" => {
+                    let tag = Op::Catch;
+                    return tag
+                }
+                "string-length" => {
+                    let tag = Op::StrLength;
+                    return tag
+                }
+                "substring" => {
+                    let tag = Op::Substring;
+                    return tag
+                }
+                "modexp" => {
+                    let tag = Op::ModExp;
+                    return tag
+                }
+                "string<" => {
+                    let tag = Op::StrLess;
+                    return tag
+                }
+                "string->symbol" => {
+                    let tag = Op::StrToSym;
+                    return tag
+                }
+                "symbol->string" => {
+                    let tag = Op::SymToStr;
+                    return tag
+                }
+                "num->string" => {
+                    let tag = Op::NumToStr;
+                    return tag
+                }
+                "logand" => {
+                    let tag = Op::LogAnd;
+                    return tag
+                }
+                "logor" => {
+                    let tag = Op::LogOr;
+                    return tag
+                }
+                "logxor" => {
+                    let tag = Op::LogXor;
+                    return tag
+                }
+                "lognot" => {
+                    let tag = Op::LogNot;
+                    return tag
+                }
+                "ash" => {
+                    let tag = Op::Ash;
+                    return tag
+                }
+                "rotl" => {
+                    let tag = Op::Rotl;
+                    return tag
+                }
+                "rotr" => {
+                    let tag = Op::Rotr;
+                    return tag
+                }
             }
 
         }
@@ -1004,6 +1149,17 @@ pub fn convert_data<F: AbstractField + Ord>(digests: &SymbolsDigests<F>) -> Func
                     let ptr = store(cbody_tag, cbody, cenv);
                     return (tag, ptr)
                 }
+                Tag::Err => {
+                    let (code, payload_tag, payload) = load(expr);
+                    let (cpayload_tag, cpayload) = call(convert_data, payload_tag, payload);
+                    match cpayload_tag {
+                        Tag::Err => {
+                            return (cpayload_tag, cpayload)
+                        }
+                    };
+                    let ptr = store(code, cpayload_tag, cpayload);
+                    return (expr_tag, ptr)
+                }
             };
             return (expr_tag, expr)
         }
@@ -1077,8 +1233,14 @@ pub fn deconvert_data<F: AbstractField + Ord>(digests: &SymbolsDigests<F>) -> Fu
                     let ptr = store(body_tag, body, env);
                     return (tag, ptr)
                 }
+                Tag::Err => {
+                    let (code, cpayload_tag, cpayload) = load(cexpr);
+                    let (payload_tag, payload) = call(deconvert_data, cpayload_tag, cpayload);
+                    let ptr = store(code, payload_tag, payload);
+                    return (cexpr_tag, ptr)
+                }
                 InternalTag::T, InternalTag::Nil, Tag::Sym, Tag::Num, Tag::Str,
-                Tag::Char, Tag::Comm, Tag::U64, Tag::Key, Tag::Err, Tag::Builtin => {
+                Tag::Char, Tag::Comm, Tag::U64, Tag::I64, Tag::BigNum, Tag::Key, Tag::Builtin => {
                     return (cexpr_tag, cexpr)
                 }
             }
@@ -1136,15 +1298,15 @@ mod test {
         let expect_eq = |computed: usize, expected: Expect| {
             expected.assert_eq(&computed.to_string());
         };
-        expect_eq(compile.width(), expect!["116"]);
-        expect_eq(symbol_to_op.width(), expect!["46"]);
+        expect_eq(compile.width(), expect!["130"]);
+        expect_eq(symbol_to_op.width(), expect!["61"]);
         expect_eq(compile_lambda.width(), expect!["42"]);
         expect_eq(compile_let.width(), expect!["57"]);
         expect_eq(compile_mutual_binds.width(), expect!["55"]);
         expect_eq(compile_fold_right.width(), expect!["40"]);
         expect_eq(compile_fold_left.width(), expect!["38"]);
         expect_eq(compile_fold_rel.width(), expect!["58"]);
-        expect_eq(convert_data.width(), expect!["63"]);
-        expect_eq(deconvert_data.width(), expect!["48"]);
+        expect_eq(convert_data.width(), expect!["65"]);
+        expect_eq(deconvert_data.width(), expect!["49"]);
     }
 }
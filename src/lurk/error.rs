@@ -1,8 +1,12 @@
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use p3_field::{AbstractField, PrimeField32};
+use strum::{EnumCount, EnumIter};
 
-#[derive(Clone, Copy, FromPrimitive, Debug)]
+/// Error codes of the Lurk evaluators. At runtime, an error value is a pointer
+/// to a triple `(code, payload_tag, payload)`, where payload-less errors carry
+/// `nil` as their payload
+#[derive(Clone, Copy, FromPrimitive, Debug, EnumCount, EnumIter, PartialEq, Eq)]
 #[repr(u32)]
 pub enum EvalErr {
     UnboundVar = 0,
@@ -26,15 +30,34 @@ pub enum EvalErr {
     CantCastToU64,
     CantCastToBigNum,
     CantCastToComm,
+    NotSymbol,
+    IndexOutOfBounds,
+    Fail,
     Todo,
 }
 
 impl EvalErr {
+    /// The pointer to the payload-less error value, which relies on errors being
+    /// preallocated right after the symbols (see `preallocate_symbols`)
     pub(crate) fn to_field<F: AbstractField>(self) -> F {
-        F::from_canonical_u32(self as u32)
+        F::from_canonical_u32(self as u32 + 1)
     }
 
-    pub(crate) fn from_field<F: PrimeField32>(f: &F) -> Self {
+    /// The raw error code, as stored in the error triple and in its digest
+    pub(crate) fn code(self) -> ErrCode {
+        ErrCode(self)
+    }
+
+    pub(crate) fn from_code<F: PrimeField32>(f: &F) -> Self {
         Self::from_u32(f.as_canonical_u32()).expect("Field element doesn't map to a EvalErr")
     }
 }
+
+#[derive(Clone, Copy)]
+pub(crate) struct ErrCode(EvalErr);
+
+impl ErrCode {
+    pub(crate) fn to_field<F: AbstractField>(self) -> F {
+        F::from_canonical_u32(self.0 as u32)
+    }
+}
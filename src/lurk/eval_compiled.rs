@@ -27,8 +27,15 @@ use super::{
     ingress::{egress, ingress, preallocate_symbols, SymbolsDigests},
     lang::{Coroutine, Lang},
     misc::{
-        big_num_lessthan, digest_equal, hash3, hash4, hash5, u64_add, u64_divrem, u64_iszero,
-        u64_lessthan, u64_mul, u64_sub,
+        big_num_add, big_num_divrem, big_num_lessthan, big_num_modexp, big_num_modexp_reduced,
+        big_num_mul, big_num_sub, digest_equal, hash3, hash4, hash5, i64_divrem, i64_lessthan,
+        i64_negate_if, u64_add, u64_and, u64_ash, u64_divrem, u64_from_num, u64_iszero,
+        u64_lessthan, u64_mul, u64_not, u64_or, u64_rotl, u64_rotr, u64_shl, u64_shr, u64_sub,
+        u64_xor,
+    },
+    string::{
+        string_drop, string_length, string_lessthan, string_take, string_to_symbol, string_to_u64,
+        substring, symbol_name, u64_to_string,
     },
     symbol::Symbol,
     tag::Tag,
@@ -38,7 +45,7 @@ use super::{
 fn native_lurk_funcs<F: PrimeField32>(
     digests: &SymbolsDigests<F>,
     _coroutines: &FxIndexMap<Symbol, Coroutine<F>>,
-) -> [FuncE<F>; 35] {
+) -> [FuncE<F>; 66] {
     [
         // Entrypoint
         lurk_main(),
@@ -54,8 +61,37 @@ fn native_lurk_funcs<F: PrimeField32>(
         u64_divrem(),
         u64_lessthan(),
         u64_iszero(),
+        u64_from_num(),
+        i64_lessthan(),
+        i64_divrem(),
+        i64_negate_if(),
+        u64_and(),
+        u64_or(),
+        u64_xor(),
+        u64_not(),
+        u64_shl(),
+        u64_shr(),
+        u64_rotl(),
+        u64_rotr(),
+        u64_ash(),
         digest_equal(),
         big_num_lessthan(),
+        big_num_add(),
+        big_num_sub(),
+        big_num_mul(),
+        big_num_divrem(),
+        big_num_modexp(),
+        big_num_modexp_reduced(),
+        // Strings
+        string_length(),
+        string_drop(),
+        string_take(),
+        substring(),
+        string_lessthan(),
+        u64_to_string(),
+        string_to_u64(),
+        symbol_name(),
+        string_to_symbol(digests),
         // Ingress/Egress
         ingress(digests),
         egress(digests),
@@ -77,6 +113,9 @@ fn native_lurk_funcs<F: PrimeField32>(
         eval_binop(digests),
         eval_binop_num(digests),
         eval_op_misc(),
+        eval_substring(),
+        eval_modexp(),
+        eval_catch(digests),
         extend_env_with_mutuals(),
         eval_mutual_bindings(),
         env_lookup(),
@@ -160,7 +199,7 @@ pub fn eval<F: AbstractField>() -> FuncE<F> {
     func!(
         partial fn eval(expr_tag, expr, env): [2] {
             match expr_tag {
-                Val::Fun, Val::Thunk, Val::RestFun, Tag::U64, Tag::Num, Tag::BigNum, Tag::Comm, Tag::Char, Tag::Str,
+                Val::Fun, Val::Thunk, Val::RestFun, Tag::U64, Tag::I64, Tag::Num, Tag::BigNum, Tag::Comm, Tag::Char, Tag::Str,
                 Tag::Key, Tag::Fun, Tag::Cons, Tag::Env, Tag::Err, InternalTag::T, InternalTag::Nil => {
                     return (expr_tag, expr)
                 }
@@ -209,13 +248,15 @@ pub fn eval<F: AbstractField>() -> FuncE<F> {
                     let (val_tag, val) = call(apply, fun_tag, fun, args_tag, args, env);
                     return (val_tag, val)
                 }
-                Op::Car, Op::Cdr, Op::Atom, Op::Open, Op::Secret, Op::U64, Op::Char, Op::Comm, Op::Bignum, Op::Emit => {
+                Op::Car, Op::Cdr, Op::Atom, Op::Open, Op::Secret, Op::U64, Op::Char, Op::Comm, Op::Bignum, Op::Emit,
+                Op::StrLength, Op::StrToSym, Op::SymToStr, Op::NumToStr, Op::LogNot => {
                     // The reason this is unconstrained is because `eval_unop` fully constrains the tag
                     #[unconstrained]
                     let (val_tag, val) = call(eval_unop, expr_tag, expr, env);
                     return (val_tag, val)
                 }
-                Op::MkCons, Op::MkStrcons, Op::Eq, Op::TypeEq, Op::Begin, Op::Hide => {
+                Op::MkCons, Op::MkStrcons, Op::StrLess, Op::Eq, Op::TypeEq, Op::Begin, Op::Hide,
+                Op::LogAnd, Op::LogOr, Op::LogXor, Op::Ash, Op::Rotl, Op::Rotr => {
                     // The reason this is unconstrained is because `eval_binop` fully constrains the tag
                     #[unconstrained]
                     let (val_tag, val) = call(eval_binop, expr_tag, expr, env);
@@ -295,7 +336,8 @@ pub fn apply<F: AbstractField>() -> FuncE<F> {
                 }
             };
             let err_tag = Tag::Err;
-            let err = EvalErr::ApplyNonFunc;
+            let code = EvalErr::ApplyNonFunc.code();
+            let err = store(code, fun_tag, fun);
             return (err_tag, err)
         }
     )
@@ -305,7 +347,8 @@ pub fn eval_unop<F: PrimeField32>(digests: &SymbolsDigests<F>) -> FuncE<F> {
     func!(
         partial fn eval_unop(expr_tag, expr, env): [2] {
             match expr_tag {
-                Op::Car, Op::Cdr, Op::Atom, Op::Open, Op::Secret, Op::U64, Op::Char, Op::Comm, Op::Bignum, Op::Emit => {
+                Op::Car, Op::Cdr, Op::Atom, Op::Open, Op::Secret, Op::U64, Op::Char, Op::Comm, Op::Bignum, Op::Emit,
+                Op::StrLength, Op::StrToSym, Op::SymToStr, Op::NumToStr, Op::LogNot => {
                     let (arg_tag, arg) = load(expr);
                     let (arg_tag, arg) = call(eval, arg_tag, arg, env);
                     match arg_tag {
@@ -408,6 +451,16 @@ pub fn eval_unop<F: PrimeField32>(digests: &SymbolsDigests<F>) -> FuncE<F> {
                                     let val_tag = Tag::U64;
                                     return (val_tag, val)
                                 }
+                                Tag::Str => {
+                                    if !arg {
+                                        let err = EvalErr::CantCastToU64;
+                                        return (err_tag, err)
+                                    }
+                                    let zero = [0; 8];
+                                    let zero = store(zero);
+                                    let (res_tag, res) = call(string_to_u64, arg, zero);
+                                    return (res_tag, res)
+                                }
                             };
                             let err = EvalErr::CantCastToU64;
                             return(err_tag, err)
@@ -444,6 +497,68 @@ pub fn eval_unop<F: PrimeField32>(digests: &SymbolsDigests<F>) -> FuncE<F> {
                             let err = EvalErr::CantCastToChar;
                             return(err_tag, err)
                         }
+                        Op::StrLength => {
+                            match arg_tag {
+                                Tag::Str => {
+                                    let len = call(string_length, arg);
+                                    let u64_tag = Tag::U64;
+                                    return (u64_tag, len)
+                                }
+                            };
+                            let err = EvalErr::NotString;
+                            return (err_tag, err)
+                        }
+                        Op::StrToSym => {
+                            match arg_tag {
+                                Tag::Str => {
+                                    let sym = call(string_to_symbol, arg);
+                                    let sym_tag = Tag::Sym;
+                                    return (sym_tag, sym)
+                                }
+                            };
+                            let err = EvalErr::NotString;
+                            return (err_tag, err)
+                        }
+                        Op::SymToStr => {
+                            match arg_tag {
+                                Tag::Sym, Tag::Builtin, Tag::Coroutine, Tag::Key, InternalTag::Nil, InternalTag::T => {
+                                    let name = call(symbol_name, arg_tag, arg);
+                                    let str_tag = Tag::Str;
+                                    return (str_tag, name)
+                                }
+                            };
+                            let err = EvalErr::NotSymbol;
+                            return (err_tag, err)
+                        }
+                        Op::NumToStr => {
+                            match arg_tag {
+                                Tag::U64 => {
+                                    let empty = 0;
+                                    let str = call(u64_to_string, arg, empty);
+                                    let str_tag = Tag::Str;
+                                    return (str_tag, str)
+                                }
+                                Tag::Num => {
+                                    let u = call(u64_from_num, arg);
+                                    let empty = 0;
+                                    let str = call(u64_to_string, u, empty);
+                                    let str_tag = Tag::Str;
+                                    return (str_tag, str)
+                                }
+                            };
+                            let err = EvalErr::NotU64;
+                            return (err_tag, err)
+                        }
+                        Op::LogNot => {
+                            match arg_tag {
+                                Tag::U64 => {
+                                    let res = call(u64_not, arg);
+                                    return (arg_tag, res)
+                                }
+                            };
+                            let err = EvalErr::NotU64;
+                            return (err_tag, err)
+                        }
                     }
                 }
             }
@@ -455,7 +570,8 @@ pub fn eval_binop<F: PrimeField32>(digests: &SymbolsDigests<F>) -> FuncE<F> {
     func!(
         partial fn eval_binop(expr_tag, expr, env): [2] {
             match expr_tag {
-                Op::MkCons, Op::MkStrcons, Op::Eq, Op::TypeEq, Op::Begin, Op::Hide => {
+                Op::MkCons, Op::MkStrcons, Op::StrLess, Op::Eq, Op::TypeEq, Op::Begin, Op::Hide,
+                Op::LogAnd, Op::LogOr, Op::LogXor, Op::Ash, Op::Rotl, Op::Rotr => {
                     let (exp1_tag, exp1, exp2_tag, exp2) = load(expr);
                     let (val1_tag, val1) = call(eval, exp1_tag, exp1, env);
                     match val1_tag {
@@ -492,6 +608,29 @@ pub fn eval_binop<F: PrimeField32>(digests: &SymbolsDigests<F>) -> FuncE<F> {
                             }
                             return (str_tag, strcons)
                         }
+                        Op::StrLess => {
+                            let err_tag = Tag::Err;
+                            let str_tag = Tag::Str;
+                            let not_str1 = sub(val1_tag, str_tag);
+                            let not_str2 = sub(val2_tag, str_tag);
+                            if not_str1 {
+                                let err = EvalErr::NotString;
+                                return (err_tag, err)
+                            }
+                            if not_str2 {
+                                let err = EvalErr::NotString;
+                                return (err_tag, err)
+                            }
+                            let res = call(string_lessthan, val1, val2);
+                            if res {
+                                let t_tag = InternalTag::T;
+                                let t = digests.lurk_symbol_ptr("t");
+                                return (t_tag, t)
+                            }
+                            let nil_tag = InternalTag::Nil;
+                            let nil = digests.lurk_symbol_ptr("nil");
+                            return (nil_tag, nil)
+                        }
                         Op::Begin => {
                             return (val2_tag, val2)
                         }
@@ -533,6 +672,46 @@ pub fn eval_binop<F: PrimeField32>(digests: &SymbolsDigests<F>) -> FuncE<F> {
                             let nil_tag = InternalTag::Nil;
                             return (nil_tag, nil)
                         }
+                        Op::LogAnd, Op::LogOr, Op::LogXor, Op::Rotl, Op::Rotr => {
+                            let err_tag = Tag::Err;
+                            let u64_tag = Tag::U64;
+                            let not_u64_1 = sub(val1_tag, u64_tag);
+                            let not_u64_2 = sub(val2_tag, u64_tag);
+                            if not_u64_1 {
+                                let err = EvalErr::NotU64;
+                                return (err_tag, err)
+                            }
+                            if not_u64_2 {
+                                let err = EvalErr::NotU64;
+                                return (err_tag, err)
+                            }
+                            match expr_tag {
+                                Op::LogAnd => {
+                                    let res = call(u64_and, val1, val2);
+                                    return (u64_tag, res)
+                                }
+                                Op::LogOr => {
+                                    let res = call(u64_or, val1, val2);
+                                    return (u64_tag, res)
+                                }
+                                Op::LogXor => {
+                                    let res = call(u64_xor, val1, val2);
+                                    return (u64_tag, res)
+                                }
+                                Op::Rotl => {
+                                    let res = call(u64_rotl, val1, val2);
+                                    return (u64_tag, res)
+                                }
+                                Op::Rotr => {
+                                    let res = call(u64_rotr, val1, val2);
+                                    return (u64_tag, res)
+                                }
+                            }
+                        }
+                        Op::Ash => {
+                            let (res_tag, res) = call(u64_ash, val1_tag, val1, val2_tag, val2);
+                            return (res_tag, res)
+                        }
                     }
                 }
             }
@@ -637,6 +816,79 @@ pub fn eval_binop_num<F: PrimeField32>(digests: &SymbolsDigests<F>) -> FuncE<F>
                                 }
                             }
                         }
+                        [Tag::I64, Tag::I64] => {
+                            let i64_tag = Tag::I64;
+                            match expr_tag {
+                                // two's complement addition, subtraction and multiplication
+                                // coincide with the unsigned ones
+                                Op::Add => {
+                                    let res = call(u64_add, val1, val2);
+                                    return (i64_tag, res)
+                                }
+                                Op::Sub => {
+                                    let res = call(u64_sub, val1, val2);
+                                    return (i64_tag, res)
+                                }
+                                Op::Mul => {
+                                    let res = call(u64_mul, val1, val2);
+                                    return (i64_tag, res)
+                                }
+                                Op::Div, Op::Mod => {
+                                    let is_zero = call(u64_iszero, val2);
+                                    if is_zero {
+                                        return (err_tag, err_div_zero)
+                                    }
+                                    let (quot, rem, overflow) = call(i64_divrem, val1, val2);
+                                    if overflow {
+                                        let err = EvalErr::InvalidArg;
+                                        return (err_tag, err)
+                                    }
+                                    match expr_tag {
+                                        Op::Div => {
+                                            return (i64_tag, quot)
+                                        }
+                                        Op::Mod => {
+                                            return (i64_tag, rem)
+                                        }
+                                    }
+                                }
+                                Op::Less => {
+                                    let res = call(i64_lessthan, val1, val2);
+                                    if res {
+                                        return (t_tag, t)
+                                    }
+                                    return (nil_tag, nil)
+                                }
+                                Op::GreatEq => {
+                                    let res = call(i64_lessthan, val1, val2);
+                                    if res {
+                                        return (nil_tag, nil)
+                                    }
+                                    return (t_tag, t)
+                                }
+                                Op::Great => {
+                                    let res = call(i64_lessthan, val2, val1);
+                                    if res {
+                                        return (t_tag, t)
+                                    }
+                                    return (nil_tag, nil)
+                                }
+                                Op::LessEq => {
+                                    let res = call(i64_lessthan, val2, val1);
+                                    if res {
+                                        return (nil_tag, nil)
+                                    }
+                                    return (t_tag, t)
+                                }
+                                Op::NumEq => {
+                                    let res = call(digest_equal, val1, val2);
+                                    if res {
+                                        return (t_tag, t)
+                                    }
+                                    return (nil_tag, nil)
+                                }
+                            }
+                        }
                         [Tag::Num, Tag::Num] => {
                             match expr_tag {
                                 Op::Add => {
@@ -672,6 +924,7 @@ pub fn eval_binop_num<F: PrimeField32>(digests: &SymbolsDigests<F>) -> FuncE<F>
                             }
                         }
                         [Tag::BigNum, Tag::BigNum] => {
+                            let big_num_tag = Tag::BigNum;
                             match expr_tag {
                                 Op::Less => {
                                     let res = call(big_num_lessthan, val1, val2);
@@ -709,9 +962,32 @@ pub fn eval_binop_num<F: PrimeField32>(digests: &SymbolsDigests<F>) -> FuncE<F>
                                     }
                                     return (nil_tag, nil)
                                 }
-                                Op::Add, Op::Sub, Op::Mul, Op::Div, Op::Mod => {
-                                    let err = EvalErr::InvalidArg;
-                                    return (err_tag, err)
+                                Op::Add => {
+                                    let res = call(big_num_add, val1, val2);
+                                    return (big_num_tag, res)
+                                }
+                                Op::Sub => {
+                                    let res = call(big_num_sub, val1, val2);
+                                    return (big_num_tag, res)
+                                }
+                                Op::Mul => {
+                                    let res = call(big_num_mul, val1, val2);
+                                    return (big_num_tag, res)
+                                }
+                                Op::Div, Op::Mod => {
+                                    let divisor: [8] = load(val2);
+                                    if !divisor {
+                                        return (err_tag, err_div_zero)
+                                    }
+                                    let (quot, rem) = call(big_num_divrem, val1, val2);
+                                    match expr_tag {
+                                        Op::Div => {
+                                            return (big_num_tag, quot)
+                                        }
+                                        Op::Mod => {
+                                            return (big_num_tag, rem)
+                                        }
+                                    }
                                 }
                             }
                         }
@@ -742,13 +1018,31 @@ pub fn eval_op_misc<F: PrimeField32>() -> FuncE<F> {
                     let (res_tag, res) = load(expr);
                     return (res_tag, res)
                 }
+                // other operations
                 Op::Fail => {
-                    let zero = 0;
-                    let one = 1;
-                    assert_eq!(zero, one, |_, _| "Explicit fail encountered".to_string());
-                    return (zero, zero)
+                    // `(fail)` is compiled with a null pointer
+                    if !expr {
+                        let zero = 0;
+                        let one = 1;
+                        assert_eq!(zero, one, |_, _| "Explicit fail encountered".to_string());
+                        return (zero, zero)
+                    }
+                    let (arg_tag, arg) = load(expr);
+                    let (val_tag, val) = call(eval, arg_tag, arg, env);
+                    match val_tag {
+                        Tag::Err => {
+                            return (val_tag, val)
+                        }
+                    };
+                    let err_tag = Tag::Err;
+                    let code = EvalErr::Fail.code();
+                    let err = store(code, val_tag, val);
+                    return (err_tag, err)
+                }
+                Op::Catch => {
+                    let (res_tag, res) = call(eval_catch, expr, env);
+                    return (res_tag, res)
                 }
-                // other operations
                 Op::Let => {
                     let (param_tag, param, val_tag, val, body_tag, body) = load(expr);
                     let (val_tag, val) = call(eval, val_tag, val, env);
@@ -790,6 +1084,14 @@ pub fn eval_op_misc<F: PrimeField32>() -> FuncE<F> {
                     let (res_tag, res) = call(eval, t_tag, t, env);
                     return (res_tag, res)
                 }
+                Op::Substring => {
+                    let (res_tag, res) = call(eval_substring, expr, env);
+                    return (res_tag, res)
+                }
+                Op::ModExp => {
+                    let (res_tag, res) = call(eval_modexp, expr, env);
+                    return (res_tag, res)
+                }
                 Op::App, Op::Apply,
                 Op::And, Op::Or, Op::Not, Op::Eval, Op::Breakpoint => {
                     let err_tag = Tag::Err;
@@ -807,6 +1109,118 @@ pub fn eval_op_misc<F: PrimeField32>() -> FuncE<F> {
     )
 }
 
+pub fn eval_substring<F: AbstractField>() -> FuncE<F> {
+    func!(
+        partial fn eval_substring(expr, env): [2] {
+            let (str_tag, str, start_tag, start, end_tag, end) = load(expr);
+            let (str_tag, str) = call(eval, str_tag, str, env);
+            match str_tag {
+                Tag::Err => {
+                    return (str_tag, str)
+                }
+            };
+            let (start_tag, start) = call(eval, start_tag, start, env);
+            match start_tag {
+                Tag::Err => {
+                    return (start_tag, start)
+                }
+            };
+            let (end_tag, end) = call(eval, end_tag, end, env);
+            match end_tag {
+                Tag::Err => {
+                    return (end_tag, end)
+                }
+            };
+            let (res_tag, res) = call(substring, str_tag, str, start_tag, start, end_tag, end);
+            return (res_tag, res)
+        }
+    )
+}
+
+pub fn eval_modexp<F: AbstractField>() -> FuncE<F> {
+    func!(
+        partial fn eval_modexp(expr, env): [2] {
+            let (base_tag, base, exp_tag, exp, modulus_tag, modulus) = load(expr);
+            let (base_tag, base) = call(eval, base_tag, base, env);
+            match base_tag {
+                Tag::Err => {
+                    return (base_tag, base)
+                }
+            };
+            let (exp_tag, exp) = call(eval, exp_tag, exp, env);
+            match exp_tag {
+                Tag::Err => {
+                    return (exp_tag, exp)
+                }
+            };
+            let (modulus_tag, modulus) = call(eval, modulus_tag, modulus, env);
+            match modulus_tag {
+                Tag::Err => {
+                    return (modulus_tag, modulus)
+                }
+            };
+            let err_tag = Tag::Err;
+            let big_num_tag = Tag::BigNum;
+            let not_big_num = EvalErr::NotBigNum;
+            let base_not_big_num = sub(base_tag, big_num_tag);
+            if base_not_big_num {
+                return (err_tag, not_big_num)
+            }
+            let exp_not_big_num = sub(exp_tag, big_num_tag);
+            if exp_not_big_num {
+                return (err_tag, not_big_num)
+            }
+            let modulus_not_big_num = sub(modulus_tag, big_num_tag);
+            if modulus_not_big_num {
+                return (err_tag, not_big_num)
+            }
+            let modulus_digest: [8] = load(modulus);
+            if !modulus_digest {
+                let err_div_zero = EvalErr::DivByZero;
+                return (err_tag, err_div_zero)
+            }
+            let res = call(big_num_modexp, base, exp, modulus);
+            return (big_num_tag, res)
+        }
+    )
+}
+
+/// Evaluates the protected expression and, if it results in an error, applies
+/// the handler to the error code, as a `U64`, and to the error payload
+pub fn eval_catch<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F> {
+    func!(
+        partial fn eval_catch(expr, env): [2] {
+            let (expr_tag, expr, handler_tag, handler) = load(expr);
+            let (val_tag, val) = call(eval, expr_tag, expr, env);
+            match val_tag {
+                Tag::Err => {
+                    let (handler_tag, handler) = call(eval, handler_tag, handler, env);
+                    match handler_tag {
+                        Tag::Err => {
+                            return (handler_tag, handler)
+                        }
+                    };
+                    let (code, payload_tag, payload) = load(val);
+                    let code_tag = Tag::U64;
+                    let padding = [0; 7];
+                    let code = store(code, padding);
+                    // the arguments are evaluated by `apply`, so the payload is quoted
+                    let quote_tag = Op::Quote;
+                    let quoted = store(payload_tag, payload);
+                    let mkcons_tag = Op::MkCons;
+                    let nil_tag = InternalTag::Nil;
+                    let nil = digests.lurk_symbol_ptr("nil");
+                    let args = store(quote_tag, quoted, nil_tag, nil);
+                    let args = store(code_tag, code, mkcons_tag, args);
+                    let (res_tag, res) = call(apply, handler_tag, handler, mkcons_tag, args, env);
+                    return (res_tag, res)
+                }
+            };
+            return (val_tag, val)
+        }
+    )
+}
+
 pub fn equal_inner<F: AbstractField>() -> FuncE<F> {
     func!(
         fn equal_inner(a_tag, a, b_tag, b): [1] {
@@ -834,7 +1248,7 @@ pub fn equal_inner<F: AbstractField>() -> FuncE<F> {
                     }
                     return one
                 }
-                Tag::Key, Tag::Sym, Tag::Builtin, Tag::Coroutine, Tag::U64, Tag::BigNum, Tag::Comm => {
+                Tag::Key, Tag::Sym, Tag::Builtin, Tag::Coroutine, Tag::U64, Tag::I64, Tag::BigNum, Tag::Comm => {
                     let a_digest: [8] = load(a);
                     let b_digest: [8] = load(b);
                     let diff = sub(a_digest, b_digest);
@@ -891,8 +1305,11 @@ pub fn env_lookup<F: AbstractField>() -> FuncE<F> {
     func!(
         fn env_lookup(x_tag_digest: [9], env): [2] {
             if !env {
+                let (x_tag, x_digest: [8]) = x_tag_digest;
+                let x = store(x_digest);
+                let code = EvalErr::UnboundVar.code();
                 let err_tag = Tag::Err;
-                let err = EvalErr::UnboundVar;
+                let err = store(code, x_tag, x);
                 return (err_tag, err)
             }
             let (y_tag, y, val_tag, val, tail_env) = load(env);
@@ -969,6 +1386,9 @@ mod test {
         let eval_binop = FuncChip::from_name("eval_binop", toplevel);
         let eval_binop_num = FuncChip::from_name("eval_binop_num", toplevel);
         let eval_op_misc = FuncChip::from_name("eval_op_misc", toplevel);
+        let eval_substring = FuncChip::from_name("eval_substring", toplevel);
+        let eval_modexp = FuncChip::from_name("eval_modexp", toplevel);
+        let eval_catch = FuncChip::from_name("eval_catch", toplevel);
         let extend_env_with_mutuals = FuncChip::from_name("extend_env_with_mutuals", toplevel);
         let eval_mutual_bindings = FuncChip::from_name("eval_mutual_bindings", toplevel);
         let equal_inner = FuncChip::from_name("equal_inner", toplevel);
@@ -979,12 +1399,15 @@ mod test {
         expect_eq(lurk_main.width(), expect!["114"]);
         expect_eq(eval.width(), expect!["73"]);
         expect_eq(apply.width(), expect!["105"]);
-        expect_eq(eval_op_misc.width(), expect!["81"]);
-        expect_eq(eval_unop.width(), expect!["122"]);
-        expect_eq(eval_binop.width(), expect!["119"]);
-        expect_eq(eval_binop_num.width(), expect!["120"]);
+        expect_eq(eval_op_misc.width(), expect!["86"]);
+        expect_eq(eval_substring.width(), expect!["92"]);
+        expect_eq(eval_modexp.width(), expect!["114"]);
+        expect_eq(eval_catch.width(), expect!["104"]);
+        expect_eq(eval_unop.width(), expect!["140"]);
+        expect_eq(eval_binop.width(), expect!["136"]);
+        expect_eq(eval_binop_num.width(), expect!["157"]);
         expect_eq(extend_env_with_mutuals.width(), expect!["30"]);
         expect_eq(eval_mutual_bindings.width(), expect!["66"]);
-        expect_eq(equal_inner.width(), expect!["58"]);
+        expect_eq(equal_inner.width(), expect!["59"]);
     }
 }
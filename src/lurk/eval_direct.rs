@@ -18,8 +18,15 @@ use super::{
     ingress::{egress, ingress, preallocate_symbols, InternalTag, SymbolsDigests},
     lang::{Coroutine, Lang},
     misc::{
-        big_num_lessthan, digest_equal, hash3, hash4, hash5, u64_add, u64_divrem, u64_iszero,
-        u64_lessthan, u64_mul, u64_sub,
+        big_num_add, big_num_divrem, big_num_lessthan, big_num_modexp, big_num_modexp_reduced,
+        big_num_mul, big_num_sub, digest_equal, hash3, hash4, hash5, i64_divrem, i64_lessthan,
+        i64_negate_if, u64_add, u64_and, u64_ash, u64_divrem, u64_from_num, u64_iszero,
+        u64_lessthan, u64_mul, u64_not, u64_or, u64_rotl, u64_rotr, u64_shl, u64_shr, u64_sub,
+        u64_xor,
+    },
+    string::{
+        string_drop, string_length, string_lessthan, string_take, string_to_symbol, string_to_u64,
+        substring, symbol_name, u64_to_string,
     },
     symbol::Symbol,
     tag::Tag,
@@ -29,19 +36,22 @@ use super::{
 fn native_lurk_funcs<F: PrimeField32>(
     digests: &SymbolsDigests<F>,
     coroutines: &FxIndexMap<Symbol, Coroutine<F>>,
-) -> [FuncE<F>; 37] {
+) -> [FuncE<F>; 68] {
     [
         lurk_main(),
         preallocate_symbols(digests),
         eval(),
         eval_builtin_expr(digests),
         eval_apply_builtin(),
+        eval_catch(digests),
         eval_coroutine_expr(digests, coroutines),
         eval_opening_unop(digests),
         eval_hide(),
         eval_unop(digests),
         eval_binop_num(digests),
         eval_binop_misc(digests),
+        eval_substring(),
+        eval_modexp(),
         eval_begin(),
         eval_list(),
         coerce_if_sym(),
@@ -66,8 +76,36 @@ fn native_lurk_funcs<F: PrimeField32>(
         u64_divrem(),
         u64_lessthan(),
         u64_iszero(),
+        u64_from_num(),
+        i64_lessthan(),
+        i64_divrem(),
+        i64_negate_if(),
+        u64_and(),
+        u64_or(),
+        u64_xor(),
+        u64_not(),
+        u64_shl(),
+        u64_shr(),
+        u64_rotl(),
+        u64_rotr(),
+        u64_ash(),
         digest_equal(),
         big_num_lessthan(),
+        big_num_add(),
+        big_num_sub(),
+        big_num_mul(),
+        big_num_divrem(),
+        big_num_modexp(),
+        big_num_modexp_reduced(),
+        string_length(),
+        string_drop(),
+        string_take(),
+        substring(),
+        string_lessthan(),
+        u64_to_string(),
+        string_to_u64(),
+        symbol_name(),
+        string_to_symbol(digests),
     ]
 }
 
@@ -480,7 +518,8 @@ pub fn eval_builtin_expr<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE
                         }
                     }
                 }
-                "cons", "strcons", "type-eq", "type-eqq", "apply" => {
+                "cons", "strcons", "string<", "logand", "logor", "logxor", "ash", "rotl", "rotr",
+                "type-eq", "type-eqq", "apply", "catch" => {
                     let rest_not_cons = sub(rest_tag, cons_tag);
                     if rest_not_cons {
                         return (err_tag, invalid_form)
@@ -496,7 +535,7 @@ pub fn eval_builtin_expr<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE
                         return (err_tag, invalid_form)
                     }
                     match head [|name| digests.builtin_symbol_ptr(name).to_field()] {
-                        "cons", "strcons" => {
+                        "cons", "strcons", "string<", "logand", "logor", "logxor", "ash", "rotl", "rotr" => {
                             let (res_tag, res) = call(eval_binop_misc, head, fst_tag, fst, snd_tag, snd, env);
                             return (res_tag, res)
                         }
@@ -546,6 +585,10 @@ pub fn eval_builtin_expr<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE
                             let (res_tag, res) = call(eval_apply_builtin, fst_tag, fst, snd_tag, snd, env);
                             return (res_tag, res)
                         }
+                        "catch" => {
+                            let (res_tag, res) = call(eval_catch, fst_tag, fst, snd_tag, snd, env);
+                            return (res_tag, res)
+                        }
                     }
                 }
                 "list" => {
@@ -635,7 +678,7 @@ pub fn eval_builtin_expr<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE
                     let (expr_tag, expr) = call(eval_begin, rest_tag, rest, env);
                     return (expr_tag, expr)
                 }
-                "current-env", "empty-env", "fail" => {
+                "current-env", "empty-env" => {
                     let rest_not_nil = sub(rest_tag, nil_tag);
                     if rest_not_nil {
                         return (err_tag, invalid_form)
@@ -649,13 +692,35 @@ pub fn eval_builtin_expr<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE
                             let env = 0;
                             return (env_tag, env)
                         }
-                        "fail" => {
+                    }
+                }
+                "fail" => {
+                    match rest_tag {
+                        InternalTag::Nil => {
                             let zero = 0;
                             let one = 1;
                             assert_eq!(zero, one, |_, _| "Explicit fail encountered".to_string());
                             return (zero, zero)
                         }
-                    }
+                        Tag::Cons => {
+                            // `(fail x)` returns an error with the value of `x` as payload
+                            let (expr_tag, expr, rest_tag, _rest) = load(rest);
+                            let rest_not_nil = sub(rest_tag, nil_tag);
+                            if rest_not_nil {
+                                return (err_tag, invalid_form)
+                            }
+                            let (val_tag, val) = call(eval, expr_tag, expr, env);
+                            match val_tag {
+                                Tag::Err => {
+                                    return (val_tag, val)
+                                }
+                            };
+                            let code = EvalErr::Fail.code();
+                            let err = store(code, val_tag, val);
+                            return (err_tag, err)
+                        }
+                    };
+                    return (err_tag, invalid_form)
                 }
                 "breakpoint" => {
                     breakpoint;
@@ -745,10 +810,19 @@ pub fn eval_builtin_expr<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE
                         }
                     }
                 }
-                "u64", "char", "atom", "emit", "bignum", "comm" => {
+                "u64", "char", "atom", "emit", "bignum", "comm", "string-length", "string->symbol",
+                "symbol->string", "num->string", "lognot" => {
                     let (res_tag, res) = call(eval_unop, head, rest_tag, rest, env);
                     return (res_tag, res)
                 }
+                "substring" => {
+                    let (res_tag, res) = call(eval_substring, rest_tag, rest, env);
+                    return (res_tag, res)
+                }
+                "modexp" => {
+                    let (res_tag, res) = call(eval_modexp, rest_tag, rest, env);
+                    return (res_tag, res)
+                }
                 "commit", "open", "secret" => {
                     let (res_tag, res) = call(eval_opening_unop, head, rest_tag, rest, env);
                     return (res_tag, res)
@@ -780,6 +854,44 @@ pub fn eval_apply_builtin<F: AbstractField>() -> FuncE<F> {
     )
 }
 
+/// Evaluates `expr` and, if it results in an error, applies the value of
+/// `handler` to the error code, as a `U64`, and to the error payload
+pub fn eval_catch<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F> {
+    func!(
+        partial fn eval_catch(expr_tag, expr, handler_tag, handler, env): [2] {
+            let (val_tag, val) = call(eval, expr_tag, expr, env);
+            match val_tag {
+                Tag::Err => {
+                    let (handler_tag, handler) = call(eval, handler_tag, handler, env);
+                    match handler_tag {
+                        Tag::Err => {
+                            return (handler_tag, handler)
+                        }
+                    };
+                    let (code, payload_tag, payload) = load(val);
+                    let code_tag = Tag::U64;
+                    let padding = [0; 7];
+                    let code = store(code, padding);
+                    // the arguments are evaluated by `apply`, so the payload is quoted
+                    // in order to be passed as is: `(code (quote payload))`
+                    let quote_tag = Tag::Builtin;
+                    let quote = digests.builtin_symbol_ptr("quote");
+                    let nil_tag = InternalTag::Nil;
+                    let nil = digests.lurk_symbol_ptr("nil");
+                    let cons_tag = Tag::Cons;
+                    let quoted = store(payload_tag, payload, nil_tag, nil);
+                    let quoted = store(quote_tag, quote, cons_tag, quoted);
+                    let args = store(cons_tag, quoted, nil_tag, nil);
+                    let args = store(code_tag, code, cons_tag, args);
+                    let (res_tag, res) = call(apply, handler_tag, handler, cons_tag, args, env);
+                    return (res_tag, res)
+                }
+            };
+            return (val_tag, val)
+        }
+    )
+}
+
 pub fn coerce_if_sym<F: AbstractField>() -> FuncE<F> {
     func!(
         fn coerce_if_sym(tag): [1] {
@@ -936,7 +1048,7 @@ pub fn equal_inner<F: AbstractField>() -> FuncE<F> {
                     }
                     return one
                 }
-                Tag::Key, Tag::Sym, Tag::Builtin, Tag::Coroutine, Tag::U64, Tag::BigNum, Tag::Comm => {
+                Tag::Key, Tag::Sym, Tag::Builtin, Tag::Coroutine, Tag::U64, Tag::I64, Tag::BigNum, Tag::Comm => {
                     let a_digest: [8] = load(a);
                     let b_digest: [8] = load(b);
                     let diff = sub(a_digest, b_digest);
@@ -1151,6 +1263,79 @@ pub fn eval_binop_num<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F>
                         }
                     }
                 }
+                [Tag::I64, Tag::I64] => {
+                    let i64_tag = Tag::I64;
+                    match head [|name| digests.builtin_symbol_ptr(name).to_field()] {
+                        // two's complement addition, subtraction and multiplication
+                        // coincide with the unsigned ones
+                        "+" => {
+                            let res = call(u64_add, val1, val2);
+                            return (i64_tag, res)
+                        }
+                        "-" => {
+                            let res = call(u64_sub, val1, val2);
+                            return (i64_tag, res)
+                        }
+                        "*" => {
+                            let res = call(u64_mul, val1, val2);
+                            return (i64_tag, res)
+                        }
+                        "/", "%" => {
+                            let is_zero = call(u64_iszero, val2);
+                            if is_zero {
+                                return (err_tag, err_div_zero)
+                            }
+                            let (quot, rem, overflow) = call(i64_divrem, val1, val2);
+                            if overflow {
+                                let err = EvalErr::InvalidArg;
+                                return (err_tag, err)
+                            }
+                            match head [|name| digests.builtin_symbol_ptr(name).to_field()] {
+                                "/" => {
+                                    return (i64_tag, quot)
+                                }
+                                "%" => {
+                                    return (i64_tag, rem)
+                                }
+                            }
+                        }
+                        "<" => {
+                            let res = call(i64_lessthan, val1, val2);
+                            if res {
+                                return (t_tag, t)
+                            }
+                            return (nil_tag, nil)
+                        }
+                        ">=" => {
+                            let res = call(i64_lessthan, val1, val2);
+                            if res {
+                                return (nil_tag, nil)
+                            }
+                            return (t_tag, t)
+                        }
+                        ">" => {
+                            let res = call(i64_lessthan, val2, val1);
+                            if res {
+                                return (t_tag, t)
+                            }
+                            return (nil_tag, nil)
+                        }
+                        "<=" => {
+                            let res = call(i64_lessthan, val2, val1);
+                            if res {
+                                return (nil_tag, nil)
+                            }
+                            return (t_tag, t)
+                        }
+                        "=" => {
+                            let res = call(digest_equal, val1, val2);
+                            if res {
+                                return (t_tag, t)
+                            }
+                            return (nil_tag, nil)
+                        }
+                    }
+                }
                 [Tag::Num, Tag::Num] => {
                     match head [|name| digests.builtin_symbol_ptr(name).to_field()] {
                         "+" => {
@@ -1186,6 +1371,7 @@ pub fn eval_binop_num<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F>
                     }
                 }
                 [Tag::BigNum, Tag::BigNum] => {
+                    let big_num_tag = Tag::BigNum;
                     match head [|name| digests.builtin_symbol_ptr(name).to_field()] {
                         "<" => {
                             let res = call(big_num_lessthan, val1, val2);
@@ -1223,9 +1409,32 @@ pub fn eval_binop_num<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F>
                             }
                             return (nil_tag, nil)
                         }
-                        "+", "-", "*", "/", "%" => {
-                            let err = EvalErr::InvalidArg;
-                            return (err_tag, err)
+                        "+" => {
+                            let res = call(big_num_add, val1, val2);
+                            return (big_num_tag, res)
+                        }
+                        "-" => {
+                            let res = call(big_num_sub, val1, val2);
+                            return (big_num_tag, res)
+                        }
+                        "*" => {
+                            let res = call(big_num_mul, val1, val2);
+                            return (big_num_tag, res)
+                        }
+                        "/", "%" => {
+                            let divisor: [8] = load(val2);
+                            if !divisor {
+                                return (err_tag, err_div_zero)
+                            }
+                            let (quot, rem) = call(big_num_divrem, val1, val2);
+                            match head [|name| digests.builtin_symbol_ptr(name).to_field()] {
+                                "/" => {
+                                    return (big_num_tag, quot)
+                                }
+                                "%" => {
+                                    return (big_num_tag, rem)
+                                }
+                            }
                         }
                     }
                 }
@@ -1274,7 +1483,187 @@ pub fn eval_binop_misc<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F
                     }
                     return (str_tag, strcons)
                 }
+                "string<" => {
+                    let str_tag = Tag::Str;
+                    let not_str1 = sub(val1_tag, str_tag);
+                    let not_str2 = sub(val2_tag, str_tag);
+                    if not_str1 {
+                        let err = EvalErr::NotString;
+                        return (err_tag, err)
+                    }
+                    if not_str2 {
+                        let err = EvalErr::NotString;
+                        return (err_tag, err)
+                    }
+                    let res = call(string_lessthan, val1, val2);
+                    if res {
+                        let t_tag = InternalTag::T;
+                        let t = digests.lurk_symbol_ptr("t");
+                        return (t_tag, t)
+                    }
+                    let nil_tag = InternalTag::Nil;
+                    let nil = digests.lurk_symbol_ptr("nil");
+                    return (nil_tag, nil)
+                }
+                "logand", "logor", "logxor", "rotl", "rotr" => {
+                    let u64_tag = Tag::U64;
+                    let not_u64_1 = sub(val1_tag, u64_tag);
+                    let not_u64_2 = sub(val2_tag, u64_tag);
+                    if not_u64_1 {
+                        let err = EvalErr::NotU64;
+                        return (err_tag, err)
+                    }
+                    if not_u64_2 {
+                        let err = EvalErr::NotU64;
+                        return (err_tag, err)
+                    }
+                    match head [|name| digests.builtin_symbol_ptr(name).to_field()] {
+                        "logand" => {
+                            let res = call(u64_and, val1, val2);
+                            return (u64_tag, res)
+                        }
+                        "logor" => {
+                            let res = call(u64_or, val1, val2);
+                            return (u64_tag, res)
+                        }
+                        "logxor" => {
+                            let res = call(u64_xor, val1, val2);
+                            return (u64_tag, res)
+                        }
+                        "rotl" => {
+                            let res = call(u64_rotl, val1, val2);
+                            return (u64_tag, res)
+                        }
+                        "rotr" => {
+                            let res = call(u64_rotr, val1, val2);
+                            return (u64_tag, res)
+                        }
+                    }
+                }
+                "ash" => {
+                    let (res_tag, res) = call(u64_ash, val1_tag, val1, val2_tag, val2);
+                    return (res_tag, res)
+                }
+            }
+        }
+    )
+}
+
+pub fn eval_substring<F: AbstractField>() -> FuncE<F> {
+    func!(
+        partial fn eval_substring(rest_tag, rest, env): [2] {
+            let err_tag = Tag::Err;
+            let cons_tag = Tag::Cons;
+            let nil_tag = InternalTag::Nil;
+            let invalid_form = EvalErr::InvalidForm;
+            let rest_not_cons = sub(rest_tag, cons_tag);
+            if rest_not_cons {
+                return (err_tag, invalid_form)
+            }
+            let (str_tag, str, rest_tag, rest) = load(rest);
+            let rest_not_cons = sub(rest_tag, cons_tag);
+            if rest_not_cons {
+                return (err_tag, invalid_form)
+            }
+            let (start_tag, start, rest_tag, rest) = load(rest);
+            let rest_not_cons = sub(rest_tag, cons_tag);
+            if rest_not_cons {
+                return (err_tag, invalid_form)
+            }
+            let (end_tag, end, rest_tag, _rest) = load(rest);
+            let rest_not_nil = sub(rest_tag, nil_tag);
+            if rest_not_nil {
+                return (err_tag, invalid_form)
+            }
+            let (str_tag, str) = call(eval, str_tag, str, env);
+            match str_tag {
+                Tag::Err => {
+                    return (str_tag, str)
+                }
+            };
+            let (start_tag, start) = call(eval, start_tag, start, env);
+            match start_tag {
+                Tag::Err => {
+                    return (start_tag, start)
+                }
+            };
+            let (end_tag, end) = call(eval, end_tag, end, env);
+            match end_tag {
+                Tag::Err => {
+                    return (end_tag, end)
+                }
+            };
+            let (res_tag, res) = call(substring, str_tag, str, start_tag, start, end_tag, end);
+            return (res_tag, res)
+        }
+    )
+}
+
+pub fn eval_modexp<F: AbstractField>() -> FuncE<F> {
+    func!(
+        partial fn eval_modexp(rest_tag, rest, env): [2] {
+            let err_tag = Tag::Err;
+            let cons_tag = Tag::Cons;
+            let nil_tag = InternalTag::Nil;
+            let invalid_form = EvalErr::InvalidForm;
+            let rest_not_cons = sub(rest_tag, cons_tag);
+            if rest_not_cons {
+                return (err_tag, invalid_form)
+            }
+            let (base_tag, base, rest_tag, rest) = load(rest);
+            let rest_not_cons = sub(rest_tag, cons_tag);
+            if rest_not_cons {
+                return (err_tag, invalid_form)
+            }
+            let (exp_tag, exp, rest_tag, rest) = load(rest);
+            let rest_not_cons = sub(rest_tag, cons_tag);
+            if rest_not_cons {
+                return (err_tag, invalid_form)
+            }
+            let (modulus_tag, modulus, rest_tag, _rest) = load(rest);
+            let rest_not_nil = sub(rest_tag, nil_tag);
+            if rest_not_nil {
+                return (err_tag, invalid_form)
+            }
+            let (base_tag, base) = call(eval, base_tag, base, env);
+            match base_tag {
+                Tag::Err => {
+                    return (base_tag, base)
+                }
+            };
+            let (exp_tag, exp) = call(eval, exp_tag, exp, env);
+            match exp_tag {
+                Tag::Err => {
+                    return (exp_tag, exp)
+                }
+            };
+            let (modulus_tag, modulus) = call(eval, modulus_tag, modulus, env);
+            match modulus_tag {
+                Tag::Err => {
+                    return (modulus_tag, modulus)
+                }
+            };
+            let big_num_tag = Tag::BigNum;
+            let not_big_num = EvalErr::NotBigNum;
+            let base_not_big_num = sub(base_tag, big_num_tag);
+            if base_not_big_num {
+                return (err_tag, not_big_num)
+            }
+            let exp_not_big_num = sub(exp_tag, big_num_tag);
+            if exp_not_big_num {
+                return (err_tag, not_big_num)
+            }
+            let modulus_not_big_num = sub(modulus_tag, big_num_tag);
+            if modulus_not_big_num {
+                return (err_tag, not_big_num)
             }
+            let modulus_digest: [8] = load(modulus);
+            if !modulus_digest {
+                let err_div_zero = EvalErr::DivByZero;
+                return (err_tag, err_div_zero)
+            }
+            let res = call(big_num_modexp, base, exp, modulus);
+            return (big_num_tag, res)
         }
     )
 }
@@ -1329,6 +1718,16 @@ pub fn eval_unop<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F> {
                             let val_tag = Tag::U64;
                             return (val_tag, val)
                         }
+                        Tag::Str => {
+                            if !val {
+                                let err = EvalErr::CantCastToU64;
+                                return (err_tag, err)
+                            }
+                            let zero = [0; 8];
+                            let zero = store(zero);
+                            let (res_tag, res) = call(string_to_u64, val, zero);
+                            return (res_tag, res)
+                        }
                     };
                     let err = EvalErr::CantCastToU64;
                     return(err_tag, err)
@@ -1374,6 +1773,68 @@ pub fn eval_unop<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F> {
                     let err = EvalErr::CantCastToComm;
                     return(err_tag, err)
                 }
+                "string-length" => {
+                    match val_tag {
+                        Tag::Str => {
+                            let len = call(string_length, val);
+                            let u64_tag = Tag::U64;
+                            return (u64_tag, len)
+                        }
+                    };
+                    let err = EvalErr::NotString;
+                    return (err_tag, err)
+                }
+                "string->symbol" => {
+                    match val_tag {
+                        Tag::Str => {
+                            let sym = call(string_to_symbol, val);
+                            let sym_tag = Tag::Sym;
+                            return (sym_tag, sym)
+                        }
+                    };
+                    let err = EvalErr::NotString;
+                    return (err_tag, err)
+                }
+                "symbol->string" => {
+                    match val_tag {
+                        Tag::Sym, Tag::Builtin, Tag::Coroutine, Tag::Key, InternalTag::Nil, InternalTag::T => {
+                            let name = call(symbol_name, val_tag, val);
+                            let str_tag = Tag::Str;
+                            return (str_tag, name)
+                        }
+                    };
+                    let err = EvalErr::NotSymbol;
+                    return (err_tag, err)
+                }
+                "num->string" => {
+                    match val_tag {
+                        Tag::U64 => {
+                            let empty = 0;
+                            let str = call(u64_to_string, val, empty);
+                            let str_tag = Tag::Str;
+                            return (str_tag, str)
+                        }
+                        Tag::Num => {
+                            let u = call(u64_from_num, val);
+                            let empty = 0;
+                            let str = call(u64_to_string, u, empty);
+                            let str_tag = Tag::Str;
+                            return (str_tag, str)
+                        }
+                    };
+                    let err = EvalErr::NotU64;
+                    return (err_tag, err)
+                }
+                "lognot" => {
+                    match val_tag {
+                        Tag::U64 => {
+                            let res = call(u64_not, val);
+                            return (val_tag, res)
+                        }
+                    };
+                    let err = EvalErr::NotU64;
+                    return (err_tag, err)
+                }
              }
         }
     )
@@ -1663,7 +2124,8 @@ pub fn apply<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F> {
             // Expression must be a function
             let head_not_fun = sub(head_tag, fun_tag);
             if head_not_fun {
-                let err = EvalErr::ApplyNonFunc;
+                let code = EvalErr::ApplyNonFunc.code();
+                let err = store(code, head_tag, head);
                 return (err_tag, err)
             }
 
@@ -1818,8 +2280,11 @@ pub fn env_lookup<F: AbstractField>() -> FuncE<F> {
     func!(
         fn env_lookup(x_tag_digest: [9], env): [2] {
             if !env {
+                let (x_tag, x_digest: [8]) = x_tag_digest;
+                let x = store(x_digest);
+                let code = EvalErr::UnboundVar.code();
                 let err_tag = Tag::Err;
-                let err = EvalErr::UnboundVar;
+                let err = store(code, x_tag, x);
                 return (err_tag, err)
             }
             let (y_tag, y, val_tag, val, tail_env) = load(env);
@@ -1865,11 +2330,14 @@ mod test {
         let eval = FuncChip::from_name("eval", toplevel);
         let eval_builtin_expr = FuncChip::from_name("eval_builtin_expr", toplevel);
         let eval_apply_builtin = FuncChip::from_name("eval_apply_builtin", toplevel);
+        let eval_catch = FuncChip::from_name("eval_catch", toplevel);
         let eval_opening_unop = FuncChip::from_name("eval_opening_unop", toplevel);
         let eval_hide = FuncChip::from_name("eval_hide", toplevel);
         let eval_unop = FuncChip::from_name("eval_unop", toplevel);
         let eval_binop_num = FuncChip::from_name("eval_binop_num", toplevel);
         let eval_binop_misc = FuncChip::from_name("eval_binop_misc", toplevel);
+        let eval_substring = FuncChip::from_name("eval_substring", toplevel);
+        let eval_modexp = FuncChip::from_name("eval_modexp", toplevel);
         let eval_begin = FuncChip::from_name("eval_begin", toplevel);
         let eval_list = FuncChip::from_name("eval_list", toplevel);
         let eval_let = FuncChip::from_name("eval_let", toplevel);
@@ -1894,23 +2362,54 @@ mod test {
         let u64_divrem = FuncChip::from_name("u64_divrem", toplevel);
         let u64_lessthan = FuncChip::from_name("u64_lessthan", toplevel);
         let u64_iszero = FuncChip::from_name("u64_iszero", toplevel);
+        let u64_from_num = FuncChip::from_name("u64_from_num", toplevel);
+        let i64_lessthan = FuncChip::from_name("i64_lessthan", toplevel);
+        let i64_divrem = FuncChip::from_name("i64_divrem", toplevel);
+        let i64_negate_if = FuncChip::from_name("i64_negate_if", toplevel);
+        let u64_and = FuncChip::from_name("u64_and", toplevel);
+        let u64_or = FuncChip::from_name("u64_or", toplevel);
+        let u64_xor = FuncChip::from_name("u64_xor", toplevel);
+        let u64_not = FuncChip::from_name("u64_not", toplevel);
+        let u64_shl = FuncChip::from_name("u64_shl", toplevel);
+        let u64_shr = FuncChip::from_name("u64_shr", toplevel);
+        let u64_rotl = FuncChip::from_name("u64_rotl", toplevel);
+        let u64_rotr = FuncChip::from_name("u64_rotr", toplevel);
+        let u64_ash = FuncChip::from_name("u64_ash", toplevel);
         let digest_equal = FuncChip::from_name("digest_equal", toplevel);
         let big_num_lessthan = FuncChip::from_name("big_num_lessthan", toplevel);
+        let big_num_add = FuncChip::from_name("big_num_add", toplevel);
+        let big_num_sub = FuncChip::from_name("big_num_sub", toplevel);
+        let big_num_mul = FuncChip::from_name("big_num_mul", toplevel);
+        let big_num_divrem = FuncChip::from_name("big_num_divrem", toplevel);
+        let big_num_modexp = FuncChip::from_name("big_num_modexp", toplevel);
+        let big_num_modexp_reduced = FuncChip::from_name("big_num_modexp_reduced", toplevel);
+        let string_length = FuncChip::from_name("string_length", toplevel);
+        let string_drop = FuncChip::from_name("string_drop", toplevel);
+        let string_take = FuncChip::from_name("string_take", toplevel);
+        let substring = FuncChip::from_name("substring", toplevel);
+        let string_lessthan = FuncChip::from_name("string_lessthan", toplevel);
+        let u64_to_string = FuncChip::from_name("u64_to_string", toplevel);
+        let string_to_u64 = FuncChip::from_name("string_to_u64", toplevel);
+        let symbol_name = FuncChip::from_name("symbol_name", toplevel);
+        let string_to_symbol = FuncChip::from_name("string_to_symbol", toplevel);
 
         let expect_eq = |computed: usize, expected: Expect| {
             expected.assert_eq(&computed.to_string());
         };
         expect_eq(lurk_main.width(), expect!["97"]);
-        expect_eq(preallocate_symbols.width(), expect!["180"]);
+        expect_eq(preallocate_symbols.width(), expect!["344"]);
         expect_eq(eval_coroutine_expr.width(), expect!["10"]);
         expect_eq(eval.width(), expect!["78"]);
-        expect_eq(eval_builtin_expr.width(), expect!["146"]);
+        expect_eq(eval_builtin_expr.width(), expect!["155"]);
         expect_eq(eval_apply_builtin.width(), expect!["79"]);
+        expect_eq(eval_catch.width(), expect!["104"]);
         expect_eq(eval_opening_unop.width(), expect!["97"]);
         expect_eq(eval_hide.width(), expect!["115"]);
-        expect_eq(eval_unop.width(), expect!["78"]);
-        expect_eq(eval_binop_num.width(), expect!["107"]);
-        expect_eq(eval_binop_misc.width(), expect!["70"]);
+        expect_eq(eval_unop.width(), expect!["91"]);
+        expect_eq(eval_binop_num.width(), expect!["144"]);
+        expect_eq(eval_binop_misc.width(), expect!["83"]);
+        expect_eq(eval_substring.width(), expect!["109"]);
+        expect_eq(eval_modexp.width(), expect!["131"]);
         expect_eq(eval_begin.width(), expect!["68"]);
         expect_eq(eval_list.width(), expect!["72"]);
         expect_eq(eval_let.width(), expect!["94"]);
@@ -1920,12 +2419,12 @@ mod test {
         expect_eq(coerce_if_sym.width(), expect!["9"]);
         expect_eq(open_comm.width(), expect!["50"]);
         expect_eq(equal.width(), expect!["86"]);
-        expect_eq(equal_inner.width(), expect!["58"]);
+        expect_eq(equal_inner.width(), expect!["59"]);
         expect_eq(car_cdr.width(), expect!["61"]);
         expect_eq(apply.width(), expect!["114"]);
         expect_eq(env_lookup.width(), expect!["52"]);
-        expect_eq(ingress.width(), expect!["104"]);
-        expect_eq(egress.width(), expect!["81"]);
+        expect_eq(ingress.width(), expect!["106"]);
+        expect_eq(egress.width(), expect!["83"]);
         expect_eq(hash3.width(), expect!["493"]);
         expect_eq(hash4.width(), expect!["655"]);
         expect_eq(hash5.width(), expect!["815"]);
@@ -1935,8 +2434,36 @@ mod test {
         expect_eq(u64_divrem.width(), expect!["166"]);
         expect_eq(u64_lessthan.width(), expect!["44"]);
         expect_eq(u64_iszero.width(), expect!["26"]);
+        expect_eq(u64_from_num.width(), expect!["24"]);
+        expect_eq(i64_lessthan.width(), expect!["23"]);
+        expect_eq(i64_divrem.width(), expect!["47"]);
+        expect_eq(i64_negate_if.width(), expect!["17"]);
+        expect_eq(u64_and.width(), expect!["65"]);
+        expect_eq(u64_or.width(), expect!["65"]);
+        expect_eq(u64_xor.width(), expect!["65"]);
+        expect_eq(u64_not.width(), expect!["21"]);
+        expect_eq(u64_shl.width(), expect!["111"]);
+        expect_eq(u64_shr.width(), expect!["111"]);
+        expect_eq(u64_rotl.width(), expect!["221"]);
+        expect_eq(u64_rotr.width(), expect!["221"]);
+        expect_eq(u64_ash.width(), expect!["34"]);
         expect_eq(digest_equal.width(), expect!["38"]);
         expect_eq(big_num_lessthan.width(), expect!["78"]);
+        expect_eq(big_num_add.width(), expect!["1070"]);
+        expect_eq(big_num_sub.width(), expect!["1070"]);
+        expect_eq(big_num_mul.width(), expect!["1938"]);
+        expect_eq(big_num_divrem.width(), expect!["1771"]);
+        expect_eq(big_num_modexp.width(), expect!["1789"]);
+        expect_eq(big_num_modexp_reduced.width(), expect!["6233"]);
+        expect_eq(string_length.width(), expect!["27"]);
+        expect_eq(string_drop.width(), expect!["35"]);
+        expect_eq(string_take.width(), expect!["41"]);
+        expect_eq(substring.width(), expect!["36"]);
+        expect_eq(string_lessthan.width(), expect!["56"]);
+        expect_eq(u64_to_string.width(), expect!["44"]);
+        expect_eq(string_to_u64.width(), expect!["69"]);
+        expect_eq(symbol_name.width(), expect!["68"]);
+        expect_eq(string_to_symbol.width(), expect!["33"]);
     }
 
     #[test]
@@ -1,7 +1,7 @@
 use p3_baby_bear::BabyBear;
 use p3_field::AbstractField;
 use rustc_hash::FxHashSet;
-use strum::{EnumCount, EnumIter};
+use strum::{EnumCount, EnumIter, IntoEnumIterator};
 
 use crate::{
     func,
@@ -13,7 +13,8 @@ use crate::{
 
 use super::{
     chipset::LurkChip,
-    state::{builtin_sym, lurk_sym, BUILTIN_SYMBOLS, LURK_SYMBOLS},
+    error::EvalErr,
+    state::{builtin_sym, lurk_sym, user_package_symbol, BUILTIN_SYMBOLS, LURK_SYMBOLS},
     symbol::Symbol,
     tag::Tag,
     zstore::{ZStore, DIGEST_SIZE},
@@ -41,6 +42,11 @@ impl SymbolsDigests<BabyBear> {
             assert_eq!(zptr.tag, Tag::Sym);
             map.insert(symbol, zptr.digest.into());
         }
+        // the user package, in which `string->symbol` interns symbols
+        let symbol = user_package_symbol();
+        let zptr = zstore.intern_symbol(&symbol, lang_symbols);
+        assert_eq!(zptr.tag, Tag::Sym);
+        map.insert(symbol, zptr.digest.into());
         for name in BUILTIN_SYMBOLS {
             let symbol = builtin_sym(name);
             let zptr = zstore.intern_symbol(&symbol, lang_symbols);
@@ -108,11 +114,21 @@ impl InternalTag {
 ///     let addr = <symbol 1 address>;
 ///     assert_eq!(ptr, addr);
 ///     ...
+///     let nil_tag = InternalTag::Nil;
+///     let nil = <nil address>;
+///     let code = <error 0 code>;
+///     let ptr = store(code, nil_tag, nil);
+///     let addr = <error 0 address>;
+///     assert_eq!(ptr, addr);
+///     ...
 ///     return
 /// }
 /// ```
+///
+/// Payload-less errors are preallocated after the symbols so that
+/// `EvalErr::to_field` can be used as their pointers
 pub fn preallocate_symbols<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F> {
-    let mut ops = Vec::with_capacity(2 * digests.0.len());
+    let mut ops = Vec::with_capacity(4 * (digests.0.len() + EvalErr::COUNT) + 2);
     let name = Ident::User("arr");
     let arr_var = Var {
         name,
@@ -127,6 +143,18 @@ pub fn preallocate_symbols<F: AbstractField>(digests: &SymbolsDigests<F>) -> Fun
         ops.push(OpE::Const(addr_var, addr));
         ops.push(OpE::AssertEq(ptr_var, addr_var, None));
     }
+    let nil_tag_var = Var::atom("nil_tag");
+    let nil_var = Var::atom("nil");
+    let code_var = Var::atom("code");
+    let nil = digests.lurk_symbol_ptr("nil").to_field();
+    ops.push(OpE::Const(nil_tag_var, InternalTag::Nil.to_field()));
+    ops.push(OpE::Const(nil_var, nil));
+    for err in EvalErr::iter() {
+        ops.push(OpE::Const(code_var, err.code().to_field()));
+        ops.push(OpE::Store(ptr_var, [code_var, nil_tag_var, nil_var].into()));
+        ops.push(OpE::Const(addr_var, err.to_field()));
+        ops.push(OpE::AssertEq(ptr_var, addr_var, None));
+    }
     let ops = ops.into();
     let ctrl = CtrlE::return_vars([]);
     FuncE {
@@ -151,6 +179,25 @@ pub fn ingress<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F> {
                     assert_eq!(rest, zeros);
                     return (tag, x)
                 }
+                Tag::Err => {
+                    let (code, rest: [7]) = digest;
+                    if !rest {
+                        let nil_tag = InternalTag::Nil;
+                        let nil = digests.lurk_symbol_ptr("nil");
+                        let ptr = store(code, nil_tag, nil);
+                        return (tag, ptr)
+                    }
+                    let (code_tag_full: [8], code_digest: [8],
+                         payload_tag_full: [8], payload_digest: [8]) = preimg(hash4, digest);
+                    let code_tag = Tag::U64;
+                    let code_tag_expected: [8] = (code_tag, zeros);
+                    assert_eq!(code_tag_full, code_tag_expected);
+                    let (code, code_rest: [7]) = code_digest;
+                    assert_eq!(code_rest, zeros);
+                    let (payload_tag, payload) = call(ingress, payload_tag_full, payload_digest);
+                    let ptr = store(code, payload_tag, payload);
+                    return (tag, ptr)
+                }
                 Tag::Char => {
                     let (bytes: [4], rest: [4]) = digest;
                     range_u8!(bytes);
@@ -159,7 +206,7 @@ pub fn ingress<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F> {
                     let ptr = store(bytes);
                     return (tag, ptr)
                 }
-                Tag::U64 => {
+                Tag::U64, Tag::I64 => {
                     range_u8!(digest);
                     let ptr = store(digest);
                     return (tag, ptr)
@@ -240,11 +287,30 @@ pub fn egress<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F> {
     func!(
         fn egress(tag, val): [9] {
             match tag {
-                Tag::Num, Tag::Err => {
+                Tag::Num => {
                     let padding = [0; 7];
                     let digest: [8] = (val, padding);
                     return (tag, digest)
                 }
+                Tag::Err => {
+                    let padding = [0; 7];
+                    let (code, payload_tag, payload) = load(val);
+                    match payload_tag {
+                        InternalTag::Nil => {
+                            let digest: [8] = (code, padding);
+                            return (tag, digest)
+                        }
+                    };
+                    // errors with payloads are hashed as a pair of the code, as
+                    // a `U64`, and the payload
+                    let (payload_tag, payload_digest: [8]) = call(egress, payload_tag, payload);
+                    let code_tag = Tag::U64;
+                    let code_tag_full: [8] = (code_tag, padding);
+                    let code_digest: [8] = (code, padding);
+                    let payload_tag_full: [8] = (payload_tag, padding);
+                    let digest: [8] = call(hash4, code_tag_full, code_digest, payload_tag_full, payload_digest);
+                    return (tag, digest)
+                }
                 Tag::Char => {
                     let padding = [0; 4];
                     let bytes: [4] = load(val);
@@ -260,7 +326,7 @@ pub fn egress<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F> {
                     let digest = Array(digests.lurk_symbol_digest("t").clone());
                     return (sym_tag, digest)
                 }
-                Tag::Sym, Tag::Builtin, Tag::Coroutine, Tag::Key, Tag::U64, Tag::BigNum, Tag::Comm => {
+                Tag::Sym, Tag::Builtin, Tag::Coroutine, Tag::Key, Tag::U64, Tag::I64, Tag::BigNum, Tag::Comm => {
                     let digest: [8] = load(val);
                     return (tag, digest)
                 }
@@ -1,4 +1,7 @@
-use crate::lair::{chipset::NoChip, expr::FuncE, FxIndexMap, Name};
+use p3_field::PrimeField32;
+use sha2::{Digest, Sha256};
+
+use crate::lair::{chipset::NoChip, encoding::Encode, expr::FuncE, FxIndexMap, Name};
 
 use super::symbol::Symbol;
 
@@ -67,6 +70,42 @@ impl<F, C> Lang<F, C> {
     }
 }
 
+impl<F: PrimeField32, C> Lang<F, C> {
+    /// A content-addressed identifier for the `Lang`: the hex-encoded SHA-256
+    /// digest of the canonical encoding of its coroutine symbols, arities and
+    /// Lair functions and of the names of its gadgets, in order
+    pub fn id(&self) -> String {
+        let mut bytes = vec![];
+        bytes.extend((self.coroutines.len() as u64).to_le_bytes());
+        for (symbol, coroutine) in &self.coroutines {
+            let Coroutine {
+                lurk_arity,
+                uses_env,
+                func_expr,
+            } = coroutine;
+            let symbol = symbol.to_string();
+            bytes.extend((symbol.len() as u64).to_le_bytes());
+            bytes.extend(symbol.as_bytes());
+            bytes.extend((*lurk_arity as u64).to_le_bytes());
+            uses_env.encode(&mut bytes);
+            func_expr.encode(&mut bytes);
+        }
+        bytes.extend((self.gadgets.len() as u64).to_le_bytes());
+        for name in self.gadgets.keys() {
+            name.encode(&mut bytes);
+        }
+        Sha256::digest(bytes)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
+/// Whether `id` is well-formed as an output of `Lang::id`
+pub fn is_lang_id(id: &str) -> bool {
+    id.len() == 64 && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
 impl<F> Lang<F, NoChip> {
     #[inline]
     pub fn empty() -> Lang<F, NoChip> {
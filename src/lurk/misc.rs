@@ -1,6 +1,10 @@
 use p3_field::AbstractField;
 
-use crate::{func, lair::expr::FuncE};
+use crate::{
+    func,
+    lair::expr::FuncE,
+    lurk::{error::EvalErr, tag::Tag},
+};
 
 pub fn hash3<F>() -> FuncE<F> {
     func!(
@@ -89,6 +93,16 @@ pub fn u64_lessthan<F>() -> FuncE<F> {
     )
 }
 
+pub fn u64_from_num<F>() -> FuncE<F> {
+    func!(
+        fn u64_from_num(num): [1] {
+            let c: [8] = extern_call(u64_from_num, num);
+            let c = store(c);
+            return c
+        }
+    )
+}
+
 pub fn u64_iszero<F>() -> FuncE<F> {
     func!(
         fn u64_iszero(a): [1] {
@@ -100,6 +114,241 @@ pub fn u64_iszero<F>() -> FuncE<F> {
     )
 }
 
+pub fn u64_and<F>() -> FuncE<F> {
+    func!(
+        fn u64_and(a, b): [1] {
+            let a: [8] = load(a);
+            let b: [8] = load(b);
+            let c: [8] = extern_call(u64_and, a, b);
+            let c = store(c);
+            return c
+        }
+    )
+}
+
+pub fn u64_or<F>() -> FuncE<F> {
+    func!(
+        fn u64_or(a, b): [1] {
+            let a: [8] = load(a);
+            let b: [8] = load(b);
+            let c: [8] = extern_call(u64_or, a, b);
+            let c = store(c);
+            return c
+        }
+    )
+}
+
+pub fn u64_xor<F>() -> FuncE<F> {
+    func!(
+        fn u64_xor(a, b): [1] {
+            let a: [8] = load(a);
+            let b: [8] = load(b);
+            let c: [8] = extern_call(u64_xor, a, b);
+            let c = store(c);
+            return c
+        }
+    )
+}
+
+pub fn u64_not<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn u64_not(a): [1] {
+            let a: [8] = load(a);
+            // flipping the bits of a byte doesn't need a lookup
+            let ones = [255; 8];
+            let b = sub(ones, a);
+            let b = store(b);
+            return b
+        }
+    )
+}
+
+/// Shifts a `U64` left by `n` bits, where `n` points to a `U64`. Shifting by 64
+/// bits or more clears all of them.
+pub fn u64_shl<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn u64_shl(a, n): [1] {
+            let o = 0;
+            let width = 64;
+            let width = store(width, o, o, o, o, o, o, o);
+            let in_range = call(u64_lessthan, n, width);
+            if in_range {
+                let a: [8] = load(a);
+                let n: [8] = load(n);
+                let c: [8] = extern_call(u64_shl, a, n);
+                let c = store(c);
+                return c
+            }
+            let zero = [0; 8];
+            let zero = store(zero);
+            return zero
+        }
+    )
+}
+
+/// Shifts a `U64` right by `n` bits, where `n` points to a `U64`. Shifting by
+/// 64 bits or more clears all of them.
+pub fn u64_shr<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn u64_shr(a, n): [1] {
+            let o = 0;
+            let width = 64;
+            let width = store(width, o, o, o, o, o, o, o);
+            let in_range = call(u64_lessthan, n, width);
+            if in_range {
+                let a: [8] = load(a);
+                let n: [8] = load(n);
+                let c: [8] = extern_call(u64_shr, a, n);
+                let c = store(c);
+                return c
+            }
+            let zero = [0; 8];
+            let zero = store(zero);
+            return zero
+        }
+    )
+}
+
+/// Rotates a `U64` left by `n` bits, where `n` points to a `U64`, modulo 64
+pub fn u64_rotl<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn u64_rotl(a, n): [1] {
+            let a: [8] = load(a);
+            let n: [8] = load(n);
+            // the shift chips take the count modulo 64, so shifting by `-n`
+            // moves the bits that wrap around, and leaves `a` as is when `n`
+            // is a multiple of 64
+            let zero = [0; 8];
+            let neg_n: [8] = extern_call(u64_sub, zero, n);
+            let hi: [8] = extern_call(u64_shl, a, n);
+            let lo: [8] = extern_call(u64_shr, a, neg_n);
+            let c: [8] = extern_call(u64_or, hi, lo);
+            let c = store(c);
+            return c
+        }
+    )
+}
+
+/// Rotates a `U64` right by `n` bits, where `n` points to a `U64`, modulo 64
+pub fn u64_rotr<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn u64_rotr(a, n): [1] {
+            let a: [8] = load(a);
+            let n: [8] = load(n);
+            let zero = [0; 8];
+            let neg_n: [8] = extern_call(u64_sub, zero, n);
+            let lo: [8] = extern_call(u64_shr, a, n);
+            let hi: [8] = extern_call(u64_shl, a, neg_n);
+            let c: [8] = extern_call(u64_or, hi, lo);
+            let c = store(c);
+            return c
+        }
+    )
+}
+
+/// Implements `(ash a n)`, shifting the `U64` `a` by `n` bits. The count `n`
+/// can be a `U64`, which shifts to the left, or an `I64`, which shifts to the
+/// right when negative.
+pub fn u64_ash<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn u64_ash(a_tag, a, n_tag, n): [2] {
+            let err_tag = Tag::Err;
+            let u64_tag = Tag::U64;
+            let not_u64 = sub(a_tag, u64_tag);
+            if not_u64 {
+                let err = EvalErr::NotU64;
+                return (err_tag, err)
+            }
+            match n_tag {
+                Tag::U64 => {
+                    let res = call(u64_shl, a, n);
+                    return (u64_tag, res)
+                }
+                Tag::I64 => {
+                    let o = 0;
+                    let msb = 128;
+                    let min = store(o, o, o, o, o, o, o, msb);
+                    // negative numbers are the ones from 2^63 onwards when seen as unsigned
+                    let is_pos = call(u64_lessthan, n, min);
+                    if is_pos {
+                        let res = call(u64_shl, a, n);
+                        return (u64_tag, res)
+                    }
+                    let zero = [0; 8];
+                    let zero = store(zero);
+                    let n = call(u64_sub, zero, n);
+                    let res = call(u64_shr, a, n);
+                    return (u64_tag, res)
+                }
+            };
+            let err = EvalErr::NotU64;
+            return (err_tag, err)
+        }
+    )
+}
+
+/// Signed comparison of two's complement `I64`s. Adding 2^63 to both operands
+/// flips their sign bits, which maps the signed order onto the unsigned one.
+pub fn i64_lessthan<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn i64_lessthan(a, b): [1] {
+            let o = 0;
+            let msb = 128;
+            let bias = store(o, o, o, o, o, o, o, msb);
+            let a = call(u64_add, a, bias);
+            let b = call(u64_add, b, bias);
+            let res = call(u64_lessthan, a, b);
+            return res
+        }
+    )
+}
+
+/// Signed division of two's complement `I64`s, truncating towards zero. The
+/// remainder has the sign of the dividend, as in Rust and OCaml. The last
+/// output flags the overflowing `i64::MIN / -1`, which callers must reject.
+pub fn i64_divrem<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn i64_divrem(a, b): [3] {
+            let o = 0;
+            let msb = 128;
+            let min = store(o, o, o, o, o, o, o, msb);
+            // negative numbers are the ones from 2^63 onwards when seen as unsigned
+            let a_is_pos = call(u64_lessthan, a, min);
+            let b_is_pos = call(u64_lessthan, b, min);
+            let one = 1;
+            let a_is_neg = sub(one, a_is_pos);
+            let b_is_neg = sub(one, b_is_pos);
+            let a_abs = call(i64_negate_if, a, a_is_neg);
+            let b_abs = call(i64_negate_if, b, b_is_neg);
+            let (quot, rem) = call(u64_divrem, a_abs, b_abs);
+            // the quotient magnitude only reaches 2^63 for `i64::MIN / ±1`,
+            // which is out of range when the divisor is negative
+            let quot_fits = call(u64_lessthan, quot, min);
+            let quot_too_big = sub(one, quot_fits);
+            let overflow = mul(quot_too_big, b_is_neg);
+            let signs_differ = sub(a_is_neg, b_is_neg);
+            let quot = call(i64_negate_if, quot, signs_differ);
+            let rem = call(i64_negate_if, rem, a_is_neg);
+            return (quot, rem, overflow)
+        }
+    )
+}
+
+/// Negates an `I64` if `neg` is non-zero
+pub fn i64_negate_if<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn i64_negate_if(a, neg): [1] {
+            if neg {
+                let o = 0;
+                let zero = store(o, o, o, o, o, o, o, o);
+                let res = call(u64_sub, zero, a);
+                return res
+            }
+            return a
+        }
+    )
+}
+
 pub fn digest_equal<F: AbstractField>() -> FuncE<F> {
     func!(
         fn digest_equal(a, b): [1] {
@@ -126,3 +375,94 @@ pub fn big_num_lessthan<F>() -> FuncE<F> {
         }
     )
 }
+
+pub fn big_num_add<F>() -> FuncE<F> {
+    func!(
+        fn big_num_add(a, b): [1] {
+            let a: [8] = load(a);
+            let b: [8] = load(b);
+            let c: [8] = extern_call(big_num_add, a, b);
+            let c = store(c);
+            return c
+        }
+    )
+}
+
+pub fn big_num_sub<F>() -> FuncE<F> {
+    func!(
+        fn big_num_sub(a, b): [1] {
+            let a: [8] = load(a);
+            let b: [8] = load(b);
+            let c: [8] = extern_call(big_num_sub, a, b);
+            let c = store(c);
+            return c
+        }
+    )
+}
+
+pub fn big_num_mul<F>() -> FuncE<F> {
+    func!(
+        fn big_num_mul(a, b): [1] {
+            let a: [8] = load(a);
+            let b: [8] = load(b);
+            let c: [8] = extern_call(big_num_mul, a, b);
+            let c = store(c);
+            return c
+        }
+    )
+}
+
+pub fn big_num_divrem<F>() -> FuncE<F> {
+    func!(
+        fn big_num_divrem(a, b): [2] {
+            let a: [8] = load(a);
+            let b: [8] = load(b);
+            let (q: [8], r: [8]) = extern_call(big_num_divrem, a, b);
+            let q = store(q);
+            let r = store(r);
+            return (q, r)
+        }
+    )
+}
+
+/// Computes `base^exp` modulo `modulus`, which must be non-zero. All arguments
+/// point to `BigNum` digests.
+pub fn big_num_modexp<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn big_num_modexp(base, exp, modulus): [1] {
+            let base: [8] = load(base);
+            let exp: [8] = load(exp);
+            let modulus: [8] = load(modulus);
+            // `big_num_mulmod` needs its operands to be smaller than the modulus
+            let (_quot: [8], base: [8]) = extern_call(big_num_divrem, base, modulus);
+            let res: [8] = call(big_num_modexp_reduced, base, exp, modulus);
+            let res = store(res);
+            return res
+        }
+    )
+}
+
+/// Square-and-multiply over digests, where `base` is already reduced modulo
+/// `modulus`
+pub fn big_num_modexp_reduced<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn big_num_modexp_reduced(base: [8], exp: [8], modulus: [8]): [8] {
+            let o = 0;
+            if !exp {
+                // reducing one covers the case where the modulus is one
+                let one = 1;
+                let (_quot: [8], res: [8]) = extern_call(big_num_divrem, one, o, o, o, o, o, o, o, modulus);
+                return res
+            }
+            let two = 2;
+            let (half: [8], bit, _bit_rest: [7]) = extern_call(big_num_divrem, exp, two, o, o, o, o, o, o, o);
+            let acc: [8] = call(big_num_modexp_reduced, base, half, modulus);
+            let acc: [8] = extern_call(big_num_mulmod, acc, acc, modulus);
+            if bit {
+                let acc: [8] = extern_call(big_num_mulmod, acc, base, modulus);
+                return acc
+            }
+            return acc
+        }
+    )
+}
@@ -15,10 +15,13 @@ pub mod parser;
 pub mod poseidon;
 pub mod stark_machine;
 pub mod state;
+pub mod string;
 pub mod symbol;
 pub mod syntax;
 pub mod tag;
 pub mod u64;
+pub mod verifier;
+pub mod zdag;
 pub mod zstore;
 
 #[cfg(test)]
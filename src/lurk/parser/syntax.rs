@@ -203,6 +203,24 @@ fn parse_numeric_suffix() -> impl Fn(Span<'_>) -> ParseResult<'_, Span<'_>> {
     }
 }
 
+/// Builds an `i64` from its sign and magnitude. Out of range literals fail
+/// without backtracking so they aren't read as symbols.
+fn signed_from_magnitude(neg: bool, x: u64, from: Span<'_>) -> ParseResult<'_, i64> {
+    let res = if neg {
+        0i64.checked_sub_unsigned(x)
+    } else {
+        i64::try_from(x).ok()
+    };
+    match res {
+        Some(x) => Ok((from, x)),
+        None => {
+            let sign = if neg { "-" } else { "" };
+            let err = ParseErrorKind::Custom(format!("{sign}{x} is out of range for i64"));
+            Err(nom::Err::Failure(ParseError::new(from, err)))
+        }
+    }
+}
+
 fn parse_numeric<F: Field>() -> impl Fn(Span<'_>) -> ParseResult<'_, Syntax<F>> {
     move |from: Span<'_>| {
         let (i, neg) = opt(tag("-"))(from)?;
@@ -244,8 +262,9 @@ fn parse_numeric<F: Field>() -> impl Fn(Span<'_>) -> ParseResult<'_, Syntax<F>>
                     ParseError::res(u64::from_str_radix(&digits, base.radix()), from, |e| {
                         ParseErrorKind::ParseIntErr(e)
                     })?;
+                let (_, x) = signed_from_magnitude(neg.is_some(), x, from)?;
                 let pos = Pos::from_upto(from, upto);
-                Ok((upto, Syntax::I64(pos, neg.is_some(), x)))
+                Ok((upto, Syntax::I64(pos, x)))
             }
             None | Some("u64") => {
                 let (_, x) =
@@ -253,8 +272,12 @@ fn parse_numeric<F: Field>() -> impl Fn(Span<'_>) -> ParseResult<'_, Syntax<F>>
                         ParseErrorKind::ParseIntErr(e)
                     })?;
                 let pos = Pos::from_upto(from, upto);
+                // Unsuffixed negative literals read as `I64`s. Since arithmetic
+                // doesn't mix `I64`s and `U64`s, `(+ -1 1)` is an error and must
+                // be written as `(+ -1 1i64)`
                 if neg.is_some() {
-                    Ok((upto, Syntax::I64(pos, neg.is_some(), x)))
+                    let (_, x) = signed_from_magnitude(true, x, from)?;
+                    Ok((upto, Syntax::I64(pos, x)))
                 } else {
                     Ok((upto, Syntax::U64(pos, x)))
                 }
@@ -524,4 +547,18 @@ mod test {
         assert!(rest.is_empty());
         assert_eq!(format!("{syn}"), "#0x123456789");
     }
+
+    #[test]
+    fn test_i64() {
+        let state = State::init_lurk_state().rccell();
+        let parse =
+            |input: &'static str| parse_syntax::<BabyBear>(state.clone(), false)(input.into());
+        let (rest, syn) = parse("-9223372036854775808").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(format!("{syn}"), "-9223372036854775808i64");
+        let (_, syn) = parse("0x7fi64").unwrap();
+        assert_eq!(format!("{syn}"), "127i64");
+        assert!(parse("-9223372036854775809").is_err());
+        assert!(parse("9223372036854775808i64").is_err());
+    }
 }
@@ -249,6 +249,12 @@ pub fn meta_package_symbol() -> Symbol {
     lurk_sym(META_PACKAGE_NAME)
 }
 
+/// Returns the symbol corresponding to the name of the user package
+#[inline]
+pub fn user_package_symbol() -> Symbol {
+    Symbol::sym(&[USER_PACKAGE_NAME])
+}
+
 /// Returns the symbol in the user package given the symbol name
 #[inline]
 pub fn user_sym(name: &str) -> Symbol {
@@ -269,7 +275,7 @@ const USER_PACKAGE_NAME: &str = "lurk-user";
 
 pub(crate) const LURK_SYMBOLS: [&str; 3] = ["nil", "t", "&rest"];
 
-pub(crate) const BUILTIN_SYMBOLS: [&str; 41] = [
+pub(crate) const BUILTIN_SYMBOLS: [&str; 56] = [
     "atom",
     "apply",
     "begin",
@@ -311,9 +317,24 @@ pub(crate) const BUILTIN_SYMBOLS: [&str; 41] = [
     ">=",
     "breakpoint",
     "fail",
+    "catch",
+    "string-length",
+    "substring",
+    "string<",
+    "string->symbol",
+    "symbol->string",
+    "num->string",
+    "logand",
+    "logor",
+    "logxor",
+    "lognot",
+    "ash",
+    "rotl",
+    "rotr",
+    "modexp",
 ];
 
-pub(crate) const META_SYMBOLS: [&str; 39] = [
+pub(crate) const META_SYMBOLS: [&str; 52] = [
     "def",
     "defq",
     "defrec",
@@ -332,6 +353,8 @@ pub(crate) const META_SYMBOLS: [&str; 39] = [
     "set-env",
     "erase-from-env",
     "prove",
+    "prove-disclosing",
+    "prove-batch",
     "verify",
     "defpackage",
     "import",
@@ -341,8 +364,12 @@ pub(crate) const META_SYMBOLS: [&str; 39] = [
     "chain",
     "transition",
     "inspect",
+    "proofs",
+    "tag-proof",
     "dump-expr",
     "load-expr",
+    "to-json",
+    "load-json",
     "defprotocol",
     "prove-protocol",
     "verify-protocol",
@@ -351,6 +378,13 @@ pub(crate) const META_SYMBOLS: [&str; 39] = [
     "microchain-get-state",
     "microchain-transition",
     "microchain-verify",
+    "microchain-get-history",
+    "microchain-verify-range",
+    "microchain-tail",
+    "microchain-export",
+    "microchain-import",
+    "microchain-keygen",
+    "microchain-set-key",
     "load-ocaml",
     "load-ocaml-expr",
 ];
@@ -0,0 +1,274 @@
+//! Lair functions backing the string builtins, shared by both evaluators.
+//!
+//! Strings are represented as in `ingress`: the empty string is the pointer
+//! `0` and non-empty strings point to a `(char_tag, char, str_tag, str)` tuple.
+
+use p3_field::AbstractField;
+
+use crate::{
+    func,
+    lair::expr::FuncE,
+    lurk::{error::EvalErr, ingress::SymbolsDigests, state::user_package_symbol, tag::Tag},
+};
+
+/// Counts the characters of a string, returning a pointer to a `U64`
+pub fn string_length<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn string_length(str): [1] {
+            if !str {
+                let zero = [0; 8];
+                let zero = store(zero);
+                return zero
+            }
+            let (_head_tag, _head, _rest_tag, rest) = load(str);
+            let len = call(string_length, rest);
+            let o = 0;
+            let i = 1;
+            let one = store(i, o, o, o, o, o, o, o);
+            let len = call(u64_add, len, one);
+            return len
+        }
+    )
+}
+
+/// Drops the first `n` characters of a string, where `n` points to a `U64`
+pub fn string_drop<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn string_drop(str, n): [2] {
+            let is_zero = call(u64_iszero, n);
+            if is_zero {
+                let str_tag = Tag::Str;
+                return (str_tag, str)
+            }
+            if !str {
+                let err_tag = Tag::Err;
+                let err = EvalErr::IndexOutOfBounds;
+                return (err_tag, err)
+            }
+            let (_head_tag, _head, _rest_tag, rest) = load(str);
+            let o = 0;
+            let i = 1;
+            let one = store(i, o, o, o, o, o, o, o);
+            let n = call(u64_sub, n, one);
+            let (res_tag, res) = call(string_drop, rest, n);
+            return (res_tag, res)
+        }
+    )
+}
+
+/// Takes the first `n` characters of a string, where `n` points to a `U64`
+pub fn string_take<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn string_take(str, n): [2] {
+            let str_tag = Tag::Str;
+            let is_zero = call(u64_iszero, n);
+            if is_zero {
+                let empty = 0;
+                return (str_tag, empty)
+            }
+            if !str {
+                let err_tag = Tag::Err;
+                let err = EvalErr::IndexOutOfBounds;
+                return (err_tag, err)
+            }
+            let (head_tag, head, _rest_tag, rest) = load(str);
+            let o = 0;
+            let i = 1;
+            let one = store(i, o, o, o, o, o, o, o);
+            let n = call(u64_sub, n, one);
+            let (rest_tag, rest) = call(string_take, rest, n);
+            match rest_tag {
+                Tag::Err => {
+                    return (rest_tag, rest)
+                }
+            };
+            let res = store(head_tag, head, rest_tag, rest);
+            return (str_tag, res)
+        }
+    )
+}
+
+/// Implements `(substring str start end)`, returning the characters from
+/// index `start` (inclusive) to index `end` (exclusive)
+pub fn substring<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn substring(str_tag, str, start_tag, start, end_tag, end): [2] {
+            let err_tag = Tag::Err;
+            let expected_str_tag = Tag::Str;
+            let not_str = sub(str_tag, expected_str_tag);
+            if not_str {
+                let err = EvalErr::NotString;
+                return (err_tag, err)
+            }
+            let u64_tag = Tag::U64;
+            let start_not_u64 = sub(start_tag, u64_tag);
+            let end_not_u64 = sub(end_tag, u64_tag);
+            if start_not_u64 {
+                let err = EvalErr::NotU64;
+                return (err_tag, err)
+            }
+            if end_not_u64 {
+                let err = EvalErr::NotU64;
+                return (err_tag, err)
+            }
+            let is_reversed = call(u64_lessthan, end, start);
+            if is_reversed {
+                let err = EvalErr::IndexOutOfBounds;
+                return (err_tag, err)
+            }
+            let (suffix_tag, suffix) = call(string_drop, str, start);
+            match suffix_tag {
+                Tag::Err => {
+                    return (suffix_tag, suffix)
+                }
+            };
+            let len = call(u64_sub, end, start);
+            let (res_tag, res) = call(string_take, suffix, len);
+            return (res_tag, res)
+        }
+    )
+}
+
+/// Whether a string comes before another in lexicographic order, comparing
+/// characters by their code points
+pub fn string_lessthan<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn string_lessthan(a, b): [1] {
+            if !b {
+                let zero = 0;
+                return zero
+            }
+            if !a {
+                let one = 1;
+                return one
+            }
+            let (_a_char_tag, a_char, _a_rest_tag, a_rest) = load(a);
+            let (_b_char_tag, b_char, _b_rest_tag, b_rest) = load(b);
+            let a_bytes: [4] = load(a_char);
+            let b_bytes: [4] = load(b_char);
+            let diff = sub(a_bytes, b_bytes);
+            if diff {
+                // comparing UTF-8 bytes from the first one amounts to comparing
+                // code points, so the first byte is the most significant
+                let (a0, a1, a2, a3) = a_bytes;
+                let (b0, b1, b2, b3) = b_bytes;
+                let o = 0;
+                let a_code = store(a3, a2, a1, a0, o, o, o, o);
+                let b_code = store(b3, b2, b1, b0, o, o, o, o);
+                let res = call(u64_lessthan, a_code, b_code);
+                return res
+            }
+            let res = call(string_lessthan, a_rest, b_rest);
+            return res
+        }
+    )
+}
+
+/// Prepends the decimal digits of a `U64` to the string `acc`
+pub fn u64_to_string<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn u64_to_string(n, acc): [1] {
+            let o = 0;
+            let ten = 10;
+            let ten = store(ten, o, o, o, o, o, o, o);
+            let (quot, rem) = call(u64_divrem, n, ten);
+            let (digit, _rest: [7]) = load(rem);
+            let zero_code = 48;
+            let code = add(digit, zero_code);
+            let digit_char = store(code, o, o, o);
+            let char_tag = Tag::Char;
+            let str_tag = Tag::Str;
+            let acc = store(char_tag, digit_char, str_tag, acc);
+            let quot_is_zero = call(u64_iszero, quot);
+            if quot_is_zero {
+                return acc
+            }
+            let res = call(u64_to_string, quot, acc);
+            return res
+        }
+    )
+}
+
+/// Parses the decimal digits of a string as a `U64`, accumulating on `acc`,
+/// which points to a `U64`. Errors on characters other than digits and on
+/// numbers that don't fit in 64 bits.
+pub fn string_to_u64<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn string_to_u64(str, acc): [2] {
+            if !str {
+                let u64_tag = Tag::U64;
+                return (u64_tag, acc)
+            }
+            let err_tag = Tag::Err;
+            let err = EvalErr::CantCastToU64;
+            let (_head_tag, head, _rest_tag, rest) = load(str);
+            let (code, _code_rest: [3]) = load(head);
+            match code {
+                48, 49, 50, 51, 52, 53, 54, 55, 56, 57 => {
+                    // `acc * 10` overflows iff `acc` is bigger than `(2^64 - 1) / 10`
+                    let o = 0;
+                    let max_lo = 153;
+                    let max_hi = 25;
+                    let max = store(max_lo, max_lo, max_lo, max_lo, max_lo, max_lo, max_lo, max_hi);
+                    let too_big = call(u64_lessthan, max, acc);
+                    if too_big {
+                        return (err_tag, err)
+                    }
+                    let ten = 10;
+                    let ten = store(ten, o, o, o, o, o, o, o);
+                    let acc = call(u64_mul, acc, ten);
+                    let zero_code = 48;
+                    let digit = sub(code, zero_code);
+                    let digit = store(digit, o, o, o, o, o, o, o);
+                    let sum = call(u64_add, acc, digit);
+                    // the sum wraps around on overflow
+                    let overflows = call(u64_lessthan, sum, acc);
+                    if overflows {
+                        return (err_tag, err)
+                    }
+                    let (res_tag, res) = call(string_to_u64, rest, sum);
+                    return (res_tag, res)
+                }
+            };
+            return (err_tag, err)
+        }
+    )
+}
+
+/// Returns the name of a symbol, which is the last element of its path, as a
+/// string. The symbol can have any symbol-like tag, including the internal
+/// ones for `nil` and `t`.
+pub fn symbol_name<F: AbstractField>() -> FuncE<F> {
+    func!(
+        fn symbol_name(sym_tag, sym): [1] {
+            let (_sym_tag, digest: [8]) = call(egress, sym_tag, sym);
+            // the root symbol has an empty path
+            if !digest {
+                let empty = 0;
+                return empty
+            }
+            let (name_tag_full: [8], name_digest: [8],
+                 _parent_tag_full: [8], _parent_digest: [8]) = preimg(hash4, digest);
+            let (_name_tag, name) = call(ingress, name_tag_full, name_digest);
+            return name
+        }
+    )
+}
+
+/// Interns a string as a symbol in the user package
+pub fn string_to_symbol<F: AbstractField>(digests: &SymbolsDigests<F>) -> FuncE<F> {
+    func!(
+        fn string_to_symbol(str): [1] {
+            let str_tag = Tag::Str;
+            let (_str_tag, str_digest: [8]) = call(egress, str_tag, str);
+            let padding = [0; 7];
+            let str_tag_full: [8] = (str_tag, padding);
+            let sym_tag = Tag::Sym;
+            let sym_tag_full: [8] = (sym_tag, padding);
+            let package_digest = Array(digests.symbol_digest(&user_package_symbol()).clone());
+            let digest: [8] = call(hash4, str_tag_full, str_digest, sym_tag_full, package_digest);
+            let sym = store(digest);
+            return sym
+        }
+    )
+}
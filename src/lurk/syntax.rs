@@ -14,7 +14,7 @@ pub enum Syntax<F> {
     /// A u64 integer: 1, 0xff, 1u64, 0xffu64
     U64(Pos, u64),
     /// A i64 integer: -1, -0xff, 1i64, 0xffi64, -1i64, -0xffi64
-    I64(Pos, bool, u64),
+    I64(Pos, i64),
     /// A big numeric type stored in little-endian
     BigNum(Pos, [F; DIGEST_SIZE]),
     /// A commitment hash digest stored in little-endian
@@ -41,7 +41,7 @@ impl<F> Syntax<F> {
         match self {
             Self::Num(pos, _)
             | Self::U64(pos, _)
-            | Self::I64(pos, ..)
+            | Self::I64(pos, _)
             | Self::BigNum(pos, _)
             | Self::Comm(pos, _)
             | Self::Symbol(pos, _)
@@ -60,7 +60,7 @@ impl<F: fmt::Display + PrimeField> fmt::Display for Syntax<F> {
         match self {
             Self::Num(_, x) => write!(f, "{x}"),
             Self::U64(_, x) => write!(f, "{x}u64"),
-            Self::I64(_, sign, x) => write!(f, "{}{x}i64", if *sign { "-" } else { "" }),
+            Self::I64(_, x) => write!(f, "{x}i64"),
             Self::BigNum(_, c) => write!(f, "#{:#x}", field_elts_to_biguint(c)),
             Self::Comm(_, c) => write!(f, "#c{:#x}", field_elts_to_biguint(c)),
             Self::Symbol(_, x) => write!(f, "{x}"),
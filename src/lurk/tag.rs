@@ -36,6 +36,7 @@ pub enum Tag {
     Env,
     Fix,
     Err,
+    I64,
 }
 
 impl Tag {
@@ -66,7 +67,7 @@ mod test {
 
     #[test]
     fn test_strum() {
-        assert_eq!(15, Tag::COUNT);
+        assert_eq!(16, Tag::COUNT);
         assert_eq!(Tag::COUNT, Tag::iter().count());
     }
 
@@ -133,10 +133,19 @@ fn trivial_a_1_env(zstore: &mut ZStore<F, LurkChip>) -> ZPtr<F> {
     zstore.intern_env(a, one, empty_env)
 }
 
+fn unbound_a_err(zstore: &mut ZStore<F, LurkChip>) -> ZPtr<F> {
+    let a = zstore.intern_symbol_no_lang(&user_sym("a"));
+    zstore.intern_error_with_payload(EvalErr::UnboundVar, a)
+}
+
 fn uint(u: u64) -> ZPtr<F> {
     ZPtr::u64(u)
 }
 
+fn int(i: i64) -> ZPtr<F> {
+    ZPtr::i64(i)
+}
+
 // self-evaluating
 test!(test_num, "1", |_| uint(1));
 test!(test_char, "'a'", |_| ZPtr::char('a'));
@@ -185,10 +194,8 @@ test!(
     "((lambda (x &rest y) (car (cdr y))) 1 2 3 4)",
     |_| uint(3)
 );
-test!(test_app_err, "(a)", |_| ZPtr::err(EvalErr::UnboundVar));
-test!(test_app_err2, "((lambda () a) 2)", |_| ZPtr::err(
-    EvalErr::UnboundVar
-));
+test!(test_app_err, "(a)", unbound_a_err);
+test!(test_app_err2, "((lambda () a) 2)", unbound_a_err);
 // TODO FIXME
 // test!(test_app_err3, "(apply (lambda (x) x) 1)", |_| ZPtr::err(
 //     EvalErr::ArgsNotList
@@ -206,6 +213,59 @@ test!(test_sub, "(- 5 2)", |_| uint(3));
 test!(test_mul, "(* 2 3)", |_| uint(6));
 test!(test_div, "(/ 6 3)", |_| uint(2));
 test!(test_arith, "(+ (* 2 2) (* 2 3))", |_| uint(10));
+test!(test_i64_add, "(+ -3 5i64)", |_| int(2));
+test!(test_i64_sub, "(- 2i64 5i64)", |_| int(-3));
+test!(test_i64_mul, "(* -3 4i64)", |_| int(-12));
+test!(test_i64_div, "(/ -7 2i64)", |_| int(-3));
+test!(test_i64_div2, "(/ 7i64 -2)", |_| int(-3));
+test!(test_i64_mod, "(% -7 2i64)", |_| int(-1));
+test!(test_i64_less, "(< -1 0i64)", |z| *z.t());
+test!(test_i64_less2, "(< 0i64 -1)", |z| *z.nil());
+test!(
+    test_i64_greater_eq,
+    "(>= -9223372036854775808 9223372036854775807i64)",
+    |z| *z.nil()
+);
+test!(test_i64_div_min, "(/ -9223372036854775808 1i64)", |_| int(
+    i64::MIN
+));
+test!(test_i64_div_overflow, "(/ -9223372036854775808 -1)", |_| {
+    ZPtr::err(EvalErr::InvalidArg)
+});
+test!(test_i64_mod_overflow, "(% -9223372036854775808 -1)", |_| {
+    ZPtr::err(EvalErr::InvalidArg)
+});
+test!(test_i64_div_by_zero, "(/ -1 0i64)", |_| ZPtr::err(
+    EvalErr::DivByZero
+));
+// negative literals are `I64`s, which don't mix with `U64`s
+test!(test_i64_mixed, "(+ -1 1)", |_| ZPtr::err(
+    EvalErr::InvalidArg
+));
+test!(test_logand, "(logand 12 10)", |_| uint(8));
+test!(test_logor, "(logor 12 10)", |_| uint(14));
+test!(test_logxor, "(logxor 12 10)", |_| uint(6));
+test!(test_lognot, "(lognot 0)", |_| uint(u64::MAX));
+test!(test_logand_not_u64, "(logand 1 'a)", |_| ZPtr::err(
+    EvalErr::NotU64
+));
+test!(test_ash, "(ash 3 62)", |_| uint(3 << 62));
+test!(test_ash_negative, "(ash 48 -4)", |_| uint(3));
+test!(test_ash_positive_i64, "(ash 3 4i64)", |_| uint(48));
+test!(test_ash_overflow, "(ash 1 64)", |_| uint(0));
+test!(
+    test_ash_negative_overflow,
+    "(ash 18446744073709551615 -64)",
+    |_| uint(0)
+);
+test!(test_rotl, "(rotl 9223372036854775809 1)", |_| uint(3));
+test!(test_rotl_wrap, "(rotl 3 65)", |_| uint(6));
+test!(test_rotl_full, "(rotl 5 64)", |_| uint(5));
+test!(test_rotr, "(rotr 1 1)", |_| uint(1 << 63));
+test!(test_rotr_zero, "(rotr 6 0)", |_| uint(6));
+test!(test_rotr_not_u64, "(rotr 1 'a)", |_| ZPtr::err(
+    EvalErr::NotU64
+));
 test!(test_num_eq, "(= 0 1)", |z| *z.nil());
 test!(test_num_eq2, "(= 1 1)", |z| *z.t());
 test!(
@@ -269,6 +329,38 @@ test!(test_car, "(car (cons 0 1))", |_| uint(0));
 test!(test_cdr, "(cdr (cons 0 1))", |_| uint(1));
 test!(test_strcons, "(strcons 'a' \"bc\")", |z| z
     .intern_string("abc"));
+test!(test_string_length, "(string-length \"abc\")", |_| uint(3));
+test!(test_substring, "(substring \"hello\" 1 3)", |z| z
+    .intern_string("el"));
+test!(test_substring_err, "(substring \"hello\" 3 6)", |_| {
+    ZPtr::err(EvalErr::IndexOutOfBounds)
+});
+test!(test_string_less1, "(string< \"abc\" \"abd\")", |z| *z.t());
+test!(test_string_less2, "(string< \"abc\" \"ab\")", |z| *z.nil());
+test!(test_string_less3, "(string< \"ÿ\" \"Ā\")", |z| *z.t());
+test!(test_string_less4, "(string< \"Ā\" \"ÿ\")", |z| *z.nil());
+test!(test_num_to_string, "(num->string 120)", |z| z
+    .intern_string("120"));
+test!(test_num_to_string2, "(num->string 2013265920n)", |z| z
+    .intern_string("2013265920"));
+test!(test_string_to_u64, "(u64 \"18446744073709551615\")", |_| {
+    uint(u64::MAX)
+});
+test!(
+    test_string_to_u64_err1,
+    "(u64 \"18446744073709551616\")",
+    |_| ZPtr::err(EvalErr::CantCastToU64)
+);
+test!(test_string_to_u64_err2, "(u64 \"12a\")", |_| {
+    ZPtr::err(EvalErr::CantCastToU64)
+});
+test!(test_string_to_u64_err3, "(u64 \"\")", |_| {
+    ZPtr::err(EvalErr::CantCastToU64)
+});
+test!(test_symbol_to_string, "(symbol->string 'foo)", |z| z
+    .intern_string("foo"));
+test!(test_string_to_symbol, "(string->symbol \"foo\")", |z| z
+    .intern_symbol_no_lang(&user_sym("foo")));
 test!(test_eq1, "(eq (cons 1 2) '(1 . 2))", |z| *z.t());
 test!(test_eq2, "(eq (cons 1 3) '(1 . 2))", |z| *z.nil());
 test!(test_eq3, "(eq :a :a)", |z| *z.t());
@@ -400,7 +492,7 @@ test!(
               (x a)
               (even? (lambda (n) (if (= n 0) t (odd? (- n 1))))))
        (odd? 1))",
-    |_| ZPtr::err(EvalErr::UnboundVar)
+    unbound_a_err
 );
 test!(
     test_fib,
@@ -541,6 +633,60 @@ test!(test_big_num_order13, "(< #0x17084a3b94580234614c1ebde7dbb24bc3cb26ba2a84d
 test!(test_big_num_order14, "(<= #0x17084a3b94580234614c1ebde7dbb24bc3cb26ba2a84d1355c06cca90b8fb7 #0x17084a3b94580234614c1ebde7dbb24bc3cb26ba2a84d1355c06cca90b8fb7)", |z| *z.t());
 test!(test_big_num_order15, "(eq #0x17084a3b94580234614c1ebde7dbb24bc3cb26ba2a84d1355c06cca90b8fb7 #0x7b4dd31c2678ef3c257cda6a06f0c830aaeab011c2c4e7fa9a27c699550539)", |z| *z.nil());
 test!(test_big_num_order16, "(eq #0x17084a3b94580234614c1ebde7dbb24bc3cb26ba2a84d1355c06cca90b8fb7 #0x17084a3b94580234614c1ebde7dbb24bc3cb26ba2a84d1355c06cca90b8fb7)", |z| *z.t());
+test!(test_big_num_add, "(+ #0x78000000 #0x1)", |_| {
+    let mut digest = [F::zero(); 8];
+    digest[1] = F::one();
+    ZPtr::big_num(digest)
+});
+test!(test_big_num_sub, "(- #0x0 #0x1)", |_| ZPtr::big_num(
+    [F::from_canonical_u32(0x78000000); 8]
+));
+test!(test_big_num_mul, "(* #0x78000001 #0x78000001)", |_| {
+    let mut digest = [F::zero(); 8];
+    digest[2] = F::one();
+    ZPtr::big_num(digest)
+});
+test!(test_big_num_div, "(/ #0x64 #0x7)", |_| {
+    let mut digest = [F::zero(); 8];
+    digest[0] = F::from_canonical_u32(14);
+    ZPtr::big_num(digest)
+});
+test!(test_big_num_mod, "(% #0x64 #0x7)", |_| {
+    let mut digest = [F::zero(); 8];
+    digest[0] = F::from_canonical_u32(2);
+    ZPtr::big_num(digest)
+});
+test!(test_big_num_div_by_zero, "(/ #0x1 #0x0)", |_| ZPtr::err(
+    EvalErr::DivByZero
+));
+test!(test_big_num_modexp, "(modexp #0x7 #0x7b #0x3e8)", |_| {
+    let mut digest = [F::zero(); 8];
+    digest[0] = F::from_canonical_u32(343);
+    ZPtr::big_num(digest)
+});
+test!(
+    test_big_num_modexp2,
+    "(modexp #0x1234567890abcdef #0x10001 #0x34800000a)",
+    |_| {
+        let mut digest = [F::zero(); 8];
+        digest[0] = F::from_canonical_u32(1172984620);
+        digest[1] = F::from_canonical_u32(5);
+        ZPtr::big_num(digest)
+    }
+);
+test!(test_big_num_modexp_one, "(modexp #0x5 #0x0 #0x1)", |_| {
+    ZPtr::big_num([F::zero(); 8])
+});
+test!(
+    test_big_num_modexp_by_zero,
+    "(modexp #0x5 #0x2 #0x0)",
+    |_| ZPtr::err(EvalErr::DivByZero)
+);
+test!(
+    test_big_num_modexp_not_big_num,
+    "(modexp 5 #0x2 #0x3)",
+    |_| ZPtr::err(EvalErr::NotBigNum)
+);
 
 // shadowing built-ins
 test!(test_shadow1, "(let ((cons 1)) (+ cons 1))", |_| uint(2));
@@ -567,7 +713,7 @@ test!(
 );
 
 // errors
-test!(test_unbound_var, "a", |_| ZPtr::err(EvalErr::UnboundVar));
+test!(test_unbound_var, "a", unbound_a_err);
 test_raw!(
     test_unbound_var2,
     |z| {
@@ -583,7 +729,11 @@ test_raw!(
         let bindings = z.intern_list([binding]);
         z.intern_list([let_, bindings, cons_sym])
     },
-    |_| ZPtr::err(EvalErr::UnboundVar)
+    |z| {
+        let mut cons_sym = z.intern_symbol_no_lang(&builtin_sym("cons"));
+        cons_sym.tag = Tag::Sym;
+        z.intern_error_with_payload(EvalErr::UnboundVar, cons_sym)
+    }
 );
 
 test!(invalid_form_let, "(let ((a 1)))", |_| ZPtr::err(
@@ -649,3 +799,44 @@ test!(test_equal_non_num2, "(= (comm #0x0) (comm #0x0))", |_| {
 // test!(test_rest_err4, "((lambda (&rest) &rest) 1)", |_| {
 //     ZPtr::err(EvalErr::ParamInvalidRest)
 // });
+
+// error payloads and `catch`
+test!(test_apply_non_func, "(1 2)", |z| z
+    .intern_error_with_payload(EvalErr::ApplyNonFunc, uint(1)));
+test!(test_fail_payload, "(fail 1)", |z| z
+    .intern_error_with_payload(EvalErr::Fail, uint(1)));
+test!(test_fail_string_payload, "(fail \"oops\")", |z| {
+    let payload = z.intern_string("oops");
+    z.intern_error_with_payload(EvalErr::Fail, payload)
+});
+test!(test_fail_nil_payload, "(fail nil)", |_| ZPtr::err(
+    EvalErr::Fail
+));
+test!(test_fail_err, "(fail a)", unbound_a_err);
+test!(test_catch_no_err, "(catch 1 2)", |_| uint(1));
+test!(test_catch_code, "(catch (/ 1 0) (lambda (c p) c))", |_| {
+    uint(EvalErr::DivByZero as u64)
+});
+test!(
+    test_catch_nil_payload,
+    "(catch (/ 1 0) (lambda (c p) p))",
+    |z| *z.nil()
+);
+test!(
+    test_catch_payload,
+    "(catch (fail \"oops\") (lambda (c p) p))",
+    |z| z.intern_string("oops")
+);
+test!(test_catch_unbound_var, "(catch a (lambda (c p) p))", |z| z
+    .intern_symbol_no_lang(&user_sym("a")));
+test!(
+    test_catch_nested,
+    "(catch (+ 1 (fail 2)) (lambda (c p) (* p 10)))",
+    |_| uint(20)
+);
+test!(
+    test_catch_rethrow,
+    "(catch (catch (fail 1) (lambda (c p) (fail (+ p 1)))) (lambda (c p) p))",
+    |_| uint(2)
+);
+test!(test_catch_handler_err, "(catch (fail 1) a)", unbound_a_err);
@@ -108,22 +108,27 @@ fn block(zstore: &mut ZStore<F, LurkChip>, xs: &[ZPtr<F>]) -> ZPtr<F> {
 // bindings record.
 // This means most of these tests have an outer `let` binding to make sure this bindings record
 // is not empty. This is susceptible to change.
-test!(test_int, "let x = 123", |z| block(z, &[ZPtr::u64(123)]));
+test!(test_int, "let x = 123", |z| block(z, &[ZPtr::i64(123)]));
 test!(test_int2, "let x = 123;; let x = 456", |z| block(
     z,
-    &[ZPtr::u64(456)]
+    &[ZPtr::i64(456)]
 ));
 test!(test_int3, "let x = 123;; let y = 456", |z| block(
     z,
-    &[ZPtr::u64(123), ZPtr::u64(456)]
+    &[ZPtr::i64(123), ZPtr::i64(456)]
 ));
 test!(test_op, "let x = 123;; let y = 456;; let sum = x + y;; let sub = y - x;; let mul = x * y;; let div = y / x;; let rem = y mod x", |z| block(
     z,
-    &[ZPtr::u64(123), ZPtr::u64(456), ZPtr::u64(123 + 456), ZPtr::u64(456 - 123), ZPtr::u64(123 * 456), ZPtr::u64(456 / 123), ZPtr::u64(456 % 123)]
+    &[ZPtr::i64(123), ZPtr::i64(456), ZPtr::i64(123 + 456), ZPtr::i64(456 - 123), ZPtr::i64(123 * 456), ZPtr::i64(456 / 123), ZPtr::i64(456 % 123)]
 ));
+test!(
+    test_negative_int,
+    "let x = -123;; let y = x / 2",
+    |z| block(z, &[ZPtr::i64(-123), ZPtr::i64(-61)])
+);
 test!(test_cmp, "let x = 123;; let y = 456;; let lt = x < y;; let lteq = x <= y;; let gt = x > y;; let gteq = x >= y;; let eq = x == y;; let noteq = x != y", |z| block(
     z,
-    &[ZPtr::u64(123), ZPtr::u64(456), *z.t(), *z.t(), *z.nil(), *z.nil(), *z.nil(), *z.t()]
+    &[ZPtr::i64(123), ZPtr::i64(456), *z.t(), *z.t(), *z.nil(), *z.nil(), *z.nil(), *z.t()]
 ));
 // TODO: ocaml uses all the same comparison builtins for non-integer data types as well (strings, chars, possibly others)
 // test!(test_cmp_char, "let x = 'a';; let y = 'z';; let lt = x < y;; let lteq = x <= y;; let gt = x > y;; let gteq = x >= y;; let eq = x == y;; let noteq = x != y", |z| block(
@@ -142,17 +147,17 @@ test!(test_string, r#"let x = "abc";; let y = "def""#, |z| {
 test!(
     test_fib,
     "let x = let rec fib n = if n <= 1 then n else fib(n - 1) + fib(n - 2) in fib 15",
-    |z| block(z, &[ZPtr::u64(610)])
+    |z| block(z, &[ZPtr::i64(610)])
 );
 test!(
     test_letrec,
     "let x = let rec odd x = if (x = 0) then false else even (x - 1) and even x = if (x = 0) then true else odd (x - 1) in odd 17",
     |z| {
-        block(z, &[ZPtr::u64(1)])
+        block(z, &[ZPtr::i64(1)])
     }
 );
 
 test_file!(test_fib2, "ocaml/fib.ml", |z| block(
     z,
-    &[ZPtr::u64(3736710778780434371)]
+    &[ZPtr::i64(3736710778780434371)]
 ));
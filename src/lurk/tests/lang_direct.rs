@@ -9,12 +9,16 @@ use sphinx_core::utils::BabyBearPoseidon2;
 
 use crate::{
     func,
-    lair::{chipset::Chipset, toplevel::Toplevel, Name},
+    lair::{
+        chipset::{Chipset, NoChip},
+        toplevel::Toplevel,
+        Name,
+    },
     lurk::{
         chipset::LurkChip,
         error::EvalErr,
         eval_direct::build_lurk_toplevel,
-        lang::{Coroutine, Lang},
+        lang::{is_lang_id, Coroutine, Lang},
         state::user_sym,
         symbol::Symbol,
         tag::Tag,
@@ -172,6 +176,11 @@ fn num(n: u32) -> ZPtr<F> {
     ZPtr::num(F::from_canonical_u32(n))
 }
 
+fn unbound_a_err(zstore: &mut ZStore<F, LurkChip>) -> ZPtr<F> {
+    let a = zstore.intern_symbol_no_lang(&user_sym("a"));
+    zstore.intern_error_with_payload(EvalErr::UnboundVar, a)
+}
+
 test!(test_mul, "(mul-square (+ 1n 2n))", |_| num(9));
 test!(test_extern, "(extern-square (+ 1n 2n))", |_| num(9));
 
@@ -198,12 +207,8 @@ test!(test_extern_mistype, "(extern-square 3)", |_| ZPtr::err(
     EvalErr::InvalidArg
 ));
 
-test!(test_mul_arg_err, "(mul-square a)", |_| ZPtr::err(
-    EvalErr::UnboundVar
-));
-test!(test_extern_arg_err, "(extern-square a)", |_| ZPtr::err(
-    EvalErr::UnboundVar
-));
+test!(test_mul_arg_err, "(mul-square a)", unbound_a_err);
+test!(test_extern_arg_err, "(extern-square a)", unbound_a_err);
 
 test!(
     test_mul_shadow1,
@@ -248,3 +253,17 @@ test!(
     "((lambda (&rest extern-square) (+ (car extern-square) 1n)) 2n)",
     |_| num(3)
 );
+
+#[test]
+fn test_lang_id() {
+    let lang_id = square_lang::<F>().id();
+    assert!(is_lang_id(&lang_id));
+    assert_eq!(lang_id, square_lang::<F>().id());
+    assert_ne!(lang_id, Lang::<F, NoChip>::empty().id());
+
+    // the ID changes with the coroutine bodies
+    let (mut coroutines, gadgets) = square_lang::<F>().into_parts();
+    let swapped_func_expr = extern_square().func_expr;
+    coroutines[&user_sym("mul-square")].func_expr = swapped_func_expr;
+    assert_ne!(lang_id, Lang::new(coroutines, gadgets).id());
+}
@@ -2,9 +2,12 @@ use std::array;
 use std::borrow::{Borrow, BorrowMut};
 
 use p3_air::AirBuilder;
-use p3_field::PrimeField32;
+use p3_field::{AbstractField, PrimeField32};
 
+use crate::gadgets::unsigned::bitwise::{Bitwise, BitwiseOp};
+use crate::gadgets::unsigned::field::FieldToWord32;
 use crate::gadgets::unsigned::is_zero::IsZero;
+use crate::gadgets::unsigned::shift::{ShiftDirection, ShiftWitness};
 use crate::{
     air::builder::{LookupBuilder, Record, RequireRecord},
     gadgets::{
@@ -26,6 +29,8 @@ pub type DivRem64<T> = DivRem<T, 8>;
 pub type Compare64<T> = CompareWitness<T, 8>;
 pub type IsZero64<T> = IsZero<T, 8>;
 pub type Product64<T> = Product<T, 8>;
+pub type Bitwise64<T> = Bitwise<T, 8>;
+pub type Shift64<T> = ShiftWitness<T, 8>;
 
 #[derive(Clone)]
 pub enum U64 {
@@ -35,6 +40,34 @@ pub enum U64 {
     DivRem,
     LessThan,
     IsZero,
+    And,
+    Or,
+    Xor,
+    /// Left shift, with the shift amount taken modulo 64
+    Shl,
+    /// Logical right shift, with the shift amount taken modulo 64
+    Shr,
+    /// Converts a field element into the `U64` with the same value
+    FromNum,
+}
+
+impl U64 {
+    fn bitwise_op(&self) -> Option<BitwiseOp> {
+        match self {
+            U64::And => Some(BitwiseOp::And),
+            U64::Or => Some(BitwiseOp::Or),
+            U64::Xor => Some(BitwiseOp::Xor),
+            _ => None,
+        }
+    }
+
+    fn shift_direction(&self) -> Option<ShiftDirection> {
+        match self {
+            U64::Shl => Some(ShiftDirection::Left),
+            U64::Shr => Some(ShiftDirection::Right),
+            _ => None,
+        }
+    }
 }
 
 fn into_u64<F: PrimeField32>(slice: &[F]) -> u64 {
@@ -47,6 +80,7 @@ impl<F: PrimeField32> Chipset<F> for U64 {
     fn input_size(&self) -> usize {
         match self {
             U64::IsZero => 8,
+            U64::FromNum => 1,
             _ => 16,
         }
     }
@@ -67,6 +101,9 @@ impl<F: PrimeField32> Chipset<F> for U64 {
             U64::DivRem => DivRem64::<F>::witness_size(),
             U64::LessThan => Compare64::<F>::witness_size(),
             U64::IsZero => IsZero64::<F>::witness_size(),
+            U64::And | U64::Or | U64::Xor => Bitwise64::<F>::witness_size(),
+            U64::Shl | U64::Shr => Shift64::<F>::witness_size(),
+            U64::FromNum => FieldToWord32::<F>::witness_size(),
         }
     }
 
@@ -78,6 +115,9 @@ impl<F: PrimeField32> Chipset<F> for U64 {
             U64::DivRem => DivRem64::<F>::num_requires(),
             U64::LessThan => Compare64::<F>::num_requires(),
             U64::IsZero => IsZero64::<F>::num_requires(),
+            U64::And | U64::Or | U64::Xor => Bitwise64::<F>::num_requires(),
+            U64::Shl | U64::Shr => Shift64::<F>::num_requires(),
+            U64::FromNum => FieldToWord32::<F>::num_requires(),
         }
     }
 
@@ -88,9 +128,12 @@ impl<F: PrimeField32> Chipset<F> for U64 {
         queries: &mut QueryRecord<F>,
         requires: &mut Vec<Record>,
     ) -> Vec<F> {
-        let in1 = into_u64(&input[0..8]);
+        let in1 = match self {
+            U64::FromNum => 0, // unused
+            _ => into_u64(&input[0..8]),
+        };
         let in2 = match self {
-            U64::IsZero => 0, // unused
+            U64::IsZero | U64::FromNum => 0, // unused
             _ => into_u64(&input[8..16]),
         };
         let bytes = &mut queries.bytes.context(nonce, requires);
@@ -125,13 +168,34 @@ impl<F: PrimeField32> Chipset<F> for U64 {
                 witness.populate_is_zero(&in1);
                 witness.iter_result().into_iter().collect()
             }
+            U64::And | U64::Or | U64::Xor => {
+                let op = self.bitwise_op().unwrap();
+                let mut witness = Bitwise64::<F>::default();
+                witness.populate(op, &in1, &in2, bytes);
+                witness.iter_result().into_iter().collect()
+            }
+            U64::Shl | U64::Shr => {
+                let direction = self.shift_direction().unwrap();
+                let mut witness = Shift64::<F>::default();
+                let out = witness.populate(direction, &in1, &in2, bytes);
+                Word64::<F>::from_unsigned(&out).into_iter().collect()
+            }
+            U64::FromNum => {
+                let mut witness = FieldToWord32::<F>::default();
+                witness.populate(&input[0].as_canonical_u32(), bytes);
+                let hi = [F::zero(); 4];
+                witness.iter_result().into_iter().chain(hi).collect()
+            }
         }
     }
 
     fn populate_witness(&self, input: &[F], witness: &mut [F]) -> Vec<F> {
-        let in1 = into_u64(&input[0..8]);
+        let in1 = match self {
+            U64::FromNum => 0, // unused
+            _ => into_u64(&input[0..8]),
+        };
         let in2 = match self {
-            U64::IsZero => 0, // unused
+            U64::IsZero | U64::FromNum => 0, // unused
             _ => into_u64(&input[8..16]),
         };
         let bytes = &mut DummyBytesRecord;
@@ -166,6 +230,24 @@ impl<F: PrimeField32> Chipset<F> for U64 {
                 witness.populate_is_zero(&in1);
                 witness.iter_result().into_iter().collect()
             }
+            U64::And | U64::Or | U64::Xor => {
+                let op = self.bitwise_op().unwrap();
+                let witness: &mut Bitwise64<F> = witness.borrow_mut();
+                witness.populate(op, &in1, &in2, bytes);
+                witness.iter_result().into_iter().collect()
+            }
+            U64::Shl | U64::Shr => {
+                let direction = self.shift_direction().unwrap();
+                let witness: &mut Shift64<F> = witness.borrow_mut();
+                let out = witness.populate(direction, &in1, &in2, bytes);
+                Word64::<F>::from_unsigned(&out).into_iter().collect()
+            }
+            U64::FromNum => {
+                let witness: &mut FieldToWord32<F> = witness.borrow_mut();
+                witness.populate(&input[0].as_canonical_u32(), bytes);
+                let hi = [F::zero(); 4];
+                witness.iter_result().into_iter().chain(hi).collect()
+            }
         }
     }
 
@@ -178,9 +260,12 @@ impl<F: PrimeField32> Chipset<F> for U64 {
         nonce: AB::Expr,
         requires: &[RequireRecord<AB::Var>],
     ) -> Vec<AB::Expr> {
-        let in1 = ins[0..8].iter().cloned().collect::<Word64<_>>();
+        let in1 = match self {
+            U64::FromNum => Word64::default(), // unused
+            _ => ins[0..8].iter().cloned().collect::<Word64<_>>(),
+        };
         let in2 = match self {
-            U64::IsZero => Word64::default(), // unused
+            U64::IsZero | U64::FromNum => Word64::default(), // unused
             _ => ins[8..16].iter().cloned().collect::<Word64<_>>(),
         };
         let mut air_record = BytesAirRecordWithContext::default();
@@ -215,6 +300,31 @@ impl<F: PrimeField32> Chipset<F> for U64 {
                 let out = witness.eval_is_zero(builder, in1, is_real.clone());
                 vec![out.into()]
             }
+            U64::And | U64::Or | U64::Xor => {
+                let op = self.bitwise_op().unwrap();
+                let witness: &Bitwise64<AB::Var> = witness.borrow();
+                let out = witness.eval(op, &in1, &in2, &mut air_record, is_real.clone());
+                out.map(Into::into).into_iter().collect()
+            }
+            U64::Shl | U64::Shr => {
+                let direction = self.shift_direction().unwrap();
+                let witness: &Shift64<AB::Var> = witness.borrow();
+                let out = witness.eval(
+                    builder,
+                    direction,
+                    &in1,
+                    &in2,
+                    &mut air_record,
+                    is_real.clone(),
+                );
+                out.into_iter().collect()
+            }
+            U64::FromNum => {
+                let witness: &FieldToWord32<AB::Var> = witness.borrow();
+                let out = witness.eval(builder, &ins[0], &mut air_record, is_real.clone());
+                let hi = (0..4).map(|_| AB::Expr::zero());
+                out.into_iter().map(Into::into).chain(hi).collect()
+            }
         };
         air_record.require_all(builder, nonce, requires.iter().cloned());
         out
@@ -224,7 +334,7 @@ impl<F: PrimeField32> Chipset<F> for U64 {
 #[cfg(test)]
 mod test {
     use p3_baby_bear::BabyBear as F;
-    use p3_field::AbstractField;
+    use p3_field::{AbstractField, PrimeField32};
     use sphinx_core::{stark::StarkMachine, utils::BabyBearPoseidon2};
 
     use crate::{
@@ -605,4 +715,130 @@ mod test {
         let shard = Shard::new(&queries);
         machine.debug_constraints(&pk, shard.clone());
     }
+
+    fn u64_args(values: &[u64]) -> Vec<F> {
+        values
+            .iter()
+            .flat_map(|x| x.to_le_bytes())
+            .map(F::from_canonical_u8)
+            .collect()
+    }
+
+    #[test]
+    fn u64_bitwise_test() {
+        sphinx_core::utils::setup_logger();
+
+        let bitwise_func = func!(
+        fn bitwise(a: [8], b: [8]): [24] {
+            let c: [8] = extern_call(u64_and, a, b);
+            let d: [8] = extern_call(u64_or, a, b);
+            let e: [8] = extern_call(u64_xor, a, b);
+            return (c, d, e)
+        });
+        let lurk_chip_map = lurk_chip_map_native();
+        let toplevel = Toplevel::new(&[bitwise_func], lurk_chip_map);
+
+        let bitwise_chip = FuncChip::from_name("bitwise", &toplevel);
+        let mut queries = QueryRecord::new(&toplevel);
+        let (a, b) = (0x00ff_f0f0_1234_abcd, 0xff0f_00ff_4321_0000);
+        let args = &u64_args(&[a, b]);
+        let out = toplevel
+            .execute_by_name("bitwise", args, &mut queries, None)
+            .unwrap();
+        assert_eq!(out.as_ref(), &u64_args(&[a & b, a | b, a ^ b]));
+
+        let lair_chips = build_lair_chip_vector(&bitwise_chip);
+        debug_chip_constraints_and_queries_with_sharding(&queries, &lair_chips, None);
+
+        let config = BabyBearPoseidon2::new();
+        let machine = StarkMachine::new(
+            config,
+            build_chip_vector(&bitwise_chip),
+            queries.expect_public_values().len(),
+        );
+
+        let (pk, _vk) = machine.setup(&LairMachineProgram);
+        let shard = Shard::new(&queries);
+        machine.debug_constraints(&pk, shard.clone());
+    }
+
+    #[test]
+    fn u64_shift_test() {
+        sphinx_core::utils::setup_logger();
+
+        let shift_func = func!(
+        fn shift(a: [8], b: [8]): [16] {
+            let c: [8] = extern_call(u64_shl, a, b);
+            let d: [8] = extern_call(u64_shr, a, b);
+            return (c, d)
+        });
+        let lurk_chip_map = lurk_chip_map_native();
+        let toplevel = Toplevel::new(&[shift_func], lurk_chip_map);
+
+        let shift_chip = FuncChip::from_name("shift", &toplevel);
+        let a = 0x8000_1234_5678_9abc;
+        for b in [0, 3, 21, 63, 64, 100] {
+            let mut queries = QueryRecord::new(&toplevel);
+            let args = &u64_args(&[a, b]);
+            let out = toplevel
+                .execute_by_name("shift", args, &mut queries, None)
+                .unwrap();
+            let b = b as u32;
+            assert_eq!(
+                out.as_ref(),
+                &u64_args(&[a.wrapping_shl(b), a.wrapping_shr(b)])
+            );
+
+            let lair_chips = build_lair_chip_vector(&shift_chip);
+            debug_chip_constraints_and_queries_with_sharding(&queries, &lair_chips, None);
+
+            let config = BabyBearPoseidon2::new();
+            let machine = StarkMachine::new(
+                config,
+                build_chip_vector(&shift_chip),
+                queries.expect_public_values().len(),
+            );
+
+            let (pk, _vk) = machine.setup(&LairMachineProgram);
+            let shard = Shard::new(&queries);
+            machine.debug_constraints(&pk, shard.clone());
+        }
+    }
+
+    #[test]
+    fn u64_from_num_test() {
+        sphinx_core::utils::setup_logger();
+
+        let from_num_func = func!(
+        fn from_num(a): [8] {
+            let b: [8] = extern_call(u64_from_num, a);
+            return b
+        });
+        let lurk_chip_map = lurk_chip_map_native();
+        let toplevel = Toplevel::new(&[from_num_func], lurk_chip_map);
+
+        let from_num_chip = FuncChip::from_name("from_num", &toplevel);
+        let mut queries = QueryRecord::new(&toplevel);
+        for a in [0, 255, 0x1234_5678, F::ORDER_U32 - 1] {
+            let args = &[F::from_canonical_u32(a)];
+            let out = toplevel
+                .execute_by_name("from_num", args, &mut queries, None)
+                .unwrap();
+            assert_eq!(out.as_ref(), &u64_args(&[a.into()]));
+        }
+
+        let lair_chips = build_lair_chip_vector(&from_num_chip);
+        debug_chip_constraints_and_queries_with_sharding(&queries, &lair_chips, None);
+
+        let config = BabyBearPoseidon2::new();
+        let machine = StarkMachine::new(
+            config,
+            build_chip_vector(&from_num_chip),
+            queries.expect_public_values().len(),
+        );
+
+        let (pk, _vk) = machine.setup(&LairMachineProgram);
+        let shard = Shard::new(&queries);
+        machine.debug_constraints(&pk, shard.clone());
+    }
 }
@@ -0,0 +1,569 @@
+//! Verification of Lurk reduction proofs, independent from the REPL.
+//!
+//! Proofs are checked against the native Lurk toplevel by default, which is all
+//! it takes for services that only need to know whether a proof holds for a
+//! claim.
+
+use anyhow::Result;
+use hashbrown::HashMap;
+use once_cell::sync::OnceCell;
+use p3_air::BaseAir;
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractField, PrimeField32};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sphinx_core::{
+    air::MachineAir,
+    stark::{
+        Challenge, Com, MachineProof, OpeningProof, ShardCommitment, ShardOpenedValues, ShardProof,
+        StarkGenericConfig, StarkMachine, StarkVerifyingKey,
+    },
+    utils::BabyBearPoseidon2,
+};
+use std::sync::Mutex;
+
+use crate::lair::{
+    chipset::{Chipset, NoChip},
+    encoding::Encode,
+    func_chip::FuncChip,
+    lair_chip::{build_lair_chip_vector, LairChip, LairMachineProgram},
+    provenance::DEPTH_W,
+    toplevel::Toplevel,
+};
+
+use super::{
+    chipset::LurkChip,
+    eval_direct::{build_lurk_toplevel, build_lurk_toplevel_native},
+    lang::Lang,
+    stark_machine::{new_machine, NUM_PUBLIC_VALUES},
+    tag::Tag,
+    zdag::ZDag,
+    zstore::{ZPtr, ZStore, DIGEST_SIZE, ZPTR_SIZE},
+};
+
+type F = BabyBear;
+
+#[derive(Serialize, Deserialize, Clone)]
+struct CryptoShardProof {
+    commitment: ShardCommitment<Com<BabyBearPoseidon2>>,
+    opened_values: ShardOpenedValues<Challenge<BabyBearPoseidon2>>,
+    opening_proof: OpeningProof<BabyBearPoseidon2>,
+    chip_ordering: HashMap<String, usize>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct CryptoProof {
+    // `verifier_version` leads so that it can be read without the shard proofs
+    verifier_version: String,
+    depth: u32,
+    shard_proofs: Vec<CryptoShardProof>,
+}
+
+#[cfg(test)]
+impl CryptoProof {
+    /// A proof without shard proofs for the native verifier, which is rejected
+    pub(crate) fn empty() -> Self {
+        Self {
+            verifier_version: get_verifier_version().to_string(),
+            depth: 0,
+            shard_proofs: vec![],
+        }
+    }
+}
+
+/// Identifies the circuit that proofs are verified against: the SHA-256 digest
+/// of the canonical encoding of the bytecode of the toplevel functions, the
+/// names and widths of the chips of the Lurk machine and the number of public
+/// values. Proofs thus remain valid across builds that don't change the circuit.
+pub(crate) fn verifier_version<C1: Chipset<F>, C2: Chipset<F>>(
+    toplevel: &Toplevel<F, C1, C2>,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update((toplevel.func_map.len() as u64).to_le_bytes());
+    for func in toplevel.func_map.values() {
+        hasher.update(func.to_canonical_bytes());
+    }
+    let lurk_main_chip = FuncChip::from_name("lurk_main", toplevel);
+    for chip in build_lair_chip_vector(&lurk_main_chip) {
+        let (name, width, preprocessed_width) =
+            (chip.name(), chip.width(), chip.preprocessed_width());
+        hasher.update(format!("{name} {width} {preprocessed_width};"));
+    }
+    hasher.update("|");
+    hasher.update((NUM_PUBLIC_VALUES as u64).to_le_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// The verifier version for the native Lurk toplevel, as in `verifier_version`
+pub(crate) fn get_verifier_version() -> &'static str {
+    static NATIVE_VERIFIER_VERSION: OnceCell<String> = OnceCell::new();
+    NATIVE_VERIFIER_VERSION.get_or_init(|| {
+        let (toplevel, ..) = build_lurk_toplevel_native();
+        verifier_version(&toplevel)
+    })
+}
+
+fn lang_verifier_versions() -> &'static Mutex<FxHashMap<String, String>> {
+    static LANG_VERIFIER_VERSIONS: OnceCell<Mutex<FxHashMap<String, String>>> = OnceCell::new();
+    LANG_VERIFIER_VERSIONS.get_or_init(Mutex::default)
+}
+
+/// Records the verifier version for the toplevel of a `Lang`, by its ID, so
+/// it can be retrieved with `get_lang_verifier_version`
+pub(crate) fn register_lang_verifier_version(lang_id: String, verifier_version: String) {
+    lang_verifier_versions()
+        .lock()
+        .unwrap()
+        .insert(lang_id, verifier_version);
+}
+
+/// The verifier version for a `Lang` ID, provided that it's the ID of the empty
+/// `Lang` or that its version was registered with `register_lang_verifier_version`
+pub(crate) fn get_lang_verifier_version(lang_id: &str) -> Option<String> {
+    if lang_id == Lang::<F, NoChip>::empty().id() {
+        return Some(get_verifier_version().to_string());
+    }
+    lang_verifier_versions()
+        .lock()
+        .unwrap()
+        .get(lang_id)
+        .cloned()
+}
+
+impl CryptoProof {
+    #[inline]
+    pub(crate) fn into_machine_proof(
+        self,
+        expr: &ZPtr<F>,
+        env: &ZPtr<F>,
+        result: &ZPtr<F>,
+    ) -> MachineProof<BabyBearPoseidon2> {
+        let mut public_values = Vec::with_capacity(40);
+        public_values.extend(expr.flatten());
+        public_values.extend(env.digest);
+        public_values.extend(result.flatten());
+        public_values.extend(self.depth.to_le_bytes().map(F::from_canonical_u8));
+        let shard_proofs = self
+            .shard_proofs
+            .into_iter()
+            .map(|csp| {
+                let CryptoShardProof {
+                    commitment,
+                    opened_values,
+                    opening_proof,
+                    chip_ordering,
+                } = csp;
+                ShardProof {
+                    commitment,
+                    opened_values,
+                    opening_proof,
+                    chip_ordering,
+                    public_values: public_values.clone(),
+                }
+            })
+            .collect();
+        MachineProof { shard_proofs }
+    }
+
+    #[inline]
+    pub(crate) fn verifier_version(&self) -> &str {
+        &self.verifier_version
+    }
+
+    #[inline]
+    pub(crate) fn has_same_verifier_version(&self, verifier_version: &str) -> bool {
+        self.verifier_version == verifier_version
+    }
+
+    /// Wraps a `MachineProof` created by the verifier with `verifier_version`.
+    /// The asserts/expects/unwraps in this function are all internal and should
+    /// always succeed.
+    pub(crate) fn new(value: MachineProof<BabyBearPoseidon2>, verifier_version: String) -> Self {
+        let (shard_proofs, all_public_values) = value
+            .shard_proofs
+            .into_iter()
+            .map(|sp| {
+                let ShardProof {
+                    commitment,
+                    opened_values,
+                    opening_proof,
+                    chip_ordering,
+                    public_values,
+                    ..
+                } = sp;
+                (
+                    CryptoShardProof {
+                        commitment,
+                        opened_values,
+                        opening_proof,
+                        chip_ordering,
+                    },
+                    public_values,
+                )
+            })
+            .collect::<(Vec<_>, Vec<_>)>();
+        let public_values = all_public_values.first().expect("must have public values");
+        // sanity check: all shards have the same public values
+        assert!(all_public_values.iter().all(|pv| pv == public_values));
+        let depth_bytes = public_values[public_values.len() - DEPTH_W..]
+            .iter()
+            .cloned()
+            .map(|x| {
+                assert!(x <= F::from_canonical_u8(u8::MAX));
+                x.as_canonical_u32() as u8
+            })
+            .collect::<Vec<_>>();
+        let depth = u32::from_le_bytes(depth_bytes.try_into().unwrap());
+        Self {
+            verifier_version,
+            depth,
+            shard_proofs,
+        }
+    }
+}
+
+/// Carries a cryptographic proof and the Lurk data for its public values. This
+/// proof format is meant for local caching through filesystem persistence. The
+/// Lurk data for its public values is fully specified to support inspection.
+#[derive(Serialize, Deserialize, Clone)]
+pub(crate) struct CachedProof {
+    pub(crate) expr: ZPtr<F>,
+    pub(crate) env: ZPtr<F>,
+    pub(crate) result: ZPtr<F>,
+    pub(crate) zdag: ZDag<F>,
+    /// The ID of the `Lang` the proof was created with, as in `Lang::id`
+    pub(crate) lang_id: String,
+    // the crypto proof comes last so that `CachedProofHeader` can be read
+    // without it
+    pub(crate) crypto_proof: CryptoProof,
+}
+
+/// The leading fields of a serialized `CachedProof`: its claim, the Lurk data
+/// for it, its `Lang` ID and the verifier version of its crypto proof. It's
+/// meant for inspecting persisted proofs without reading their shard proofs.
+#[derive(Deserialize)]
+pub(crate) struct CachedProofHeader {
+    pub(crate) expr: ZPtr<F>,
+    pub(crate) env: ZPtr<F>,
+    pub(crate) result: ZPtr<F>,
+    pub(crate) zdag: ZDag<F>,
+    pub(crate) lang_id: String,
+    pub(crate) verifier_version: String,
+}
+
+impl CachedProof {
+    /// Wraps a `CryptoProof` created with the `Lang` of ID `lang_id` with the
+    /// Lurk data for its public values. If `num_disclosed` is provided, only
+    /// that many outermost environment bindings are carried and the rest of the
+    /// environment remains hidden behind its digest, as in
+    /// `ZDag::populate_with_env`.
+    pub(crate) fn new<C: Chipset<F>>(
+        crypto_proof: CryptoProof,
+        public_values: &[F],
+        num_disclosed: Option<usize>,
+        lang_id: String,
+        zstore: &ZStore<F, C>,
+    ) -> Self {
+        let mut zdag = ZDag::default();
+        let (expr_data, rest) = public_values.split_at(ZPTR_SIZE);
+        let (env_digest, rest) = rest.split_at(DIGEST_SIZE);
+        let (result_data, _rest) = rest.split_at(ZPTR_SIZE);
+        let expr = ZPtr::from_flat_data(expr_data);
+        let env = ZPtr::from_flat_digest(Tag::Env, env_digest);
+        let result = ZPtr::from_flat_data(result_data);
+        if let Some(num_disclosed) = num_disclosed {
+            let cache = &mut FxHashSet::default();
+            zdag.populate_with_env(&env, num_disclosed, zstore, cache);
+            zdag.populate_with(&expr, zstore, cache);
+            zdag.populate_with(&result, zstore, cache);
+        } else {
+            zdag.populate_with_many([&expr, &env, &result], zstore);
+        }
+        Self {
+            expr,
+            env,
+            result,
+            zdag,
+            lang_id,
+            crypto_proof,
+        }
+    }
+
+    /// The claim proven by the `CryptoProof`
+    #[inline]
+    pub(crate) fn claim(&self) -> Claim {
+        let Self {
+            expr, env, result, ..
+        } = self;
+        Claim {
+            expr: *expr,
+            env: *env,
+            result: *result,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn into_machine_proof(self) -> MachineProof<BabyBearPoseidon2> {
+        let Self {
+            crypto_proof,
+            expr,
+            env,
+            result,
+            ..
+        } = self;
+        crypto_proof.into_machine_proof(&expr, &env, &result)
+    }
+}
+
+/// A reduction claim: reducing `expr` with environment `env` results in `result`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Claim {
+    pub expr: ZPtr<F>,
+    pub env: ZPtr<F>,
+    pub result: ZPtr<F>,
+}
+
+/// The outcome of a proof verification
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verdict {
+    /// Whether the proof holds for the claim
+    pub accepted: bool,
+    /// The verifier version the proof was created for
+    pub proof_verifier_version: String,
+}
+
+impl Verdict {
+    /// Whether the proof was created for the version of the native verifier.
+    /// Proofs created for other versions are likely to be rejected.
+    #[inline]
+    pub fn has_same_verifier_version(&self) -> bool {
+        self.proof_verifier_version == get_verifier_version()
+    }
+}
+
+/// Verifies a bincode-serialized `CryptoProof` for a claim with the native
+/// verifier. Errors if the proof can't be deserialized.
+pub fn verify_crypto_proof(proof_bytes: &[u8], claim: &Claim) -> Result<Verdict> {
+    let crypto_proof = bincode::deserialize(proof_bytes)?;
+    Ok(Verifier::native().verify_claim(crypto_proof, claim))
+}
+
+/// Verifies a bincode-serialized `CachedProof`, as persisted in the proofs
+/// directory, for the claim it carries with the native verifier. Errors if the
+/// proof can't be deserialized.
+pub fn verify_cached_proof(proof_bytes: &[u8]) -> Result<(Claim, Verdict)> {
+    let cached_proof: CachedProof = bincode::deserialize(proof_bytes)?;
+    let claim = cached_proof.claim();
+    let verdict = Verifier::native().verify_claim(cached_proof.crypto_proof, &claim);
+    Ok((claim, verdict))
+}
+
+/// A `StarkMachine` for the toplevel of a `Lang` along with its verifying key.
+/// Building them is expensive, so each `Lang` gets a single verifier per
+/// process, which is kept alive to verify every subsequent proof.
+pub struct Verifier {
+    machine: StarkMachine<BabyBearPoseidon2, LairChip<'static, F, LurkChip, NoChip>>,
+    vk: StarkVerifyingKey<BabyBearPoseidon2>,
+}
+
+/// The number of machine setups performed by `Verifier::new`
+#[cfg(test)]
+static NUM_SETUPS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+type VerifiersMap = Mutex<FxHashMap<String, &'static Verifier>>;
+
+fn verifiers() -> &'static VerifiersMap {
+    static VERIFIERS: OnceCell<VerifiersMap> = OnceCell::new();
+    VERIFIERS.get_or_init(Mutex::default)
+}
+
+impl Verifier {
+    fn new(toplevel: &'static Toplevel<F, LurkChip, NoChip>) -> Self {
+        #[cfg(test)]
+        NUM_SETUPS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let machine = new_machine(toplevel);
+        let (_, vk) = machine.setup(&LairMachineProgram);
+        Self { machine, vk }
+    }
+
+    /// Returns the verifier for a `Lang`, which is only built the first time its
+    /// ID is seen
+    pub fn get(lang: Lang<F, NoChip>) -> &'static Self {
+        let mut verifiers = verifiers().lock().unwrap();
+        *verifiers.entry(lang.id()).or_insert_with(|| {
+            let (toplevel, ..) = build_lurk_toplevel(lang);
+            Box::leak(Box::new(Self::new(Box::leak(Box::new(toplevel)))))
+        })
+    }
+
+    /// Returns the verifier for a `Lang` ID, provided that it's the ID of the
+    /// empty `Lang` or that its verifier was already built with `get`
+    pub(crate) fn get_by_id(lang_id: &str) -> Option<&'static Self> {
+        if lang_id == Lang::<F, NoChip>::empty().id() {
+            return Some(Self::native());
+        }
+        verifiers().lock().unwrap().get(lang_id).copied()
+    }
+
+    /// The verifier for the native Lurk toplevel
+    #[inline]
+    pub fn native() -> &'static Self {
+        Self::get(Lang::empty())
+    }
+
+    pub fn verify(&self, machine_proof: &MachineProof<BabyBearPoseidon2>) -> bool {
+        let challenger = &mut self.machine.config().challenger();
+        self.machine
+            .verify(&self.vk, machine_proof, challenger)
+            .is_ok()
+    }
+
+    /// Verifies a `CryptoProof` for a claim
+    pub(crate) fn verify_claim(&self, crypto_proof: CryptoProof, claim: &Claim) -> Verdict {
+        let proof_verifier_version = crypto_proof.verifier_version().to_string();
+        let Claim { expr, env, result } = claim;
+        let machine_proof = crypto_proof.into_machine_proof(expr, env, result);
+        Verdict {
+            accepted: self.verify(&machine_proof),
+            proof_verifier_version,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::Ordering;
+
+    use crate::{
+        lair::chipset::NoChip,
+        lurk::{
+            lang::Lang,
+            state::{builtin_sym, user_sym},
+            tag::Tag,
+            zdag::ZDag,
+            zstore::{lurk_zstore, ZPtr},
+        },
+    };
+
+    use super::{
+        get_verifier_version, verify_crypto_proof, CachedProof, CachedProofHeader, Claim,
+        CryptoProof, Verifier, F, NUM_SETUPS,
+    };
+
+    #[test]
+    fn test_verifier_cache() {
+        // the machine setup only happens once across many verifications
+        let verifier = Verifier::native();
+        let num_setups = NUM_SETUPS.load(Ordering::SeqCst);
+        assert!(num_setups >= 1);
+        let claim = Claim {
+            expr: ZPtr::u64(3),
+            env: ZPtr::null(Tag::Env),
+            result: ZPtr::u64(3),
+        };
+        let empty_proof = CryptoProof {
+            shard_proofs: vec![],
+            verifier_version: get_verifier_version().to_string(),
+            depth: 0,
+        };
+        let proof_bytes = bincode::serialize(&empty_proof).unwrap();
+        let native_id = Lang::<F, NoChip>::empty().id();
+        for _ in 0..8 {
+            assert!(std::ptr::eq(verifier, Verifier::native()));
+            assert!(std::ptr::eq(verifier, Verifier::get(Lang::empty())));
+            assert!(std::ptr::eq(
+                verifier,
+                Verifier::get_by_id(&native_id).unwrap()
+            ));
+            assert!(!verify_crypto_proof(&proof_bytes, &claim).unwrap().accepted);
+        }
+        assert_eq!(NUM_SETUPS.load(Ordering::SeqCst), num_setups);
+        assert!(Verifier::get_by_id("unknown").is_none());
+    }
+
+    #[test]
+    fn test_reject_malformed_proofs() {
+        let claim = Claim {
+            expr: ZPtr::u64(3),
+            env: ZPtr::null(Tag::Env),
+            result: ZPtr::u64(3),
+        };
+        assert!(verify_crypto_proof(&[1, 2, 3], &claim).is_err());
+
+        let empty_proof = CryptoProof {
+            shard_proofs: vec![],
+            verifier_version: get_verifier_version().to_string(),
+            depth: 0,
+        };
+        let proof_bytes = bincode::serialize(&empty_proof).unwrap();
+        let verdict = verify_crypto_proof(&proof_bytes, &claim).unwrap();
+        assert!(!verdict.accepted);
+        assert!(verdict.has_same_verifier_version());
+    }
+
+    #[test]
+    fn test_hidden_bindings() {
+        let mut zstore = lurk_zstore();
+        let secret = zstore.intern_symbol_no_lang(&user_sym("secret"));
+        let public = zstore.intern_symbol_no_lang(&user_sym("public"));
+        let secret_val = zstore.intern_string("hunter2");
+        let public_val = zstore.intern_string("hello");
+        let empty_env = zstore.intern_empty_env();
+        let secret_env = zstore.intern_env(secret, secret_val, empty_env);
+        let env = zstore.intern_env(public, public_val, secret_env);
+        // `(lambda () secret)`, closing over the whole environment
+        let nil = *zstore.nil();
+        let lambda_sym = zstore.intern_symbol_no_lang(&builtin_sym("lambda"));
+        let lambda = zstore.intern_list([lambda_sym, nil, secret]);
+        let result = zstore.intern_fun(nil, secret, env);
+
+        let public_values = [
+            &lambda.flatten()[..],
+            &env.digest[..],
+            &result.flatten()[..],
+        ]
+        .concat();
+        let cached_proof = CachedProof::new(
+            CryptoProof::empty(),
+            &public_values,
+            Some(1),
+            Lang::<F, NoChip>::empty().id(),
+            &zstore,
+        );
+        let CachedProof { zdag, .. } = cached_proof;
+        let (bindings, opaque_tail) = zdag.env_bindings(&env);
+        assert_eq!(bindings, vec![(public, public_val)]);
+        assert_eq!(opaque_tail, Some(secret_env));
+        assert!(zdag.has_opaque_data(&result));
+        assert!(zdag.has_opaque_data(&secret_env));
+        assert!(zdag.has_opaque_data(&secret_val));
+    }
+
+    #[test]
+    fn test_cached_proof_header() {
+        let cached_proof = CachedProof {
+            expr: ZPtr::u64(3),
+            env: ZPtr::null(Tag::Env),
+            result: ZPtr::u64(3),
+            zdag: ZDag::default(),
+            lang_id: Lang::<F, NoChip>::empty().id(),
+            crypto_proof: CryptoProof {
+                verifier_version: get_verifier_version().to_string(),
+                depth: 0,
+                shard_proofs: vec![],
+            },
+        };
+        let proof_bytes = bincode::serialize(&cached_proof).unwrap();
+        let header: CachedProofHeader = bincode::deserialize_from(&proof_bytes[..]).unwrap();
+        assert_eq!(header.expr, cached_proof.expr);
+        assert_eq!(header.env, cached_proof.env);
+        assert_eq!(header.result, cached_proof.result);
+        assert_eq!(header.lang_id, cached_proof.lang_id);
+        assert_eq!(header.verifier_version, get_verifier_version());
+    }
+}
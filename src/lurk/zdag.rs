@@ -0,0 +1,195 @@
+use p3_field::AbstractField;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    lair::chipset::Chipset,
+    lurk::zstore::{ZPtr, ZPtrType, ZStore},
+};
+
+/// Holds Lurk data meant to be persisted and/or shared
+#[derive(Default, Serialize, Deserialize, Clone)]
+pub(crate) struct ZDag<F: std::hash::Hash + Eq>(FxHashMap<ZPtr<F>, ZPtrType<F>>);
+
+impl<F: std::hash::Hash + Eq + Copy> ZDag<F> {
+    /// Traverses a ZStore DAG, starting from a given `ZPtr`, while populating
+    /// itself.
+    pub(crate) fn populate_with<'a, C: Chipset<F>>(
+        &mut self,
+        zptr: &'a ZPtr<F>,
+        zstore: &'a ZStore<F, C>,
+        cache: &mut FxHashSet<&'a ZPtr<F>>,
+    ) {
+        if cache.contains(zptr) {
+            return;
+        }
+        let zptr_type = zstore
+            .dag
+            .get(zptr)
+            .expect("Data missing from ZStore's DAG");
+        match zptr_type {
+            ZPtrType::Atom => (),
+            ZPtrType::Tuple11(a, b) => {
+                self.populate_with(a, zstore, cache);
+                self.populate_with(b, zstore, cache);
+            }
+            ZPtrType::Tuple100(a, b, c) | ZPtrType::Tuple110(a, b, c) => {
+                self.populate_with(a, zstore, cache);
+                self.populate_with(b, zstore, cache);
+                self.populate_with(c, zstore, cache);
+            }
+        }
+        cache.insert(zptr);
+        self.0.insert(*zptr, *zptr_type);
+    }
+
+    /// Calls `populate_with` for a sequence of `ZPtr`s
+    pub(crate) fn populate_with_many<'a, C: Chipset<F>>(
+        &mut self,
+        zptrs: impl IntoIterator<Item = &'a ZPtr<F>>,
+        zstore: &ZStore<F, C>,
+    ) where
+        F: 'a,
+    {
+        let cache = &mut FxHashSet::default();
+        for zptr in zptrs {
+            self.populate_with(zptr, zstore, cache);
+        }
+    }
+
+    /// Populates itself with the spine nodes of the outermost `num_disclosed`
+    /// bindings of an environment, along with their variables and values. The
+    /// rest of the environment is only present as the digest in the tail of
+    /// the last disclosed spine node, unless it's the empty environment.
+    ///
+    /// The spine nodes of the hidden bindings are added to `cache` so that
+    /// subsequent calls to `populate_with` stop at them, keeping them hidden
+    /// even if they're reachable from other data, such as closures.
+    pub(crate) fn populate_with_env<'a, C: Chipset<F>>(
+        &mut self,
+        env: &'a ZPtr<F>,
+        num_disclosed: usize,
+        zstore: &'a ZStore<F, C>,
+        cache: &mut FxHashSet<&'a ZPtr<F>>,
+    ) {
+        let mut zptr = env;
+        for _ in 0..num_disclosed {
+            let zptr_type = zstore
+                .dag
+                .get(zptr)
+                .expect("Data missing from ZStore's DAG");
+            let ZPtrType::Tuple110(var, val, tail) = zptr_type else {
+                panic!("Not enough bindings to disclose");
+            };
+            self.0.insert(*zptr, *zptr_type);
+            self.populate_with(var, zstore, cache);
+            self.populate_with(val, zstore, cache);
+            zptr = tail;
+        }
+        if let Some(ZPtrType::Atom) = zstore.dag.get(zptr) {
+            // the empty environment has nothing to hide
+            self.0.insert(*zptr, ZPtrType::Atom);
+            return;
+        }
+        while let Some(ZPtrType::Tuple110(_, _, tail)) = zstore.dag.get(zptr) {
+            cache.insert(zptr);
+            zptr = tail;
+        }
+    }
+
+    /// Returns the bindings of an environment whose spine is in the ZDag, along
+    /// with the remainder of the environment that's opaque, if any
+    pub(crate) fn env_bindings(&self, env: &ZPtr<F>) -> (Vec<(ZPtr<F>, ZPtr<F>)>, Option<ZPtr<F>>) {
+        let mut bindings = vec![];
+        let mut zptr = env;
+        loop {
+            match self.0.get(zptr) {
+                Some(ZPtrType::Tuple110(var, val, tail)) => {
+                    bindings.push((*var, *val));
+                    zptr = tail;
+                }
+                Some(_) => return (bindings, None),
+                None => return (bindings, Some(*zptr)),
+            }
+        }
+    }
+
+    /// Moves its data to a target ZStore
+    pub(crate) fn populate_zstore<C: Chipset<F>>(self, zstore: &mut ZStore<F, C>)
+    where
+        F: AbstractField + Copy,
+    {
+        for (zptr, zptr_type) in self.0 {
+            match &zptr_type {
+                ZPtrType::Atom => (),
+                ZPtrType::Tuple11(a, b) => {
+                    let preimg = ZPtr::flatten_as_tuple11(a, b);
+                    zstore.hashes4.insert(preimg, zptr.digest);
+                    zstore.hashes4_diff.insert(preimg, zptr.digest);
+                }
+                ZPtrType::Tuple100(a, b, c) => {
+                    let preimg = ZPtr::flatten_as_tuple100(a, b, c);
+                    zstore.hashes4.insert(preimg, zptr.digest);
+                    zstore.hashes4_diff.insert(preimg, zptr.digest);
+                }
+                ZPtrType::Tuple110(a, b, c) => {
+                    let preimg = ZPtr::flatten_as_tuple110(a, b, c);
+                    zstore.hashes5.insert(preimg, zptr.digest);
+                    zstore.hashes5_diff.insert(preimg, zptr.digest);
+                }
+            }
+            zstore.dag.insert(zptr, zptr_type);
+        }
+    }
+
+    /// Whether there's data missing in the ZDag or not
+    pub(crate) fn has_opaque_data(&self, zptr: &ZPtr<F>) -> bool {
+        match self.0.get(zptr) {
+            None => true,
+            Some(ZPtrType::Atom) => false,
+            Some(ZPtrType::Tuple11(a, b)) => self.has_opaque_data(a) || self.has_opaque_data(b),
+            Some(ZPtrType::Tuple100(a, b, c) | ZPtrType::Tuple110(a, b, c)) => {
+                self.has_opaque_data(a) || self.has_opaque_data(b) || self.has_opaque_data(c)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rustc_hash::FxHashSet;
+
+    use crate::lurk::{state::user_sym, zstore::lurk_zstore};
+
+    use super::ZDag;
+
+    #[test]
+    fn test_env_disclosure() {
+        let mut zstore = lurk_zstore();
+        let secret = zstore.intern_symbol_no_lang(&user_sym("secret"));
+        let public = zstore.intern_symbol_no_lang(&user_sym("public"));
+        let secret_val = zstore.intern_string("hunter2");
+        let public_val = zstore.intern_string("hello");
+        let empty_env = zstore.intern_empty_env();
+        let secret_env = zstore.intern_env(secret, secret_val, empty_env);
+        let env = zstore.intern_env(public, public_val, secret_env);
+
+        let mut zdag = ZDag::default();
+        zdag.populate_with_env(&env, 1, &zstore, &mut FxHashSet::default());
+        let (bindings, opaque_tail) = zdag.env_bindings(&env);
+        assert_eq!(bindings, vec![(public, public_val)]);
+        assert_eq!(opaque_tail, Some(secret_env));
+        assert!(!zdag.has_opaque_data(&public_val));
+        // no spine node of the hidden bindings is carried
+        assert!(zdag.has_opaque_data(&secret_env));
+        assert!(zdag.has_opaque_data(&secret));
+        assert!(zdag.has_opaque_data(&secret_val));
+
+        let mut zdag = ZDag::default();
+        zdag.populate_with_env(&env, 2, &zstore, &mut FxHashSet::default());
+        let (bindings, opaque_tail) = zdag.env_bindings(&env);
+        assert_eq!(bindings, vec![(public, public_val), (secret, secret_val)]);
+        assert_eq!(opaque_tail, None);
+        assert!(!zdag.has_opaque_data(&env));
+    }
+}
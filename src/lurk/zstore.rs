@@ -79,7 +79,7 @@ fn into_sized<F: AbstractField + Copy, const N: usize>(slice: &[F]) -> [F; N] {
     buffer.extract()
 }
 
-fn get_char<F: PrimeField32>(digest: &[F; DIGEST_SIZE]) -> char {
+pub(crate) fn get_char<F: PrimeField32>(digest: &[F; DIGEST_SIZE]) -> char {
     let u8s = digest.map(|f| f.as_canonical_u32().try_into().expect("Invalid char limb"));
     let (bytes, rest) = u8s.split_at(4);
     assert_eq!(rest, [0; 4]);
@@ -136,11 +136,19 @@ impl<F: AbstractField + Copy> ZPtr<F> {
         }
     }
 
+    #[inline]
+    pub fn i64(i: i64) -> Self {
+        Self {
+            tag: Tag::I64,
+            digest: i.to_le_bytes().map(F::from_canonical_u8),
+        }
+    }
+
     #[inline]
     pub fn err(err: EvalErr) -> Self {
         Self {
             tag: Tag::Err,
-            digest: digest_from_field(err.to_field()),
+            digest: digest_from_field(err.code().to_field()),
         }
     }
 
@@ -385,6 +393,11 @@ impl<F: Field, C: Chipset<F>> ZStore<F, C> {
         self.memoize_atom_dag(ZPtr::u64(u))
     }
 
+    #[inline]
+    pub fn intern_i64(&mut self, i: i64) -> ZPtr<F> {
+        self.memoize_atom_dag(ZPtr::i64(i))
+    }
+
     #[inline]
     pub fn intern_big_num(&mut self, c: [F; DIGEST_SIZE]) -> ZPtr<F> {
         self.memoize_atom_dag(ZPtr::big_num(c))
@@ -400,6 +413,15 @@ impl<F: Field, C: Chipset<F>> ZStore<F, C> {
         self.memoize_atom_dag(ZPtr::err(err))
     }
 
+    /// Errors with a `nil` payload are interned as payload-less errors
+    pub fn intern_error_with_payload(&mut self, err: EvalErr, payload: ZPtr<F>) -> ZPtr<F> {
+        if payload == self.nil {
+            return self.intern_error(err);
+        }
+        let code = self.intern_u64(err as u64);
+        self.intern_tuple11(Tag::Err, code, payload)
+    }
+
     pub fn intern_string(&mut self, s: &str) -> ZPtr<F> {
         if let Some(zptr) = self.str_cache.get(s).copied() {
             return zptr;
@@ -514,6 +536,7 @@ impl<F: Field, C: Chipset<F>> ZStore<F, C> {
             Syntax::Num(_, f) => self.intern_num(*f),
             Syntax::Char(_, c) => self.intern_char(*c),
             Syntax::U64(_, u) => self.intern_u64(*u),
+            Syntax::I64(_, i) => self.intern_i64(*i),
             Syntax::BigNum(_, c) => self.intern_big_num(*c),
             Syntax::Comm(_, c) => self.intern_comm(*c),
             Syntax::String(_, s) => self.intern_string(s),
@@ -538,7 +561,7 @@ impl<F: Field, C: Chipset<F>> ZStore<F, C> {
                 let x = self.intern_syntax(x, lang_symbols);
                 self.intern_list([quote, x])
             }
-            Syntax::I64(..) | Syntax::Meta(..) => panic!("not supported"),
+            Syntax::Meta(..) => panic!("not supported"),
         };
         self.syn_cache.insert(syn.clone(), zptr);
         zptr
@@ -689,7 +712,17 @@ impl<F: Field, C: Chipset<F>> ZStore<F, C> {
                 );
             }
             Tag::Sym | Tag::Key | Tag::Builtin | Tag::Coroutine => (), // these should be already memoized
-            Tag::Num | Tag::U64 | Tag::Char | Tag::Err | Tag::BigNum | Tag::Comm => {
+            Tag::Err if digest[1..] != zeros[1..] => {
+                let preimg = hashes4_inv.get(digest).expect("Hash4 preimg not found");
+                let (code, payload) = preimg.split_at(ZPTR_SIZE);
+                let (payload_tag, payload_digest) = payload.split_at(DIGEST_SIZE);
+                let code_digest = &code[DIGEST_SIZE..];
+                let payload_tag = Tag::from_field(&payload_tag[0]);
+                recurse!(Tag::U64, code_digest);
+                recurse!(payload_tag, payload_digest);
+                memoize_tuple11!(Tag::U64, code_digest, payload_tag, payload_digest);
+            }
+            Tag::Num | Tag::U64 | Tag::I64 | Tag::Char | Tag::Err | Tag::BigNum | Tag::Comm => {
                 self.memoize_atom_dag(ZPtr {
                     tag,
                     digest: into_sized(digest),
@@ -706,6 +739,20 @@ impl<F: Field, C: Chipset<F>> ZStore<F, C> {
         (a, b)
     }
 
+    /// Returns the error and its payload, if there's one
+    pub fn fetch_error(&self, zptr: &ZPtr<F>) -> (EvalErr, Option<&ZPtr<F>>)
+    where
+        F: PrimeField32,
+    {
+        assert_eq!(zptr.tag, Tag::Err);
+        match self.dag.get(zptr) {
+            Some(ZPtrType::Tuple11(code, payload)) => {
+                (EvalErr::from_code(&code.digest[0]), Some(payload))
+            }
+            _ => (EvalErr::from_code(&zptr.digest[0]), None),
+        }
+    }
+
     #[inline]
     pub fn fetch_tuple100(&self, zptr: &ZPtr<F>) -> (&ZPtr<F>, &ZPtr<F>, &ZPtr<F>) {
         let Some(ZPtrType::Tuple100(a, b, c)) = self.dag.get(zptr) else {
@@ -834,6 +881,13 @@ impl<F: Field, C: Chipset<F>> ZStore<F, C> {
                         .map(|f| u8::try_from(f.as_canonical_u32()).expect("invalid u64 limbs"))
                 )
             ),
+            Tag::I64 => format!(
+                "{}i64",
+                i64::from_le_bytes(
+                    zptr.digest
+                        .map(|f| u8::try_from(f.as_canonical_u32()).expect("invalid i64 limbs"))
+                )
+            ),
             Tag::Char => format!("'{}'", get_char(&zptr.digest)),
             Tag::BigNum => format!("#{:#x}", field_elts_to_biguint(&zptr.digest)),
             Tag::Comm => format!("#c{:#x}", field_elts_to_biguint(&zptr.digest)),
@@ -880,7 +934,12 @@ impl<F: Field, C: Chipset<F>> ZStore<F, C> {
                 let (body, ..) = self.fetch_tuple110(zptr);
                 format!("<Fix {}>", self.fmt_with_state(state, body))
             }
-            Tag::Err => format!("<Err {:?}>", EvalErr::from_field(&zptr.digest[0])),
+            Tag::Err => match self.fetch_error(zptr) {
+                (err, None) => format!("<Err {err:?}>"),
+                (err, Some(payload)) => {
+                    format!("<Err {err:?} {}>", self.fmt_with_state(state, payload))
+                }
+            },
         }
     }
 
@@ -908,6 +967,7 @@ mod test {
         lair::execute::QueryRecord,
         lurk::{
             chipset::lurk_hasher,
+            error::EvalErr,
             eval_direct::build_lurk_toplevel_native,
             state::{builtin_sym, user_sym, State},
             symbol::Symbol,
@@ -997,6 +1057,9 @@ mod test {
         let one_u64 = ZPtr::u64(1);
         assert_eq!(zstore.fmt_with_state(state, &one_u64), "1");
 
+        let minus_one_i64 = ZPtr::i64(-1);
+        assert_eq!(zstore.fmt_with_state(state, &minus_one_i64), "-1i64");
+
         let zero_big_num = ZPtr::big_num([BabyBear::zero(); 8]);
         assert_eq!(zstore.fmt_with_state(state, &zero_big_num), "#0x0");
 
@@ -1049,5 +1112,13 @@ mod test {
         assert_eq!(zstore.fmt_with_state(state, &empty_env), "<Env ()>");
         let env = zstore.intern_env(x, one, empty_env);
         assert_eq!(zstore.fmt_with_state(state, &env), "<Env ((x . 1n))>");
+
+        let err = zstore.intern_error(EvalErr::DivByZero);
+        assert_eq!(zstore.fmt_with_state(state, &err), "<Err DivByZero>");
+        let nil = *zstore.nil();
+        let err = zstore.intern_error_with_payload(EvalErr::Fail, nil);
+        assert_eq!(err, ZPtr::err(EvalErr::Fail));
+        let err = zstore.intern_error_with_payload(EvalErr::Fail, abc_str);
+        assert_eq!(zstore.fmt_with_state(state, &err), "<Err Fail \"abc\">");
     }
 }
@@ -126,15 +126,10 @@ fn transform_lambda<F: Field, C1: Chipset<F>>(
             zstore.intern_symbol_no_lang(&sym)
         }
         LambdaSyntax::Int(_, sign, i) => {
-            // TODO: output i63s
-            if *sign {
-                let minus = zstore.intern_symbol_no_lang(&builtin_sym("-"));
-                let zero = zstore.intern_u64(0);
-                let i = zstore.intern_u64(*i);
-                zstore.intern_list([minus, zero, i])
-            } else {
-                zstore.intern_u64(*i)
-            }
+            // OCaml's 63-bit ints are represented as `I64`s, so overflows wrap
+            // at 64 bits instead
+            let i = *i as i64;
+            zstore.intern_i64(if *sign { i.wrapping_neg() } else { i })
         }
         LambdaSyntax::Float(_, _) => {
             // We do not support floats, but emit a `(fail)` so we still generate something
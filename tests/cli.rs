@@ -0,0 +1,18 @@
+// Runs the `lurk` binary to check what reaches its standard output
+use std::process::Command;
+
+#[test]
+fn test_eval_json_output() {
+    let output = Command::new(env!("CARGO_BIN_EXE_lurk"))
+        .args(["eval", "--output", "json", "(+ 1 2) \"hi\" (cons 1 2)"])
+        .output()
+        .expect("Failed to run lurk");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let results = stdout
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("Non-JSON output"))
+        .collect::<Vec<_>>();
+    assert_eq!(results.len(), 3);
+    assert!(results.iter().all(|value| value.get("result").is_some()));
+}